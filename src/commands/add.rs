@@ -1,20 +1,22 @@
 use anyhow::{bail, Context, Result};
 use std::fs;
 use std::path::Path;
-use std::process::Command;
 
-pub fn run(packages: Vec<String>) -> Result<()> {
-    if packages.is_empty() {
-        bail!("No packages specified. Usage: zora add <package1> <package2> ...");
-    }
+use crate::config::ProjectConfig;
+
+/// Table headers dependencies go under, preferred name first.
+const DEPS_SECTIONS: &[&str] = &["deps"];
+const DEV_DEPS_SECTIONS: &[&str] = &["dev-dependencies", "dev-deps", "dev_deps"];
 
-    // Check if vcpkg is installed
-    let vcpkg_check = Command::new("vcpkg")
-        .arg("version")
-        .output();
+pub fn run(packages: Vec<String>, submodule: Option<String>, dev: bool) -> Result<()> {
+    let sections = if dev { DEV_DEPS_SECTIONS } else { DEPS_SECTIONS };
 
-    if vcpkg_check.is_err() {
-        bail!("vcpkg not found. Please install vcpkg and ensure it's in your PATH.\nSee: https://vcpkg.io/en/getting-started.html");
+    if let Some(url) = submodule {
+        return add_submodule(&url, &packages, sections);
+    }
+
+    if packages.is_empty() {
+        bail!("No packages specified. Usage: zora add <package1> <package2> ...");
     }
 
     // Check if project.toml exists
@@ -26,40 +28,73 @@ pub fn run(packages: Vec<String>) -> Result<()> {
     let project_toml = fs::read_to_string("project.toml")
         .context("failed to read project.toml")?;
 
-    // Install each package with vcpkg
-    for package in &packages {
-        println!("📦 Installing {} via vcpkg...", package);
-        
-        let status = Command::new("vcpkg")
-            .args(&["install", package])
-            .status()
-            .context(format!("failed to install package: {}", package))?;
-
-        if !status.success() {
-            bail!("Failed to install package: {}", package);
-        }
+    // Update project.toml with new dependencies
+    let entries: Vec<(String, String)> = packages.iter()
+        .map(|package| (package.clone(), "\"*\"".to_string()))
+        .collect();
+    let updated_toml = add_dependencies_to_toml(&project_toml, sections, &entries)?;
+    fs::write("project.toml", updated_toml)
+        .context("failed to write updated project.toml")?;
+
+    println!("✔ Added {} package(s) to project.toml", packages.len());
 
-        println!("✔ Installed {}", package);
+    // vcpkg installs them from the manifest when the build configures
+    let config = ProjectConfig::load()?;
+    if config.use_vcpkg && crate::vcpkg::sync_manifest(&config)? {
+        println!("✔ Updated {}", crate::vcpkg::MANIFEST_FILE);
+    }
+    if crate::lockfile::update(&config)? {
+        println!("✔ Updated {}", crate::lockfile::LOCK_FILE);
+    }
+    if dev {
+        println!("They're used by 'zora test' and 'zora bench' only.");
+    } else {
+        println!("Run 'zora build' to install them and rebuild.");
     }
 
-    // Update project.toml with new dependencies
-    let updated_toml = add_dependencies_to_toml(&project_toml, &packages)?;
+    Ok(())
+}
+
+/// Adds a dependency tracked as a git submodule and records it as a path dep.
+fn add_submodule(url: &str, args: &[String], sections: &[&str]) -> Result<()> {
+    let [path] = args else {
+        bail!("Usage: zora add --submodule <url> <path>");
+    };
+
+    if !Path::new("project.toml").exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("submodule path must end in a directory name")?
+        .to_string();
+
+    println!("📦 Adding {} as a submodule at {}...", url, path);
+    crate::git::submodule_add(url, path)?;
+
+    let project_toml = fs::read_to_string("project.toml")
+        .context("failed to read project.toml")?;
+    let entry = (name.clone(), format!("{{ path = \"{}\" }}", path));
+    let updated_toml = add_dependencies_to_toml(&project_toml, sections, &[entry])?;
     fs::write("project.toml", updated_toml)
         .context("failed to write updated project.toml")?;
 
-    println!("\n✔ Added {} package(s) to project.toml", packages.len());
-    println!("Run 'zora build' to rebuild with new dependencies.");
-
+    println!("✔ Added {} to project.toml", name);
     Ok(())
 }
 
-fn add_dependencies_to_toml(toml_content: &str, packages: &[String]) -> Result<String> {
+/// Appends `name = value` lines to the first of `sections` present (e.g.
+/// [deps]), where `value` is a TOML expression.
+fn add_dependencies_to_toml(toml_content: &str, sections: &[&str], packages: &[(String, String)]) -> Result<String> {
     let mut lines: Vec<String> = toml_content.lines().map(|s| s.to_string()).collect();
     
     // Find the [deps] section
     let mut deps_index = None;
     for (i, line) in lines.iter().enumerate() {
-        if line.trim() == "[deps]" {
+        let header = line.trim();
+        if sections.iter().any(|section| header == format!("[{}]", section)) {
             deps_index = Some(i);
             break;
         }
@@ -70,7 +105,7 @@ fn add_dependencies_to_toml(toml_content: &str, packages: &[String]) -> Result<S
         None => {
             // If [deps] section doesn't exist, add it at the end
             lines.push(String::new());
-            lines.push("[deps]".to_string());
+            lines.push(format!("[{}]", sections[0]));
             lines.len() - 1
         }
     };
@@ -106,8 +141,8 @@ fn add_dependencies_to_toml(toml_content: &str, packages: &[String]) -> Result<S
         .collect();
 
     // Add new packages that aren't already listed
-    for package in packages {
-        let dep_line = format!("{} = \"*\"", package);
+    for (package, value) in packages {
+        let dep_line = format!("{} = {}", package, value);
         if !existing_deps.iter().any(|d| d.starts_with(package)) {
             lines.insert(insert_index, dep_line);
             insert_index += 1;
@@ -0,0 +1,155 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use walkdir::WalkDir;
+
+use crate::config::ProjectConfig;
+use crate::output::{command_line, Verbosity};
+
+/// Compiles every source with the compiler's built-in static analyzer
+/// (`--analyze` for clang, `-fanalyzer` for GCC 10+) and reports its
+/// findings grouped by file. Nothing is linked or copied to target/.
+pub fn run(mode: &str, features: &[String], verbosity: Verbosity) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let compiler = config.compiler();
+    let analyzer_flags = analyzer_flags(&config, &compiler)?;
+
+    println!("{} with {}...", "Analyzing".bright_cyan(), compiler);
+
+    let inactive = config.excluded_sources(&config.enabled_features(features)?)?;
+    let mut source_files = vec![];
+    for source_dir in &config.sources.dirs {
+        for entry in WalkDir::new(source_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            let is_source = path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| config.source_extensions().contains(&ext))
+                .unwrap_or(false);
+            let relative = path.strip_prefix(".").unwrap_or(path);
+            if path.is_file() && is_source && !inactive.iter().any(|p| p == relative) {
+                source_files.push(path.to_path_buf());
+            }
+        }
+    }
+
+    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+
+    // Findings keyed by the file they're reported in, which may be a header
+    let mut findings: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut failed: Vec<PathBuf> = vec![];
+
+    for source_file in &source_files {
+        let mut cmd = Command::new(&compiler);
+        cmd.args(config.compile_flags(mode))
+            .args(config.stdlib_flags(&compiler)?)
+            .args(&analyzer_flags)
+            .arg("-c")
+            .arg(source_file)
+            .arg("-o")
+            .arg(null_device);
+
+        if verbosity.commands() {
+            println!("  {} {}", "Running".dimmed(), command_line(&cmd));
+        }
+
+        let output = cmd.output()
+            .with_context(|| format!("failed to run {}", compiler))?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        if verbosity.tool_output() {
+            eprint!("{}", stderr);
+        }
+
+        for line in stderr.lines() {
+            if let Some((file, message)) = diagnostic(line) {
+                let messages = findings.entry(file.to_string()).or_default();
+                if !messages.iter().any(|m| m == message) {
+                    messages.push(message.to_string());
+                }
+            }
+        }
+
+        if !output.status.success() {
+            failed.push(source_file.clone());
+        }
+    }
+
+    for (file, messages) in &findings {
+        println!("\n{} ({})", file.bright_white().bold(), messages.len());
+        for message in messages {
+            println!("  {} {}", "→".yellow(), message);
+        }
+    }
+
+    for source_file in &failed {
+        println!("  {} {} failed to compile", "✗".red(), source_file.display());
+    }
+
+    let total: usize = findings.values().map(Vec::len).sum();
+    println!();
+    if total > 0 || !failed.is_empty() {
+        bail!("{} analyzer finding(s) in {} file(s)", total, findings.len());
+    }
+
+    println!("{} No analyzer findings in {} source file(s)",
+        "✓".green().bold(),
+        source_files.len()
+    );
+    Ok(())
+}
+
+/// The analyzer flags the compiler supports: clang's `--analyze` with text
+/// output, or GCC's `-fanalyzer`, probed by compiling an empty file.
+fn analyzer_flags(config: &ProjectConfig, compiler: &str) -> Result<Vec<String>> {
+    let version = Command::new(compiler)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Compiler '{}' not found", compiler))?;
+
+    if String::from_utf8_lossy(&version.stdout).contains("clang") {
+        return Ok(vec![
+            "--analyze".to_string(),
+            "-Xanalyzer".to_string(),
+            "-analyzer-output=text".to_string(),
+        ]);
+    }
+
+    let language = if config.is_cpp() { "c++" } else { "c" };
+    let null_device = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    let probe = Command::new(compiler)
+        .args(["-fanalyzer", "-x", language, "-c", "-", "-o", null_device])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .and_then(|mut child| {
+            child.stdin.take().map(|mut stdin| stdin.write_all(b"int zora_probe;\n"));
+            child.wait()
+        });
+
+    match probe {
+        Ok(status) if status.success() => Ok(vec!["-fanalyzer".to_string()]),
+        _ => bail!("{} has no built-in static analyzer. Use clang or GCC 10 or newer.", compiler),
+    }
+}
+
+/// Splits a `file:line:col: warning: message` line into the file and the
+/// `line:col: warning: message` rest. Notes and other continuation lines
+/// aren't findings.
+fn diagnostic(line: &str) -> Option<(&str, &str)> {
+    let (file, rest) = line.split_once(':')?;
+    if file.is_empty() || !(rest.contains(": warning: ") || rest.contains(": error: ")) {
+        return None;
+    }
+    Some((file, rest))
+}
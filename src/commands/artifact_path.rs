@@ -0,0 +1,17 @@
+use anyhow::{bail, Result};
+
+use crate::config::ProjectConfig;
+
+/// Prints the path of a built artifact, for scripts and deploy pipelines.
+pub fn run(name: Option<String>, release: bool, build_dir: Option<String>) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let slot = build_dir.unwrap_or_else(|| if release { "release" } else { "dev" }.to_string());
+    let path = super::build::artifact_path(&config, name.as_deref(), &slot)?;
+
+    println!("{}", path.display());
+    Ok(())
+}
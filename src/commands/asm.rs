@@ -0,0 +1,99 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::ProjectConfig;
+
+pub fn run(file: String, ir: bool, output: Option<String>) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    if !Path::new(&file).exists() {
+        bail!("File not found: {}", file);
+    }
+
+    let config = ProjectConfig::load()?;
+
+    // LLVM IR can only come from clang
+    let compiler = if ir && !config.compiler().contains("clang") {
+        if config.is_cpp() { "clang++".to_string() } else { "clang".to_string() }
+    } else {
+        config.compiler()
+    };
+
+    let mut cmd = Command::new(&compiler);
+    cmd.args(config.compile_flags("release"))
+        .args(config.stdlib_flags(&compiler)?)
+        .arg("-S");
+
+    if ir {
+        cmd.arg("-emit-llvm");
+    }
+
+    cmd.arg(&file).arg("-o").arg("-");
+
+    let result = cmd.output()
+        .with_context(|| format!("failed to run {}", compiler))?;
+
+    if !result.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&result.stderr));
+        bail!("Failed to compile {}", file);
+    }
+
+    let mut listing = String::from_utf8_lossy(&result.stdout).into_owned();
+
+    if config.is_cpp() {
+        if let Some(demangled) = demangle(&listing) {
+            listing = demangled;
+        }
+    }
+
+    match output {
+        Some(path) => {
+            fs::write(&path, listing)
+                .with_context(|| format!("failed to write {}", path))?;
+            println!("{} Wrote {} for {} to {}",
+                "✓".green().bold(),
+                if ir { "LLVM IR" } else { "assembly" },
+                file,
+                path
+            );
+        }
+        None => print!("{}", listing),
+    }
+
+    Ok(())
+}
+
+/// Pipes the listing through the first available C++ demangler.
+fn demangle(listing: &str) -> Option<String> {
+    for tool in ["c++filt", "llvm-cxxfilt"] {
+        let child = Command::new(tool)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn();
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+
+        // Written from another thread: a listing bigger than the pipe
+        // buffer would otherwise block both sides
+        let mut stdin = child.stdin.take()?;
+        let input = listing.to_string();
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let output = child.wait_with_output().ok()?;
+        writer.join().ok()?.ok()?;
+
+        if output.status.success() {
+            return Some(String::from_utf8_lossy(&output.stdout).into_owned());
+        }
+    }
+
+    None
+}
@@ -1,12 +1,24 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::collections::BTreeMap;
+use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
+use super::build::BuildOptions;
 use crate::config::ProjectConfig;
+use crate::{git, paths};
+
+const BENCH_DIR: &str = "benches";
+
+/// Runs of each benchmark when comparing; the fastest one is reported.
+const COMPARE_RUNS: usize = 5;
+
+/// Changes smaller than this percentage are treated as noise.
+const NOISE_PERCENT: f64 = 2.0;
 
 pub fn run(specific_bench: Option<String>) -> Result<()> {
     if !ProjectConfig::exists() {
@@ -14,23 +26,171 @@ pub fn run(specific_bench: Option<String>) -> Result<()> {
     }
 
     let config = ProjectConfig::load()?;
-    let bench_dir = "benches";
+    let root = Path::new(".");
 
-    if !Path::new(bench_dir).exists() {
+    if !root.join(BENCH_DIR).exists() {
         println!("{}", "No benchmarks directory found".yellow());
-        println!("Create benchmarks in the 'benches/' directory");
+        println!("Create benchmarks in the '{}/' directory", BENCH_DIR);
         return Ok(());
     }
 
     println!("{}", "Running benchmarks...".bright_cyan());
 
+    let bench_files = find_bench_files(root, specific_bench.as_deref());
+    if bench_files.is_empty() {
+        println!("{}", "No benchmark files found".yellow());
+        return Ok(());
+    }
+
+    crate::vcpkg::install_dev_deps(&config)?;
+    let output_dir = paths::target_root().join("benches");
+    fs::create_dir_all(&output_dir)?;
+
+    for bench_file in bench_files {
+        let bench_name = bench_file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
+        let output_file = output_dir.join(bench_name);
+
+        if !compile_bench(&config, root, &bench_file, &output_file)? {
+            println!("  {} Compilation failed for {}", "✗".red(), bench_name);
+            continue;
+        }
+
+        // Run benchmark
+        println!("\n{} {}...", "Benchmarking".bright_blue(), bench_name);
+        let start = Instant::now();
+        Command::new(&output_file).status()?;
+        let duration = start.elapsed();
+        
+        println!("  Time: {:.2?}", duration);
+    }
+
+    Ok(())
+}
+
+/// Benchmarks `reference`, checked out into a temporary git worktree so the
+/// working tree is left alone, then the working tree, and prints the change
+/// per benchmark.
+pub fn compare(reference: &str, specific_bench: Option<String>) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let worktree = env::current_dir()?.join(paths::build_root()).join("bench-compare");
+    if worktree.exists() {
+        // Left behind by an interrupted comparison
+        let _ = git::worktree_remove(&worktree);
+        if worktree.exists() {
+            fs::remove_dir_all(&worktree)
+                .with_context(|| format!("failed to remove {}", worktree.display()))?;
+        }
+    }
+
+    crate::vcpkg::install_dev_deps(&ProjectConfig::load()?)?;
+
+    println!("{} {}...", "Checking out".bright_cyan(), reference);
+    git::worktree_add(&worktree, reference)?;
+
+    println!("\n{} {}...", "Benchmarking".bright_cyan(), reference);
+    let baseline = time_benches(&worktree, "baseline", specific_bench.as_deref());
+    let removed = git::worktree_remove(&worktree);
+    let baseline = baseline?;
+    removed?;
+
+    println!("\n{} working tree...", "Benchmarking".bright_cyan());
+    let current = time_benches(Path::new("."), "current", specific_bench.as_deref())?;
+
+    let mut names: Vec<&String> = baseline.keys().chain(current.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    if names.is_empty() {
+        println!("{}", "No benchmark files found".yellow());
+        return Ok(());
+    }
+
+    let format_time = |time: Option<&Option<Duration>>| match time {
+        Some(Some(time)) => format!("{:.2?}", time),
+        Some(None) => "failed".to_string(),
+        None => "-".to_string(),
+    };
+
+    println!("\n{:<24} {:>12} {:>12} {:>9}", "Benchmark", reference, "current", "change");
+    println!("{}", "─".repeat(60));
+    for name in names {
+        let before = baseline.get(name);
+        let after = current.get(name);
+
+        let change = match (before, after) {
+            (Some(Some(before)), Some(Some(after))) if !before.is_zero() => {
+                let percent = (after.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+                let text = format!("{:+.1}%", percent);
+                if percent <= -NOISE_PERCENT {
+                    text.green().to_string()
+                } else if percent >= NOISE_PERCENT {
+                    text.red().to_string()
+                } else {
+                    text.dimmed().to_string()
+                }
+            }
+            _ => String::new(),
+        };
+
+        println!("{:<24} {:>12} {:>12} {:>9}", name, format_time(before), format_time(after), change);
+    }
+
+    Ok(())
+}
+
+/// Compiles and times every benchmark under `root`, keeping the fastest of
+/// `COMPARE_RUNS` runs. `None` marks a benchmark that didn't compile or run.
+fn time_benches(root: &Path, label: &str, filter: Option<&str>) -> Result<BTreeMap<String, Option<Duration>>> {
+    let config = ProjectConfig::load_from(root)?;
+    let output_dir = env::current_dir()?.join(paths::target_root()).join("benches").join(label);
+    fs::create_dir_all(&output_dir)?;
+
+    let mut times = BTreeMap::new();
+    for bench_file in find_bench_files(root, filter) {
+        let bench_name = bench_file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown").to_string();
+        let output_file = output_dir.join(&bench_name);
+
+        if !compile_bench(&config, root, &bench_file, &output_file)? {
+            println!("  {} Compilation failed for {}", "✗".red(), bench_name);
+            times.insert(bench_name, None);
+            continue;
+        }
+
+        let mut fastest: Option<Duration> = None;
+        for _ in 0..COMPARE_RUNS {
+            let start = Instant::now();
+            let output = Command::new(&output_file).current_dir(root).output()?;
+            let elapsed = start.elapsed();
+
+            if !output.status.success() {
+                println!("  {} {} exited with code {}", "✗".red(), bench_name, output.status.code().unwrap_or(-1));
+                fastest = None;
+                break;
+            }
+            fastest = Some(fastest.map_or(elapsed, |f| f.min(elapsed)));
+        }
+
+        if let Some(time) = fastest {
+            println!("  {} {} {:.2?}", "✓".green(), bench_name, time);
+        }
+        times.insert(bench_name, fastest);
+    }
+
+    Ok(times)
+}
+
+/// C and C++ sources under `root`/benches whose name contains `filter`.
+fn find_bench_files(root: &Path, filter: Option<&str>) -> Vec<PathBuf> {
     let mut bench_files = vec![];
-    for entry in WalkDir::new(bench_dir).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(root.join(BENCH_DIR)).into_iter().filter_map(|e| e.ok()) {
         let path = entry.path();
         if path.is_file() {
             if let Some(ext) = path.extension() {
                 if ext == "c" || ext == "cpp" {
-                    if let Some(bench_name) = &specific_bench {
+                    if let Some(bench_name) = filter {
                         if path.file_stem()
                             .and_then(|s| s.to_str())
                             .map(|s| s.contains(bench_name))
@@ -45,43 +205,113 @@ pub fn run(specific_bench: Option<String>) -> Result<()> {
             }
         }
     }
+    bench_files
+}
+
+/// Compiles a benchmark with the headers of the project at `root`. False
+/// when it doesn't compile.
+fn compile_bench(config: &ProjectConfig, root: &Path, bench_file: &Path, output_file: &Path) -> Result<bool> {
+    let bench_file = bench_file.strip_prefix(root).unwrap_or(bench_file);
+    let compiler = if config.is_cpp() { "g++" } else { "gcc" };
+    let status = Command::new(compiler)
+        .current_dir(root)
+        .arg(bench_file)
+        .arg("-o")
+        .arg(output_file)
+        .arg("-O3")
+        .arg("-I")
+        .arg("include")
+        .args(crate::vcpkg::package_flags(&config.dev_vcpkg_packages()))
+        .status()?;
+
+    Ok(status.success())
+}
+
+/// Benchmarks the release executable end to end, under hyperfine when it's
+/// installed and with a simple repeated timer otherwise.
+pub fn run_program(warmup: usize, runs: usize, args: Vec<String>) -> Result<()> {
+    if runs == 0 {
+        bail!("--runs must be at least 1");
+    }
 
-    if bench_files.is_empty() {
-        println!("{}", "No benchmark files found".yellow());
-        return Ok(());
+    println!("{}", "Building project...".bright_cyan());
+    super::build::run(BuildOptions::new("release"))?;
+
+    let exe_path = super::build::get_executable_path(None, "release")?;
+    if !exe_path.exists() {
+        bail!("Executable not found at: {}", exe_path.display());
     }
 
-    for bench_file in bench_files {
-        let bench_name = bench_file.file_stem().and_then(|s| s.to_str()).unwrap_or("unknown");
-        
-        // Compile benchmark
-        let output_dir = "target/benches";
-        fs::create_dir_all(output_dir)?;
-        let output_file = format!("{}/{}", output_dir, bench_name);
-        
-        let compiler = if config.is_cpp() { "g++" } else { "gcc" };
-        let status = Command::new(compiler)
-            .arg(&bench_file)
-            .arg("-o")
-            .arg(&output_file)
-            .arg("-O3")
-            .arg("-I")
-            .arg("include")
+    let has_hyperfine = Command::new("hyperfine")
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    println!("\n{} {}...\n", "Benchmarking".bright_blue(), exe_path.display());
+
+    if has_hyperfine {
+        let mut command_line = vec![shell_quote(&exe_path.to_string_lossy())];
+        command_line.extend(args.iter().map(|a| shell_quote(a)));
+
+        let status = Command::new("hyperfine")
+            .arg("--warmup").arg(warmup.to_string())
+            .arg("--runs").arg(runs.to_string())
+            .arg(command_line.join(" "))
             .status()?;
 
         if !status.success() {
-            println!("  {} Compilation failed for {}", "✗".red(), bench_name);
-            continue;
+            bail!("hyperfine failed");
         }
 
-        // Run benchmark
-        println!("\n{} {}...", "Benchmarking".bright_blue(), bench_name);
-        let start = Instant::now();
-        Command::new(&output_file).status()?;
-        let duration = start.elapsed();
-        
-        println!("  Time: {:.2?}", duration);
+        return Ok(());
     }
 
+    println!("{}", "hyperfine not found; using the built-in timer".dimmed());
+
+    for _ in 0..warmup {
+        run_once(&exe_path, &args)?;
+    }
+
+    let mut times = vec![];
+    for _ in 0..runs {
+        times.push(run_once(&exe_path, &args)?);
+    }
+
+    let secs: Vec<f64> = times.iter().map(Duration::as_secs_f64).collect();
+    let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+    let variance = secs.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+
+    println!("  Time (mean ± σ): {:.2?} ± {:.2?}",
+        Duration::from_secs_f64(mean),
+        Duration::from_secs_f64(variance.sqrt())
+    );
+    println!("  Range (min … max): {:.2?} … {:.2?}    {} runs",
+        times.iter().min().unwrap(),
+        times.iter().max().unwrap(),
+        runs
+    );
+
     Ok(())
 }
+
+fn run_once(exe_path: &Path, args: &[String]) -> Result<Duration> {
+    let start = Instant::now();
+    let output = Command::new(exe_path).args(args).output()?;
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        bail!("Program exited with error code: {}", output.status.code().unwrap_or(-1));
+    }
+
+    Ok(elapsed)
+}
+
+/// Quotes an argument for the shell hyperfine runs commands through.
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && arg.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=:,".contains(c)) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
@@ -1,17 +1,25 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::{Duration, UNIX_EPOCH};
 use tera::{Context as TeraContext, Tera};
+use walkdir::WalkDir;
 
-use crate::config::ProjectConfig;
+use crate::config::{BuildDependency, CopyRule, ProjectConfig, Toolchain};
+use crate::output::{command_line, run_tool, stream_tool, trace, Verbosity};
+use crate::paths;
 
-// Add BuildMode enum
-#[derive(Debug, Clone, Copy)]
+/// The built-in profiles, for commands that only take `--release`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum BuildMode {
+    #[default]
     Dev,
     Release,
 }
@@ -35,13 +43,20 @@ impl From<&str> for BuildMode {
 }
 
 const PROJECT_CMAKE_TEMPLATE: &str = r#"
-cmake_minimum_required(VERSION 3.10)
-project({{ name }} {{ language }})
+cmake_minimum_required(VERSION {% if is_objc or unity_batch_size is defined %}3.16{% else %}3.10{% endif %})
+
+set(ZORA_PROJECT_ROOT "{{ project_root }}")
 
 {% if use_vcpkg %}
+# Manifest mode: the toolchain installs what the project's vcpkg.json lists
+# into .build/vcpkg_installed while configuring. Only read before project().
+set(VCPKG_MANIFEST_DIR "${ZORA_PROJECT_ROOT}" CACHE PATH "Directory holding vcpkg.json")
+set(VCPKG_INSTALLED_DIR "${CMAKE_CURRENT_SOURCE_DIR}/../vcpkg_installed" CACHE PATH "vcpkg installed tree")
 set(CMAKE_TOOLCHAIN_FILE "$ENV{VCPKG_ROOT}/scripts/buildsystems/vcpkg.cmake" CACHE STRING "Vcpkg toolchain file")
 {% endif %}
 
+project({{ name }} {{ languages | join(sep=" ") }})
+
 {% if static_link %}
 # Static linking configuration
 set(CMAKE_FIND_LIBRARY_SUFFIXES ".a")
@@ -49,42 +64,125 @@ set(BUILD_SHARED_LIBS OFF)
 set(CMAKE_EXE_LINKER_FLAGS "${CMAKE_EXE_LINKER_FLAGS} -static")
 {% endif %}
 
+# Optimization from project.toml instead of CMake's build type defaults
+{% for lang in languages %}
+set(CMAKE_{{ lang }}_FLAGS_{{ build_type }} "{{ build_type_flags }}")
+{% endfor %}
+
+{% if linker %}
+set(CMAKE_EXE_LINKER_FLAGS "${CMAKE_EXE_LINKER_FLAGS} -fuse-ld={{ linker }}")
+set(CMAKE_SHARED_LINKER_FLAGS "${CMAKE_SHARED_LINKER_FLAGS} -fuse-ld={{ linker }}")
+{% endif %}
+
 {% if cpp_std %}
 set(CMAKE_CXX_STANDARD {{ cpp_std }})
 set(CMAKE_CXX_STANDARD_REQUIRED ON)
+{% if gnu_extensions is defined %}
+set(CMAKE_CXX_EXTENSIONS {% if gnu_extensions %}ON{% else %}OFF{% endif %})
+{% endif %}
 {% endif %}
 
 {% if c_std %}
 set(CMAKE_C_STANDARD {{ c_std }})
 set(CMAKE_C_STANDARD_REQUIRED ON)
+{% if gnu_extensions is defined %}
+set(CMAKE_C_EXTENSIONS {% if gnu_extensions %}ON{% else %}OFF{% endif %})
+{% endif %}
 {% endif %}
 
+{% if sorted_sources is defined %}
+set(SOURCES
+{% for source in sorted_sources %}
+    "${ZORA_PROJECT_ROOT}/{{ source }}"
+{% endfor %}
+)
+{% else %}
 file(GLOB_RECURSE SOURCES 
 {% for source_dir in source_dirs %}
-    "${PROJECT_SOURCE_DIR}/../../{{ source_dir }}/*.c"
-    "${PROJECT_SOURCE_DIR}/../../{{ source_dir }}/*.cpp"
+{% for ext in source_extensions %}
+    "${ZORA_PROJECT_ROOT}/{{ source_dir }}/*.{{ ext }}"
+{% endfor %}
 {% endfor %}
 )
 
+{% for bin in bins %}
+list(FILTER SOURCES EXCLUDE REGEX "{{ bin.regex }}")
+{% endfor %}
+
+{% for regex in inactive_sources %}
+list(FILTER SOURCES EXCLUDE REGEX "{{ regex }}")
+{% endfor %}
+{% endif %}
+
+{% if checks %}
+include(CheckIncludeFile{% if language == "CXX" %}CXX{% endif %})
+include(CheckFunctionExists)
+include(CheckSymbolExists)
+{% for check in checks %}
+{% if check.kind == "header" %}
+check_include_file{% if language == "CXX" %}_cxx{% endif %}("{{ check.name }}" {{ check.macro_name }})
+{% elif check.kind == "function" %}
+check_function_exists({{ check.name }} {{ check.macro_name }})
+{% else %}
+check_symbol_exists({{ check.name }} "{{ check.header }}" {{ check.macro_name }})
+{% endif %}
+{% endfor %}
+configure_file("${PROJECT_SOURCE_DIR}/config.h.in" "${PROJECT_BINARY_DIR}/generated/config.h")
+{% endif %}
+
+{% if unity_batch_size is defined %}
+# Unity build: sources are compiled in concatenated batches
+set(CMAKE_UNITY_BUILD ON)
+{% endif %}
+
+# Throttle links separately from compiles (honored by the Ninja generator).
+# zora passes ZORA_LINK_JOBS when configuring; it has to be set before the
+# targets are created for them to pick up the pool.
+if(ZORA_LINK_JOBS)
+    set_property(GLOBAL APPEND PROPERTY JOB_POOLS zora_link_pool=${ZORA_LINK_JOBS})
+    set(CMAKE_JOB_POOL_LINK zora_link_pool)
+endif()
+
 {% if is_library %}
 add_library({{ name }} {% if static_link %}STATIC{% endif %} ${SOURCES})
 {% else %}
 add_executable({{ name }} ${SOURCES})
 {% endif %}
 
-{% for include_dir in include_dirs %}
-target_include_directories({{ name }} PRIVATE "${PROJECT_SOURCE_DIR}/../../{{ include_dir }}")
+{% for bin in bins %}
+add_executable({{ bin.name }} "${ZORA_PROJECT_ROOT}/{{ bin.path }}")
+{% if is_library %}
+target_link_libraries({{ bin.name }} PRIVATE {{ name }})
+{% endif %}
 {% endfor %}
 
 {% if vcpkg_packages %}
 {% for package in vcpkg_packages %}
 find_package({{ package }} REQUIRED)
-target_link_libraries({{ name }} PRIVATE {{ package }}::{{ package }})
+{% endfor %}
+{% endif %}
+
+# Every target, [[bin]]s included, is compiled and linked the same way
+{% for target in [name] | concat(with=bin_names) %}
+{% for include_dir in include_dirs %}
+target_include_directories({{ target }} PRIVATE "${ZORA_PROJECT_ROOT}/{{ include_dir }}")
+{% endfor %}
+{% for include_dir in system_include_dirs %}
+target_include_directories({{ target }} SYSTEM PRIVATE "${ZORA_PROJECT_ROOT}/{{ include_dir }}")
+{% endfor %}
+
+{% if checks %}
+target_include_directories({{ target }} PRIVATE "${PROJECT_BINARY_DIR}/generated")
+{% endif %}
+
+{% if vcpkg_packages %}
+{% for package in vcpkg_packages %}
+target_link_libraries({{ target }} PRIVATE {{ package }}::{{ package }})
 {% endfor %}
 {% endif %}
 
 {% if build_flags %}
-target_compile_options({{ name }} PRIVATE 
+target_compile_options({{ target }} PRIVATE 
 {% for flag in build_flags %}
     "{{ flag }}"
 {% endfor %}
@@ -93,47 +191,441 @@ target_compile_options({{ name }} PRIVATE
 
 {% if defines %}
 {% for key, value in defines %}
-target_compile_definitions({{ name }} PRIVATE {{ key }}={{ value }})
+target_compile_definitions({{ target }} PRIVATE {{ key }}={{ value }})
 {% endfor %}
 {% endif %}
 
 {% if link_libs %}
-target_link_libraries({{ name }} PRIVATE 
+target_link_libraries({{ target }} PRIVATE 
 {% for lib in link_libs %}
     {{ lib }}
 {% endfor %}
 )
 {% endif %}
 
+{% for library in path_dep_libs %}
+target_link_libraries({{ target }} PRIVATE "{{ library }}")
+{% endfor %}
+
+{% if frameworks %}
+if(APPLE)
+{% for framework in frameworks %}
+    target_link_libraries({{ target }} PRIVATE "-framework {{ framework }}")
+{% endfor %}
+endif()
+{% endif %}
+
 {% if lib_dirs %}
 {% for lib_dir in lib_dirs %}
-target_link_directories({{ name }} PRIVATE "{{ lib_dir }}")
+target_link_directories({{ target }} PRIVATE "{{ lib_dir }}")
+{% endfor %}
+{% endif %}
+{% endfor %}
+
+{% for key, value in export_defines %}
+target_compile_definitions({{ name }} INTERFACE {{ key }}={{ value }})
+{% endfor %}
+
+{% if reproducible and is_library %}
+# Deterministic archives: zero timestamps, uids and modes in static libraries
+if(NOT APPLE)
+{% for lang in languages %}
+    set(CMAKE_{{ lang }}_ARCHIVE_CREATE "<CMAKE_AR> qcD <TARGET> <LINK_FLAGS> <OBJECTS>")
+    set(CMAKE_{{ lang }}_ARCHIVE_APPEND "<CMAKE_AR> qD <TARGET> <LINK_FLAGS> <OBJECTS>")
+    set(CMAKE_{{ lang }}_ARCHIVE_FINISH "<CMAKE_RANLIB> -D <TARGET>")
+{% endfor %}
+endif()
+{% endif %}
+
+{% if stdlib %}
+if(NOT CMAKE_CXX_COMPILER_ID MATCHES "Clang")
+    message(FATAL_ERROR "stdlib = {{ stdlib }} requires clang, but the C++ compiler is ${CMAKE_CXX_COMPILER_ID}")
+endif()
+{% for target in [name] | concat(with=bin_names) %}
+target_compile_options({{ target }} PRIVATE "-stdlib={{ stdlib }}")
+target_link_libraries({{ target }} PRIVATE "-stdlib={{ stdlib }}")
 {% endfor %}
 {% endif %}
 
 {% if lto %}
-set_property(TARGET {{ name }} PROPERTY INTERPROCEDURAL_OPTIMIZATION TRUE)
+# LTO only where the toolchain supports it, rather than failing the link
+include(CheckIPOSupported)
+check_ipo_supported(RESULT ZORA_IPO_SUPPORTED OUTPUT ZORA_IPO_ERROR LANGUAGES {{ languages | first }})
+if(ZORA_IPO_SUPPORTED)
+{% for target in [name] | concat(with=bin_names) %}
+    set_property(TARGET {{ target }} PROPERTY INTERPROCEDURAL_OPTIMIZATION TRUE)
+{% endfor %}
+else()
+    message(WARNING "LTO is not supported by this toolchain, building without it: ${ZORA_IPO_ERROR}")
+endif()
+{% endif %}
+
+{% if strip %}
+# Strip symbols from the executables after linking
+if(CMAKE_STRIP)
+{% for target in executables %}
+    add_custom_command(TARGET {{ target }} POST_BUILD COMMAND "${CMAKE_STRIP}" "$<TARGET_FILE:{{ target }}>")
+{% endfor %}
+endif()
+{% endif %}
+
+{% if unity_batch_size %}
+set_target_properties({{ name }} PROPERTIES UNITY_BUILD_BATCH_SIZE {{ unity_batch_size }})
+{% endif %}
+
+{% if pgo_flags %}
+# Profile-guided optimization; the flags are needed at link time too
+{% for target in [name] | concat(with=bin_names) %}
+target_compile_options({{ target }} PRIVATE{% for flag in pgo_flags %} "{{ flag }}"{% endfor %})
+target_link_libraries({{ target }} PRIVATE{% for flag in pgo_flags %} "{{ flag }}"{% endfor %})
+{% endfor %}
 {% endif %}
 "#;
 
+/// A configure check rendered into the CMake template.
+#[derive(Serialize)]
+struct ConfigureCheck {
+    kind: &'static str,
+    name: String,
+    header: String,
+    macro_name: String,
+}
 
-pub fn run(
-    name_opt: Option<String>,
-    mode: &str,
-    verbose: bool,
-    jobs: Option<usize>,
-    features: Vec<String>,
-    all_features: bool,
-    no_default_features: bool,
-    target: Option<String>,
-static_link: bool,
-) -> Result<()> {
+/// CMake regex matching a project-relative source path at the end of a
+/// globbed absolute path.
+fn source_regex(path: &str) -> String {
+    format!("/{}$", path.replace('.', "\\\\."))
+}
+
+/// The project's sources, as the CMake glob would find them minus `[[bin]]`
+/// mains, feature-gated and `[sources] exclude`d files, sorted by path so every machine compiles
+/// and links them in the same order.
+fn sorted_sources(config: &ProjectConfig, resolved: &ResolvedBuild) -> Vec<String> {
+    let mut sources: Vec<String> = config.sources.dirs.iter()
+        .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| config.source_extensions().contains(&ext)))
+        .map(|path| path.strip_prefix(".").map(Path::to_path_buf).unwrap_or(path))
+        .filter(|path| !config.bins.iter().any(|bin| Path::new(bin.path.trim_start_matches("./")) == path))
+        .filter(|path| !resolved.excluded_sources.contains(path))
+        .map(|path| path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    sources.sort();
+    sources.dedup();
+    sources
+}
+
+/// Autoconf-style macro name for a check, e.g. `sys/mman.h` -> `HAVE_SYS_MMAN_H`.
+fn check_macro(name: &str) -> String {
+    let sanitized: String = name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    format!("HAVE_{}", sanitized)
+}
+
+fn configure_checks(config: &ProjectConfig) -> Vec<ConfigureCheck> {
+    let checks = &config.build.checks;
+    let mut result = vec![];
+
+    for header in &checks.has_header {
+        result.push(ConfigureCheck {
+            kind: "header",
+            name: header.clone(),
+            header: header.clone(),
+            macro_name: check_macro(header),
+        });
+    }
+
+    for function in &checks.has_function {
+        result.push(ConfigureCheck {
+            kind: "function",
+            name: function.clone(),
+            header: String::new(),
+            macro_name: check_macro(function),
+        });
+    }
+
+    let mut symbols: Vec<_> = checks.has_symbol.iter().collect();
+    symbols.sort();
+    for (symbol, header) in symbols {
+        result.push(ConfigureCheck {
+            kind: "symbol",
+            name: symbol.clone(),
+            header: header.clone(),
+            macro_name: check_macro(symbol),
+        });
+    }
+
+    result
+}
+
+/// Roughly how much memory a large link can take.
+const MEMORY_PER_LINK_JOB: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Picks a link job limit from total memory so parallel links don't
+/// exhaust RAM. Total rather than available memory, so the limit (and with
+/// it the CMake cache) doesn't change from one build to the next. Returns
+/// None when memory can't be determined.
+fn default_link_jobs() -> Option<usize> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let total_kb: u64 = meminfo.lines()
+        .find(|line| line.starts_with("MemTotal:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()?;
+
+    Some(((total_kb * 1024) / MEMORY_PER_LINK_JOB).max(1) as usize)
+}
+
+/// A `[[bin]]` target rendered into the CMake template.
+#[derive(Serialize)]
+struct BinContext {
+    name: String,
+    path: String,
+    /// Regex matching the bin's entry point in the globbed source list
+    regex: String,
+}
+
+/// Options for a single build invocation, as collected from the CLI.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    pub name: Option<String>,
+    pub mode: String,
+    pub verbosity: Verbosity,
+    pub jobs: Option<usize>,
+    pub features: Vec<String>,
+    pub all_features: bool,
+    pub no_default_features: bool,
+    pub target: Option<String>,
+    pub static_link: bool,
+    /// Named slot under .build/ and target/; defaults to the mode
+    pub build_dir: Option<String>,
+    /// Don't start new jobs while the system load is above this
+    pub load_average: Option<f64>,
+    /// Continue past compile errors (Make and Ninja only)
+    pub keep_going: bool,
+    pub pgo: Option<PgoPhase>,
+    /// Overrides [build] linker
+    pub linker: Option<String>,
+    /// Leave zora's default warning flags out of the profile flags
+    pub no_default_flags: bool,
+    /// Reject `"*"` dependencies even without `[deps] strict`
+    pub strict_deps: bool,
+    /// Reconfigure even when the build manifest says nothing changed
+    pub force: bool,
+    /// Overrides [build] generator
+    pub generator: Option<String>,
+    /// Skip the success summary and keep stdout clear (host tools and path
+    /// dependencies included), for when it belongs to the program run after
+    /// the build
+    pub quiet: bool,
+    /// vcpkg triplet to build for, e.g. "arm64-osx"
+    pub triplet: Option<String>,
+    /// Unity build even without `[build] unity_build`
+    pub unity: bool,
+    /// Set when building a path dependency, whose own path dependencies the
+    /// dependent project has already built
+    pub skip_path_deps: bool,
+    /// Path dependencies a workspace build has already built, by project
+    /// directory, with the target dir their artifacts were copied to
+    pub built_path_deps: Vec<(PathBuf, PathBuf)>,
+}
+
+/// The two phases of a profile-guided optimization build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgoPhase {
+    /// Instrumented build that writes profile data when run
+    Generate,
+    /// Optimized rebuild from the collected profile data
+    Use,
+}
+
+impl BuildOptions {
+    pub fn new(mode: &str) -> Self {
+        BuildOptions {
+            mode: mode.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Directory name used under .build/ and target/ for this build.
+    pub fn slot(&self) -> &str {
+        self.build_dir.as_deref().unwrap_or(&self.mode)
+    }
+}
+
+/// Build settings after merging the base config, the profile overlay, the
+/// enabled features and CLI overrides. The CMake template and
+/// `zora info --effective-cmake` are both driven from this.
+#[derive(Debug)]
+pub struct ResolvedBuild {
+    pub features: Vec<String>,
+    pub build_type: &'static str,
+    /// `-O` level, set as the build type's CMake flags
+    pub opt_level: String,
+    pub debug: bool,
+    pub std: Option<String>,
+    /// `-std=gnu..` rather than the ISO dialect for direct compiles
+    pub gnu_extensions: bool,
+    pub lto: bool,
+    /// Strip the executables after linking; off with `[build] split_debug`,
+    /// which needs the debug info
+    pub strip: bool,
+    pub static_link: bool,
+    /// Unity batch size when unity building (0 for CMake's default)
+    pub unity_batch_size: Option<usize>,
+    pub flags: Vec<String>,
+    pub defines: BTreeMap<String, String>,
+    pub include_dirs: Vec<String>,
+    pub system_include_dirs: Vec<String>,
+    pub link_libs: Vec<String>,
+    pub lib_dirs: Vec<String>,
+    /// vcpkg packages linked, including those pulled in by features
+    pub vcpkg_packages: Vec<String>,
+    /// Built libraries of zora path dependencies, dependents first
+    pub path_dep_libs: Vec<String>,
+    pub excluded_sources: Vec<PathBuf>,
+}
+
+impl ResolvedBuild {
+    /// Compiler arguments for one translation unit: standard, include dirs,
+    /// flags and defines, in the order CMake emits them.
+    pub fn compile_args(&self, is_cpp: bool) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(std) = &self.std {
+            args.push(crate::config::std_flag(std, is_cpp, self.gnu_extensions));
+        }
+        args.extend(self.build_type_flags());
+
+        args.extend(self.defines.iter().map(|(key, value)| format!("-D{}={}", key, value)));
+        args.extend(self.include_dirs.iter().map(|dir| format!("-I{}", dir)));
+        for dir in &self.system_include_dirs {
+            args.push("-isystem".to_string());
+            args.push(dir.clone());
+        }
+        args.extend(self.flags.iter().cloned());
+
+        args
+    }
+
+    /// Whether this build goes through vcpkg: `ProjectConfig::uses_vcpkg`
+    /// with the enabled features' packages.
+    pub fn uses_vcpkg(&self, config: &ProjectConfig) -> bool {
+        config.use_vcpkg && !self.vcpkg_packages.is_empty()
+    }
+
+    /// Replaces CMake's `CMAKE_<LANG>_FLAGS_<BUILD_TYPE>` defaults, so the
+    /// optimization level comes from project.toml rather than CMake.
+    pub fn build_type_flags(&self) -> Vec<String> {
+        let mut flags = vec![format!("-O{}", self.opt_level)];
+        if self.debug {
+            flags.push("-g".to_string());
+        } else {
+            flags.push("-DNDEBUG".to_string());
+        }
+        flags
+    }
+}
+
+pub fn resolve(config: &ProjectConfig, opts: &BuildOptions) -> Result<ResolvedBuild> {
+    let profile = config.get_profile(&opts.mode);
+
+    let features = config.resolve_features(&opts.features, opts.all_features, opts.no_default_features)?;
+
+    // Merge build flags with the profile's
+    let mut flags = config.feature_flags();
+    flags.extend(config.merged_flags(&opts.mode, opts.no_default_flags));
+
+    if config.build.reproducible {
+        // Map the project root to "." so no absolute paths reach the binary
+        let root = std::env::current_dir()?;
+        flags.push(format!("-ffile-prefix-map={}=.", root.display()));
+        flags.push(format!("-fdebug-prefix-map={}=.", root.display()));
+    }
+
+    // Merge build defines with the profile's, then feature defines
+    let mut defines = config.merged_defines(&opts.mode);
+    defines.extend(config.feature_defines(&features));
+
+    // Path deps contribute their include/ dir, or their root if they have none,
+    // and zora libraries among them their export_defines
+    let mut include_dirs = config.include_dirs();
+    for (name, path) in config.path_deps() {
+        if Path::new(&path).join("project.toml").exists() {
+            let dep = ProjectConfig::load_from(Path::new(&path))
+                .with_context(|| format!("failed to load path dependency '{}'", name))?;
+            if dep.is_library() {
+                for (key, value) in dep.build.export_defines.clone() {
+                    defines.entry(key).or_insert(value);
+                }
+            }
+
+            // A colocated dependency's headers are in its source dirs
+            if dep.headers_colocated() {
+                for dir in dep.include_dirs() {
+                    include_dirs.push(Path::new(&path).join(dir).to_string_lossy().into_owned());
+                }
+                continue;
+            }
+        }
+
+        let dep_include = Path::new(&path).join("include");
+        if dep_include.is_dir() {
+            include_dirs.push(dep_include.to_string_lossy().into_owned());
+        } else {
+            include_dirs.push(path);
+        }
+    }
+
+    let vcpkg_packages = config.build_vcpkg_packages(&features);
+
+    Ok(ResolvedBuild {
+        excluded_sources: config.excluded_sources(&features)?,
+        features,
+        build_type: build_type(&opts.mode),
+        opt_level: config.opt_level(&opts.mode),
+        debug: profile.debug,
+        std: Some(config.std.clone()).filter(|s| !s.is_empty()),
+        gnu_extensions: config.build.gnu_extensions.unwrap_or(true),
+        lto: profile.lto,
+        strip: profile.strip && !config.build.split_debug,
+        static_link: config.build.static_link || opts.static_link,
+        unity_batch_size: (config.build.unity_build || opts.unity)
+            .then(|| config.build.unity_batch_size.unwrap_or(0)),
+        flags,
+        defines,
+        include_dirs,
+        system_include_dirs: config.includes.system.clone(),
+        link_libs: config.merged_libs(&opts.mode),
+        lib_dirs: config.build.lib_dirs.clone(),
+        vcpkg_packages,
+        path_dep_libs: path_dep_libraries(config)?.iter().rev()
+            .map(|dep| paths::cmake_path(&dep.library(opts)))
+            .collect(),
+    })
+}
+
+pub fn run(opts: BuildOptions) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
     let config = ProjectConfig::load()?;
-    let profile = config.get_profile(mode);
+    let mode = opts.mode.as_str();
+    let verbosity = opts.verbosity;
+
+    // Fail before CMake does, and more clearly
+    let missing_env = config.missing_env();
+    if !missing_env.is_empty() {
+        bail!("Required environment variable(s) not set ([build] required_env):\n  {}",
+            missing_env.join("\n  "));
+    }
+    config.check_strict_deps(opts.strict_deps)?;
 
     let pb = ProgressBar::new_spinner();
     pb.set_style(
@@ -142,214 +634,257 @@ static_link: bool,
             .unwrap()
     );
 
-    pb.set_message("Preparing build...");
-
-    // Determine enabled features
-    let mut enabled_features: HashSet<String> = HashSet::new();
-    
-    if !no_default_features {
-        enabled_features.extend(config.default_features.iter().cloned());
+    if !config.build_dependencies.is_empty() {
+        pb.set_message("Building host tools...");
+        pb.suspend(|| run_build_dependencies(&config, mode, verbosity, opts.quiet))?;
     }
-    
-    if all_features {
-        enabled_features.extend(config.features.keys().cloned());
-    } else {
-        enabled_features.extend(features.into_iter());
+
+    if !opts.skip_path_deps {
+        let path_deps: Vec<PathDep> = path_dep_libraries(&config)?.into_iter()
+            .filter(|dep| !opts.built_path_deps.iter().any(|(dir, _)| *dir == dep.dir))
+            .collect();
+        if !path_deps.is_empty() {
+            pb.set_message("Building path dependencies...");
+            pb.suspend(|| build_path_deps(&path_deps, &opts))?;
+        }
     }
 
-    // Build directory
-    let build_dir = format!(".build/{}", mode);
-    fs::create_dir_all(&build_dir).context("failed to create build directory")?;
+    pb.set_message("Preparing build...");
 
-    let project_name = name_opt.unwrap_or_else(|| config.name.clone());
+    let resolved = resolve(&config, &opts)?;
+    let enabled_features = &resolved.features;
 
-    // Prepare CMake context
-    let mut ctx = TeraContext::new();
-    ctx.insert("name", &project_name);
-    ctx.insert("language", if config.is_cpp() { "CXX" } else { "C" });
-    ctx.insert("source_dirs", &config.sources.dirs);
-    ctx.insert("include_dirs", &config.includes.dirs);
-    ctx.insert("is_library", &config.is_library());
-    ctx.insert("use_vcpkg", &!config.deps.is_empty());
-    ctx.insert("lto", &profile.lto);
-ctx.insert("static_link", &config.build.static_link);    
-    if config.is_cpp() && !config.std.is_empty() {
-        ctx.insert("cpp_std", &config.std);
-    }
-    
-    if !config.is_cpp() && !config.std.is_empty() {
-        ctx.insert("c_std", &config.std);
-    }
-
-    // Merge profile flags with build flags
-    let mut all_flags = profile.flags.clone();
-    all_flags.extend(config.build.flags.clone());
-    
-    if !all_flags.is_empty() {
-        ctx.insert("build_flags", &all_flags);
-    }
-
-    // Merge profile defines with build defines
-    let mut all_defines = profile.defines.clone();
-    all_defines.extend(config.build.defines.clone());
-    
-    // Add feature defines
-    for feature in &enabled_features {
-        all_defines.insert(
-            format!("FEATURE_{}", feature.to_uppercase().replace("-", "_")),
-            "1".to_string()
-        );
-    }
-    
-    if !all_defines.is_empty() {
-        ctx.insert("defines", &all_defines);
+    // Build directory
+    let slot = opts.slot();
+    if slot.is_empty() || slot.contains(['/', '\\']) || slot == "." || slot == ".." {
+        bail!("Invalid build directory name '{}'. Use a plain name like 'experiment-lto'", slot);
     }
 
-    if !config.build.libs.is_empty() {
-        ctx.insert("link_libs", &config.build.libs);
-    }
-    if !config.build.lib_dirs.is_empty() {
-        ctx.insert("lib_dirs", &config.build.lib_dirs);
-    }
+    let build_dir = paths::build_root().join(slot).to_string_lossy().into_owned();
+    fs::create_dir_all(&build_dir).context("failed to create build directory")?;
+    request_codemodel(Path::new(&build_dir))?;
+
+    let project_name = opts.name.clone().unwrap_or_else(|| config.name.clone());
 
-    if !config.deps.is_empty() {
-        let packages: Vec<String> = config.deps.keys().cloned().collect();
-        ctx.insert("vcpkg_packages", &packages);
+    // The CMake template reads the toolchain from $ENV{VCPKG_ROOT}
+    if resolved.uses_vcpkg(&config) {
+        if pb.suspend(crate::vcpkg::ensure_root).is_none() {
+            pb.finish_and_clear();
+            bail!("VCPKG_ROOT is not set and vcpkg was not found on PATH. Run 'zora doctor' for details.");
+        }
+        if crate::vcpkg::ensure_manifest(&config)? && verbosity.commands() {
+            pb.suspend(|| println!("  {} {}", "Updated".green(), crate::vcpkg::MANIFEST_FILE));
+        }
     }
 
     pb.set_message("Generating CMake files...");
 
-    let cmake_content = Tera::one_off(PROJECT_CMAKE_TEMPLATE, &ctx, false)
-        .context("failed to render CMakeLists.txt template")?;
+    let project = pb.suspend(|| generate(&config, &opts, &resolved, &paths::cmake_project_root()?))?;
+    let source_date_epoch = project.source_date_epoch;
 
-    let cmake_path = Path::new(&build_dir).join("CMakeLists.txt");
-    fs::write(&cmake_path, cmake_content)
-        .context("failed to write CMakeLists.txt")?;
+    let manifest = BuildManifest::collect(&config, &project);
+    let manifest_path = Path::new(&build_dir).join(MANIFEST_FILE);
 
-    if verbose {
-        println!("  {} {}", "Generated".green(), cmake_path.display());
-    }
+    if !opts.force && manifest.is_current(Path::new(&build_dir), &project) {
+        if verbosity.commands() {
+            pb.suspend(|| println!("  {} CMake configuration (no inputs changed)", "Skipped".dimmed()));
+        }
+    } else {
+        // A failed configure must not leave the old manifest claiming success
+        let _ = fs::remove_file(&manifest_path);
+
+        // CMake refuses to switch the generator of an existing build dir
+        let requested = select_generator(&config, &opts)?;
+        if let (Some(requested), Some(previous)) = (requested, cmake_generator(&build_dir)) {
+            if requested != previous {
+                trace(verbosity, format!("generator changed from {} to {}; clearing the CMake cache", previous, requested));
+                fs::remove_file(Path::new(&build_dir).join("CMakeCache.txt"))?;
+                let cmake_files = Path::new(&build_dir).join("CMakeFiles");
+                if cmake_files.exists() {
+                    fs::remove_dir_all(&cmake_files)?;
+                }
+            }
+        }
 
-    pb.set_message("Configuring project...");
+        let cmake_path = project.write(Path::new(&build_dir))?;
 
-    let mut cmake_config = Command::new("cmake");
-    cmake_config
-        .args(&[
-            "-S", &build_dir,
-            "-B", &build_dir,
-            "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON",
-            &format!("-DCMAKE_BUILD_TYPE={}", if mode == "release" { "Release" } else { "Debug" }),
-        ]);
+        if verbosity.commands() {
+            pb.suspend(|| println!("  {} {}", "Generated".green(), cmake_path.display()));
+        }
 
-    if let Some(t) = target {
-        cmake_config.arg(format!("-DCMAKE_SYSTEM_NAME={}", t));
-    }
+        pb.set_message("Configuring project...");
 
-    if verbose {
-        cmake_config.arg("-DCMAKE_VERBOSE_MAKEFILE=ON");
-    }
+        // Passed here rather than rendered, so exports don't carry this
+        // machine's limit; 0 clears a limit from an earlier configure
+        let link_jobs = config.build.link_jobs.or_else(default_link_jobs).map(|n| n.max(1));
+        trace(verbosity, format!("link jobs: {:?}", link_jobs));
 
-    let status = cmake_config.status().context("failed to run cmake")?;
+        let mut cmake_config = Command::new("cmake");
+        cmake_config
+            .args(["-S", &build_dir, "-B", &build_dir])
+            .args(&project.configure_args)
+            .arg(format!("-DZORA_LINK_JOBS={}", link_jobs.unwrap_or(0)));
 
-    if !status.success() {
-        pb.finish_and_clear();
-        bail!("CMake configuration failed");
+        if verbosity.tool_output() {
+            cmake_config.arg("-DCMAKE_VERBOSE_MAKEFILE=ON");
+        }
+
+        if let Some(epoch) = source_date_epoch {
+            cmake_config.env("SOURCE_DATE_EPOCH", epoch.to_string());
+        }
+
+        let success = pb.suspend(|| run_tool(&mut cmake_config, verbosity))?;
+
+        if !success {
+            pb.finish_and_clear();
+            bail!("CMake configuration failed");
+        }
+
+        manifest.save(&manifest_path)?;
     }
 
     pb.set_message(format!("Building {} [{}]...", project_name, mode));
 
+    // Multi-config generators (Visual Studio, Xcode) build Debug unless told
     let mut cmake_build = Command::new("cmake");
-    cmake_build.args(&["--build", &build_dir]);
+    cmake_build.args(["--build", &build_dir, "--config", resolved.build_type]);
 
-    if let Some(j) = jobs {
-        cmake_build.arg("-j").arg(j.to_string());
-    } else {
-        let num_cpus = std::thread::available_parallelism()
-            .map(|n| n.get())
-            .unwrap_or(1);
-        cmake_build.arg("-j").arg(num_cpus.to_string());
-    }
+    cmake_build.arg("-j").arg(build_jobs(&config, &opts).to_string());
 
-    if verbose {
+    if verbosity.tool_output() {
         cmake_build.arg("--verbose");
     }
 
-    let status = cmake_build.status().context("failed to run cmake build")?;
+    // Arguments after `--` go to the native build tool
+    let generator = cmake_generator(&build_dir);
+    let is_make = generator.as_deref().is_some_and(|g| g.contains("Makefiles"));
+    let is_ninja = generator.as_deref().is_some_and(|g| g.starts_with("Ninja"));
+    let mut native_args = vec![];
 
-    if !status.success() {
+    if let Some(load) = opts.load_average.or(config.build.load_average) {
+        if is_make || is_ninja {
+            trace(verbosity, format!("load average limit: {}", load));
+            native_args.push(format!("-l{}", load));
+        } else {
+            pb.suspend(|| eprintln!("{} load_average is not supported by the {} generator; ignoring",
+                "Warning:".yellow(),
+                generator.as_deref().unwrap_or("unknown")
+            ));
+        }
+    }
+
+    let mut keep_going = opts.keep_going;
+    if keep_going {
+        if is_ninja {
+            // Ninja's -k takes a failure budget; 0 means unlimited
+            native_args.extend(["-k".to_string(), "0".to_string()]);
+        } else if is_make {
+            native_args.push("-k".to_string());
+        } else {
+            keep_going = false;
+            pb.suspend(|| eprintln!("{} --keep-going is not supported by the {} generator; ignoring",
+                "Warning:".yellow(),
+                generator.as_deref().unwrap_or("unknown")
+            ));
+        }
+    }
+
+    if !native_args.is_empty() {
+        cmake_build.arg("--").args(&native_args);
+    }
+
+    if let Some(epoch) = source_date_epoch {
+        cmake_build.env("SOURCE_DATE_EPOCH", epoch.to_string());
+    }
+
+    // The compiler's warnings show unless stdout belongs to the program run next
+    let success = if keep_going {
+        pb.suspend(|| run_keep_going(&mut cmake_build, verbosity))?
+    } else if opts.quiet {
+        pb.suspend(|| run_tool(&mut cmake_build, verbosity))?
+    } else {
+        pb.suspend(|| stream_tool(&mut cmake_build, verbosity))?
+    };
+
+    if !success {
         pb.finish_and_clear();
         bail!("Build failed");
     }
 
     // Copy artifacts
-    let target_dir = format!("target/{}", mode);
+    let target_dir = paths::target_root().join(slot).to_string_lossy().into_owned();
     fs::create_dir_all(&target_dir)?;
+    let mut executables = vec![];
+    let mut libraries = vec![];
 
-    if config.is_library() {
-        for entry in fs::read_dir(&build_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if let Some(ext) = path.extension() {
-                let ext_str = ext.to_str().unwrap_or("");
-                if ["a", "so", "dll", "dylib", "lib"].contains(&ext_str) {
-                    let target_file = Path::new(&target_dir).join(path.file_name().unwrap());
-                    fs::copy(&path, &target_file)?;
-                    if verbose {
-                        println!("  {} {}", "Copied".green(), target_file.display());
-                    }
-                }
-            }
-        }
-    } else {
-        let exe_name = if cfg!(windows) {
-            format!("{}.exe", project_name)
-        } else {
-            project_name.clone()
-        };
-        
-        let built_exe = Path::new(&build_dir).join(&exe_name);
-        let target_exe = Path::new(&target_dir).join(&exe_name);
-        
-        if built_exe.exists() {
-            fs::copy(&built_exe, &target_exe)?;
-            
+    let mut wanted: Vec<&str> = config.bins.iter().map(|bin| bin.name.as_str()).collect();
+    wanted.push(&project_name);
+    for (target, artifact) in built_artifacts(Path::new(&build_dir), resolved.build_type, &wanted, config.is_library()) {
+        let target_file = Path::new(&target_dir).join(artifact.path.file_name().unwrap_or_default());
+        copy_artifact(&artifact.path, &target_file, source_date_epoch)?;
+
+        if artifact.kind == ArtifactKind::Executable {
             #[cfg(unix)]
             {
                 use std::os::unix::fs::PermissionsExt;
-                let mut perms = fs::metadata(&target_exe)?.permissions();
+                let mut perms = fs::metadata(&target_file)?.permissions();
                 perms.set_mode(0o755);
-                fs::set_permissions(&target_exe, perms)?;
-            }
-            
-            if verbose {
-                println!("  {} {}", "Copied".green(), target_exe.display());
+                fs::set_permissions(&target_file, perms)?;
             }
+            executables.push(target_file.clone());
+        } else {
+            libraries.push(target_file.clone());
+        }
+
+        if verbosity.commands() {
+            pb.suspend(|| println!("  {} {} ({})", "Copied".green(), target_file.display(), target));
         }
     }
 
-    // Create compile_commands.json symlink
-    let src = Path::new(&build_dir).join("compile_commands.json");
-    let dst = Path::new("compile_commands.json");
+    if !config.build.copy.is_empty() {
+        pb.set_message("Copying files...");
+        let copied = copy_files(&config.build.copy, mode, &target_dir, source_date_epoch)?;
+        trace(verbosity, format!("[build] copy: {} file(s)", copied));
+    }
 
-    if dst.exists() {
-        fs::remove_file(dst).ok();
+    if config.build.split_debug {
+        pb.set_message("Splitting debug info...");
+        for exe in &executables {
+            pb.suspend(|| split_debug_info(exe, verbosity))?;
+        }
     }
 
-    #[cfg(unix)]
-    {
-        if src.exists() {
-            std::os::unix::fs::symlink(&src, dst)?;
+    // Hashed after splitting so the name matches what ships
+    if config.build.hash_artifacts {
+        for artifact in libraries.iter().chain(&executables) {
+            let hashed = hash_artifact(artifact)?;
+            if verbosity.commands() {
+                println!("  {} {}", "Hashed".green(), hashed.display());
+            }
         }
     }
-    #[cfg(windows)]
-    {
-        if src.exists() {
-            std::os::windows::fs::symlink_file(&src, dst)?;
+
+    link_compile_commands(Path::new(&build_dir))?;
+
+    // A build only creates a missing lock, and not with --out-dir, which
+    // keeps the source tree read-only
+    if Path::new(crate::lockfile::LOCK_FILE).exists() {
+        if !opts.quiet && crate::lockfile::has_drifted(&config) {
+            pb.suspend(|| eprintln!("{} installed dependencies differ from {}. Run 'zora verify --locked' for details",
+                "Warning:".yellow(),
+                crate::lockfile::LOCK_FILE
+            ));
         }
+    } else if paths::out_dir().is_none() && crate::lockfile::update(&config)? && verbosity.commands() {
+        pb.suspend(|| println!("  {} {}", "Created".green(), crate::lockfile::LOCK_FILE));
     }
 
     pb.finish_and_clear();
 
+    if opts.quiet {
+        return Ok(());
+    }
+
     let feature_str = if !enabled_features.is_empty() {
         format!(" with features: {}", enabled_features.iter()
             .map(|s| s.as_str())
@@ -366,19 +901,1423 @@ ctx.insert("static_link", &config.build.static_link);
         feature_str
     );
 
+    if opts.pgo == Some(PgoPhase::Generate) {
+        println!("\n{} This build is instrumented. Run it on representative workloads,", "→".bright_blue());
+        println!("  then {} to rebuild with the collected profile", "zora build --pgo-use".bright_white());
+    }
+
     Ok(())
 }
 
-pub fn get_executable_path(name_opt: Option<String>, mode: &str) -> Result<std::path::PathBuf> {
+/// Whether a build with these options would have nothing to do: the slot
+/// was configured from the same inputs and every executable in target/ is
+/// newer than all of them. Host tools aren't tracked, so projects with
+/// `[build-dependencies]` never count as up to date.
+pub fn is_up_to_date(opts: &BuildOptions) -> Result<bool> {
+    if !ProjectConfig::exists() {
+        return Ok(false);
+    }
+
     let config = ProjectConfig::load()?;
-    let project_name = name_opt.unwrap_or_else(|| config.name.clone());
+    if !config.build_dependencies.is_empty() {
+        return Ok(false);
+    }
 
-    let exe_name = if cfg!(windows) {
-        format!("{}.exe", project_name)
-    } else {
-        project_name
-    };
+    let resolved = resolve(&config, opts)?;
+    let project = generate(&config, opts, &resolved, &paths::cmake_project_root()?)?;
+    let manifest = BuildManifest::collect(&config, &project);
+    if !manifest.is_current(&paths::build_root().join(opts.slot()), &project) {
+        return Ok(false);
+    }
+
+    let newest_input = manifest.files.values().copied().max().unwrap_or(0);
+    let mut names = config.executables();
+    if let Some(name) = &opts.name {
+        names.push(name.clone());
+    }
+
+    Ok(names.iter().all(|name| {
+        build_dir_artifact(&config, Some(name), opts.slot(), resolved.build_type)
+            .and_then(|artifact| fs::metadata(&artifact.path).ok())
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .is_some_and(|modified| modified.as_nanos() > newest_input)
+    }))
+}
+
+/// Triplets `zora build --universal` builds and merges: Apple silicon and
+/// Intel macOS.
+pub const UNIVERSAL_TRIPLETS: &[&str] = &["arm64-osx", "x64-osx"];
+
+/// Builds once per vcpkg triplet, each into its own `<slot>-<triplet>` slot.
+/// When every triplet is a macOS one the artifacts are merged with `lipo`
+/// into `target/<slot>`; other builds are left side by side.
+pub fn run_triplets(opts: BuildOptions, triplets: &[String]) -> Result<()> {
+    let mergeable = triplets.len() > 1 && triplets.iter().all(|t| osx_architecture(t).is_some());
+    if mergeable && !on_path("lipo") {
+        bail!("lipo not found. It's needed to merge {} and comes with the Xcode command line tools.",
+            triplets.join(", "));
+    }
+
+    let slot = opts.slot().to_string();
+    let mut slots = vec![];
+    for triplet in triplets {
+        let triplet_slot = format!("{}-{}", slot, triplet);
+        println!("{} {}", "Building for".bright_cyan(), triplet);
+        run(BuildOptions {
+            triplet: Some(triplet.clone()),
+            build_dir: Some(triplet_slot.clone()),
+            ..opts.clone()
+        })?;
+        slots.push(triplet_slot);
+    }
+
+    if !mergeable {
+        if triplets.len() > 1 {
+            println!("\n{} Only macOS triplets can be merged; artifacts are in {}",
+                "Note:".yellow(),
+                slots.iter()
+                    .map(|slot| paths::target_root().join(slot).display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        return Ok(());
+    }
+
+    let config = ProjectConfig::load()?;
+    let merged_dir = paths::target_root().join(&slot);
+    fs::create_dir_all(&merged_dir)?;
+
+    let first_dir = paths::target_root().join(&slots[0]);
+    let mut merged = 0;
+    for entry in fs::read_dir(&first_dir)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+        let is_artifact = config.executables().iter().any(|exe| exe == file_name)
+            || path.extension().is_some_and(|ext| ext == "a" || ext == "dylib");
+        if !path.is_file() || !is_artifact {
+            continue;
+        }
+
+        let inputs: Vec<PathBuf> = slots.iter()
+            .map(|slot| paths::target_root().join(slot).join(file_name))
+            .collect();
+        if !inputs.iter().all(|input| input.exists()) {
+            continue;
+        }
+
+        let output = merged_dir.join(file_name);
+        let mut lipo = Command::new("lipo");
+        lipo.arg("-create").arg("-output").arg(&output).args(&inputs);
+        if !run_tool(&mut lipo, opts.verbosity)? {
+            bail!("lipo failed to merge {}", file_name);
+        }
+        println!("  {} {}", "Merged".green(), output.display());
+        merged += 1;
+    }
+
+    if merged == 0 {
+        bail!("No artifacts to merge in {}", first_dir.display());
+    }
+
+    println!("{} Universal build of {} [{}]", "✓".green().bold(), triplets.join(" + "), opts.mode);
+    Ok(())
+}
+
+/// The `CMAKE_OSX_ARCHITECTURES` value for a macOS vcpkg triplet.
+fn osx_architecture(triplet: &str) -> Option<&'static str> {
+    let (arch, rest) = triplet.split_once('-')?;
+    if !rest.starts_with("osx") {
+        return None;
+    }
+    match arch {
+        "arm64" => Some("arm64"),
+        "x64" => Some("x86_64"),
+        _ => None,
+    }
+}
+
+/// A rendered CMakeLists.txt and the arguments `cmake` is configured with,
+/// apart from the source and build dirs.
+pub struct CmakeProject {
+    pub cmake_lists: String,
+    /// Template for the generated config.h when `[build.checks]` is used;
+    /// goes next to CMakeLists.txt
+    pub config_h_in: Option<String>,
+    pub configure_args: Vec<String>,
+    pub source_date_epoch: Option<u64>,
+}
+
+impl CmakeProject {
+    /// Writes CMakeLists.txt (and config.h.in) into `dir`, returning the
+    /// CMakeLists.txt path.
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        if let Some(config_h) = &self.config_h_in {
+            fs::write(dir.join("config.h.in"), config_h)
+                .context("failed to write config.h.in")?;
+        }
+
+        let cmake_path = dir.join("CMakeLists.txt");
+        fs::write(&cmake_path, &self.cmake_lists)
+            .with_context(|| format!("failed to write {}", cmake_path.display()))?;
+        Ok(cmake_path)
+    }
+}
+
+/// Name of the build manifest inside `.build/<slot>`.
+const MANIFEST_FILE: &str = ".zora-manifest.json";
+
+/// Environment variables that change what CMake configures.
+const MANIFEST_ENV: &[&str] = &["CC", "CXX", "VCPKG_ROOT"];
+
+/// The inputs a build dir was last configured from. When none changed, the
+/// CMake configure step is skipped and only `cmake --build` runs. Sources are
+/// globbed at configure time, so added and deleted files count as changes.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct BuildManifest {
+    configure_args: Vec<String>,
+    env: BTreeMap<String, String>,
+    /// Path to modification time, in nanoseconds since the epoch
+    files: BTreeMap<String, u128>,
+}
+
+impl BuildManifest {
+    fn collect(config: &ProjectConfig, project: &CmakeProject) -> Self {
+        let configure_args = project.configure_args.clone();
+
+        let env = MANIFEST_ENV.iter()
+            .filter_map(|name| Some((name.to_string(), std::env::var(name).ok()?)))
+            .collect();
+
+        let mut roots: Vec<PathBuf> = config.sources.dirs.iter()
+            .chain(&config.include_dirs())
+            .map(PathBuf::from)
+            .collect();
+        roots.extend(config.path_deps().into_iter().map(|(_, path)| PathBuf::from(path)));
+        roots.extend(std::iter::once(&config.toolchain.default)
+            .chain(config.toolchain.named.values())
+            .filter_map(|toolchain| toolchain.toolchain_file.as_ref().map(PathBuf::from)));
+        roots.push(PathBuf::from("project.toml"));
+        roots.push(PathBuf::from(crate::config::LOCAL_CONFIG_FILE));
+        roots.push(PathBuf::from(crate::vcpkg::MANIFEST_FILE));
+
+        let mut files = BTreeMap::new();
+        for root in roots {
+            for entry in WalkDir::new(&root).into_iter().filter_map(|e| e.ok()) {
+                if !entry.file_type().is_file() {
+                    continue;
+                }
+                let modified = entry.metadata().ok()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_nanos())
+                    .unwrap_or(0);
+                files.insert(entry.path().to_string_lossy().into_owned(), modified);
+            }
+        }
+
+        BuildManifest { configure_args, env, files }
+    }
+
+    /// Whether `build_dir` was configured from exactly these inputs and the
+    /// same generated CMake files.
+    fn is_current(&self, build_dir: &Path, project: &CmakeProject) -> bool {
+        if !build_dir.join("CMakeCache.txt").exists() {
+            return false;
+        }
+
+        let unchanged = |name: &str, expected: Option<&String>| {
+            fs::read_to_string(build_dir.join(name)).ok().as_ref() == expected
+        };
+        if !unchanged("CMakeLists.txt", Some(&project.cmake_lists))
+            || (project.config_h_in.is_some() && !unchanged("config.h.in", project.config_h_in.as_ref()))
+        {
+            return false;
+        }
+
+        fs::read_to_string(build_dir.join(MANIFEST_FILE)).ok()
+            .and_then(|content| serde_json::from_str::<BuildManifest>(&content).ok())
+            .is_some_and(|previous| previous == *self)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Parallel jobs for a build: `--jobs`, then `[build] jobs`, then all CPUs.
+pub fn build_jobs(config: &ProjectConfig, opts: &BuildOptions) -> usize {
+    let num_cpus = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    opts.jobs.or(config.build.jobs).unwrap_or(num_cpus)
+}
+
+/// Renders the CMake project for a resolved build. `project_root` is how the
+/// generated CMakeLists.txt refers to the project directory.
+pub fn generate(
+    config: &ProjectConfig,
+    opts: &BuildOptions,
+    resolved: &ResolvedBuild,
+    project_root: &str,
+) -> Result<CmakeProject> {
+    let verbosity = opts.verbosity;
+    let project_name = opts.name.clone().unwrap_or_else(|| config.name.clone());
+
+    let mut ctx = TeraContext::new();
+    ctx.insert("name", &project_name);
+    ctx.insert("project_root", project_root);
+    let languages = config.cmake_languages();
+    ctx.insert("language", languages[0]);
+    ctx.insert("languages", &languages);
+    ctx.insert("is_objc", &config.is_objc());
+    ctx.insert("source_extensions", config.source_extensions());
+    ctx.insert("frameworks", &config.build.frameworks);
+    ctx.insert("source_dirs", &config.sources.dirs);
+
+    ctx.insert("include_dirs", &resolved.include_dirs);
+    ctx.insert("system_include_dirs", &resolved.system_include_dirs);
+    ctx.insert("is_library", &config.is_library());
+    let vcpkg_packages = &resolved.vcpkg_packages;
+    ctx.insert("use_vcpkg", &resolved.uses_vcpkg(config));
+    ctx.insert("lto", &resolved.lto);
+    ctx.insert("strip", &resolved.strip);
+    if let Some(batch_size) = resolved.unity_batch_size {
+        ctx.insert("unity_batch_size", &batch_size);
+    }
+    ctx.insert("static_link", &resolved.static_link);
+    ctx.insert("build_type", &resolved.build_type.to_uppercase());
+    ctx.insert("build_type_flags", &resolved.build_type_flags().join(" "));
+
+    if let Some(std) = &resolved.std {
+        ctx.insert(if config.is_cpp() { "cpp_std" } else { "c_std" }, std);
+        if let Some(gnu_extensions) = config.build.gnu_extensions {
+            ctx.insert("gnu_extensions", &gnu_extensions);
+        }
+    }
+
+    let toolchain = config.toolchain(opts.target.as_deref());
+    if opts.target.is_some() && toolchain.is_none() {
+        trace(verbosity, "no [toolchain] for the target; passing it as the CMake system name");
+    }
+
+    // Validate the standard library choice up front rather than at configure time
+    let toolchain_compiler = toolchain.and_then(|t| if config.is_cpp() { t.cxx.clone() } else { t.cc.clone() });
+    let compiler = toolchain_compiler.clone().unwrap_or_else(|| config.compiler());
+    let stdlib_flags = config.stdlib_flags(&compiler)?;
+    if !stdlib_flags.is_empty() {
+        ctx.insert("stdlib", &config.build.stdlib);
+    }
+
+    let pgo_flags = match opts.pgo {
+        Some(phase) => pgo_flags(phase, &compiler, verbosity)?,
+        None => vec![],
+    };
+    if !pgo_flags.is_empty() {
+        trace(verbosity, format!("pgo flags: {:?}", pgo_flags));
+        ctx.insert("pgo_flags", &pgo_flags);
+    }
+
+    if let Some(linker) = select_linker(config, opts)? {
+        trace(verbosity, format!("linker: {}", linker));
+        ctx.insert("linker", &linker);
+    }
+
+    let source_date_epoch = if config.build.reproducible {
+        let epoch = source_date_epoch();
+        trace(verbosity, format!("SOURCE_DATE_EPOCH: {}", epoch));
+        ctx.insert("reproducible", &true);
+        Some(epoch)
+    } else {
+        None
+    };
+
+    if !resolved.flags.is_empty() {
+        ctx.insert("build_flags", &resolved.flags);
+    }
+
+    if !resolved.defines.is_empty() {
+        ctx.insert("defines", &resolved.defines);
+    }
+
+    let export_defines: BTreeMap<&String, &String> = if config.is_library() {
+        config.build.export_defines.iter().collect()
+    } else {
+        BTreeMap::new()
+    };
+    ctx.insert("export_defines", &export_defines);
+
+    trace(verbosity, format!("flags: {:?}", resolved.flags));
+    trace(verbosity, format!("defines: {:?}", resolved.defines));
+    trace(verbosity, format!("features: {:?}", resolved.features));
+
+    if !resolved.link_libs.is_empty() {
+        ctx.insert("link_libs", &resolved.link_libs);
+    }
+    if !resolved.lib_dirs.is_empty() {
+        ctx.insert("lib_dirs", &resolved.lib_dirs);
+    }
+    if !resolved.path_dep_libs.is_empty() {
+        trace(verbosity, format!("path dependency libraries: {:?}", resolved.path_dep_libs));
+    }
+    ctx.insert("path_dep_libs", &resolved.path_dep_libs);
+
+    if !vcpkg_packages.is_empty() {
+        ctx.insert("vcpkg_packages", &vcpkg_packages);
+    }
+
+    let bins: Vec<BinContext> = config.bins.iter()
+        .map(|bin| BinContext {
+            name: bin.name.clone(),
+            path: bin.path.clone(),
+            regex: source_regex(&bin.path),
+        })
+        .collect();
+    ctx.insert("bins", &bins);
+    let bin_names: Vec<&String> = bins.iter().map(|bin| &bin.name).collect();
+    ctx.insert("bin_names", &bin_names);
+    let executables: Vec<&String> = (!config.is_library()).then_some(&project_name)
+        .into_iter()
+        .chain(bin_names.iter().copied())
+        .collect();
+    ctx.insert("executables", &executables);
+
+    let inactive_sources: Vec<String> = resolved.excluded_sources
+        .iter()
+        .map(|path| source_regex(&path.to_string_lossy()))
+        .collect();
+    trace(verbosity, format!("sources excluded: {}", inactive_sources.len()));
+    ctx.insert("inactive_sources", &inactive_sources);
+
+    // Exclude globs are expanded here rather than approximated as CMake regexes
+    if config.build.sorted_sources || !config.sources.exclude.is_empty() {
+        let sources = sorted_sources(config, resolved);
+        trace(verbosity, format!("sorted sources: {}", sources.len()));
+        ctx.insert("sorted_sources", &sources);
+    }
+
+    let mut config_h_in = None;
+    if !config.build.checks.is_empty() {
+        let checks = configure_checks(config);
+
+        let mut config_h = String::from("/* Generated by zora from [build.checks]. Do not edit. */\n");
+        for check in &checks {
+            config_h.push_str(&format!("#cmakedefine {} 1\n", check.macro_name));
+        }
+        config_h_in = Some(config_h);
+
+        ctx.insert("checks", &checks);
+    }
+
+    let cmake_lists = Tera::one_off(PROJECT_CMAKE_TEMPLATE, &ctx, false)
+        .context("failed to render CMakeLists.txt template")?;
+
+    let mut configure_args = vec![
+        "-DCMAKE_EXPORT_COMPILE_COMMANDS=ON".to_string(),
+        format!("-DCMAKE_BUILD_TYPE={}", resolved.build_type),
+    ];
+
+    match (toolchain, &opts.target) {
+        (Some(toolchain), _) => configure_args.extend(toolchain_args(config, toolchain, resolved.uses_vcpkg(config))?),
+        (None, Some(t)) => configure_args.push(format!("-DCMAKE_SYSTEM_NAME={}", t)),
+        (None, None) => {}
+    }
+
+    if let Some(triplet) = &opts.triplet {
+        configure_args.push(format!("-DVCPKG_TARGET_TRIPLET={}", triplet));
+        if let Some(arch) = osx_architecture(triplet) {
+            configure_args.push(format!("-DCMAKE_OSX_ARCHITECTURES={}", arch));
+        }
+    }
+
+    // PGO flags are compiler specific, so pin the compiler they were chosen for
+    if toolchain_compiler.is_none() && (!stdlib_flags.is_empty() || !pgo_flags.is_empty()) {
+        let language = if config.is_cpp() { "CXX" } else { "C" };
+        configure_args.push(format!("-DCMAKE_{}_COMPILER={}", language, compiler));
+    }
+
+    if let Some(generator) = select_generator(config, opts)? {
+        configure_args.push("-G".to_string());
+        configure_args.push(generator.to_string());
+    }
+
+    let launcher = compiler_launcher(config)?;
+    if !launcher.is_empty() {
+        trace(verbosity, format!("compiler launcher: {}", launcher.join(" ")));
+        for language in config.cmake_languages() {
+            configure_args.push(format!("-DCMAKE_{}_COMPILER_LAUNCHER={}", language, launcher.join(";")));
+        }
+    }
+
+    Ok(CmakeProject {
+        cmake_lists,
+        config_h_in,
+        configure_args,
+        source_date_epoch,
+    })
+}
+
+/// CMake cache entries for a `[toolchain]`: its compilers, archiver,
+/// sysroot and target, and its toolchain file (chainloaded when vcpkg's
+/// toolchain is in use).
+fn toolchain_args(config: &ProjectConfig, toolchain: &Toolchain, uses_vcpkg: bool) -> Result<Vec<String>> {
+    let mut args = vec![];
+
+    if let Some(file) = &toolchain.toolchain_file {
+        let path = Path::new(file);
+        if !path.is_file() {
+            bail!("[toolchain] toolchain_file {} not found", file);
+        }
+        let variable = if uses_vcpkg { "VCPKG_CHAINLOAD_TOOLCHAIN_FILE" } else { "CMAKE_TOOLCHAIN_FILE" };
+        args.push(format!("-D{}={}", variable, paths::cmake_path(&path.canonicalize()?)));
+    }
+
+    if let Some(cc) = &toolchain.cc {
+        args.push(format!("-DCMAKE_C_COMPILER={}", cc));
+    }
+    if let Some(cxx) = &toolchain.cxx {
+        args.push(format!("-DCMAKE_CXX_COMPILER={}", cxx));
+    }
+    if let Some(ar) = &toolchain.ar {
+        args.push(format!("-DCMAKE_AR={}", ar));
+    }
+    if let Some(sysroot) = &toolchain.sysroot {
+        args.push(format!("-DCMAKE_SYSROOT={}", sysroot));
+    }
+    if let Some(target) = &toolchain.target {
+        for language in config.cmake_languages() {
+            args.push(format!("-DCMAKE_{}_COMPILER_TARGET={}", language, target));
+        }
+    }
+    if let Some(system_name) = &toolchain.system_name {
+        args.push(format!("-DCMAKE_SYSTEM_NAME={}", system_name));
+    }
+
+    Ok(args)
+}
+
+/// Default port of the local icecc daemon.
+const ICECC_DAEMON_PORT: u16 = 10245;
+
+/// Programs to wrap each compile in, e.g. `ccache icecc`, from [build]
+/// ccache/distributed. Missing tools are an error; an unreachable icecc
+/// daemon or unconfigured distcc hosts only warn, since compiles then fall
+/// back to running locally.
+fn compiler_launcher(config: &ProjectConfig) -> Result<Vec<String>> {
+    let mut launcher = vec![];
+
+    if config.build.ccache {
+        if !on_path("ccache") {
+            bail!("[build] ccache is enabled but ccache was not found on PATH");
+        }
+        launcher.push("ccache".to_string());
+    }
+
+    let Some(tool) = &config.build.distributed else {
+        return Ok(launcher);
+    };
+
+    if tool != "icecc" && tool != "distcc" {
+        bail!("Unknown distributed compiler '{}'. Use \"icecc\" or \"distcc\"", tool);
+    }
+
+    if !on_path(tool) {
+        bail!("[build] distributed = \"{}\" but {} was not found on PATH", tool, tool);
+    }
+
+    if tool == "icecc" {
+        let daemon = std::net::TcpStream::connect_timeout(
+            &([127, 0, 0, 1], ICECC_DAEMON_PORT).into(),
+            Duration::from_millis(200),
+        );
+        if daemon.is_err() {
+            eprintln!("{} icecc daemon (iceccd) is not reachable on port {}; compiles will run locally",
+                "Warning:".yellow(),
+                ICECC_DAEMON_PORT
+            );
+        }
+    } else {
+        let has_hosts = std::env::var_os("DISTCC_HOSTS").is_some()
+            || std::env::var_os("HOME")
+                .map(|home| Path::new(&home).join(".distcc/hosts").exists())
+                .unwrap_or(false)
+            || Path::new("/etc/distcc/hosts").exists();
+        if !has_hosts {
+            eprintln!("{} no distcc hosts configured (DISTCC_HOSTS or ~/.distcc/hosts); compiles will run locally",
+                "Warning:".yellow()
+            );
+        }
+    }
+
+    launcher.push(tool.clone());
+
+    Ok(launcher)
+}
+
+/// Linkers `-fuse-ld=` accepts, with the executables that provide them.
+const LINKERS: &[(&str, &[&str])] = &[
+    ("mold", &["ld.mold", "mold"]),
+    ("lld", &["ld.lld"]),
+    ("gold", &["ld.gold"]),
+];
+
+/// The linker from --linker or [build] linker, checked to be installed.
+/// Without either, picks mold or lld on Linux, where they're much faster
+/// than the default, when installed and accepted by the compiler.
+pub fn select_linker(config: &ProjectConfig, opts: &BuildOptions) -> Result<Option<String>> {
+    let installed = |linker: &str| LINKERS.iter()
+        .find(|(name, _)| *name == linker)
+        .is_some_and(|(_, programs)| programs.iter().any(|p| on_path(p)));
+
+    let Some(linker) = opts.linker.as_ref().or(config.build.linker.as_ref()) else {
+        if !cfg!(target_os = "linux") {
+            return Ok(None);
+        }
+        let compiler = config.compiler();
+        return Ok(["mold", "lld"].into_iter()
+            .find(|l| installed(l) && accepts_linker(&compiler, l))
+            .map(String::from));
+    };
+
+    if linker == "system" {
+        return Ok(None);
+    }
+    if !LINKERS.iter().any(|(name, _)| name == linker) {
+        bail!("Unknown linker '{}'. Use \"mold\", \"lld\", \"gold\" or \"system\"", linker);
+    }
+    if !installed(linker) {
+        bail!("Linker '{}' was not found on PATH", linker);
+    }
+
+    Ok(Some(linker.clone()))
+}
+
+/// Whether `compiler` links a trivial program with `-fuse-ld=<linker>`. GCC
+/// only takes `-fuse-ld=mold` from 12.1 on.
+fn accepts_linker(compiler: &str, linker: &str) -> bool {
+    let output = std::env::temp_dir().join(format!("zora-linker-probe-{}", std::process::id()));
+    let child = Command::new(compiler)
+        .args(["-x", "c", "-", "-o"])
+        .arg(&output)
+        .arg(format!("-fuse-ld={}", linker))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return false;
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(b"int main(void) { return 0; }\n");
+    }
+    let accepted = child.wait().is_ok_and(|status| status.success());
+    let _ = fs::remove_file(&output);
+    accepted
+}
+
+/// `[build] generator` names, the CMake generator each selects and the
+/// program it needs on PATH.
+const GENERATORS: &[(&str, &str, &str)] = &[
+    ("ninja", "Ninja", "ninja"),
+    ("make", "Unix Makefiles", "make"),
+];
+
+/// The CMake generator from --generator or [build] generator, checked to be
+/// installed. `None` leaves the choice to CMake.
+pub fn select_generator(config: &ProjectConfig, opts: &BuildOptions) -> Result<Option<&'static str>> {
+    let Some(name) = opts.generator.as_ref().or(config.build.generator.as_ref()) else {
+        return Ok(None);
+    };
+
+    if name == "default" {
+        return Ok(None);
+    }
+    let Some((_, generator, program)) = GENERATORS.iter().find(|(n, _, _)| n == name) else {
+        bail!("Unknown generator '{}'. Use \"ninja\", \"make\" or \"default\"", name);
+    };
+    if !on_path(program) {
+        bail!("Generator '{}' needs `{}`, which was not found on PATH. Install it or use generator = \"default\"", name, program);
+    }
+
+    Ok(Some(generator))
+}
+
+/// Compile and link flags for a PGO phase. Profile data lives in
+/// `target/pgo`: generating clears it, and for clang using it first merges
+/// the raw profiles with `llvm-profdata`.
+fn pgo_flags(phase: PgoPhase, compiler: &str, verbosity: Verbosity) -> Result<Vec<String>> {
+    let dir = std::env::current_dir()?.join(paths::target_root()).join("pgo");
+    let is_clang = Command::new(compiler)
+        .arg("--version")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains("clang"))
+        .unwrap_or(false);
+
+    if phase == PgoPhase::Generate {
+        // Stale profiles from an older binary would be rejected or mislead the optimizer
+        if dir.exists() {
+            fs::remove_dir_all(&dir).context("failed to clear target/pgo")?;
+        }
+        fs::create_dir_all(&dir).context("failed to create target/pgo")?;
+        return Ok(vec![format!("-fprofile-generate={}", dir.display())]);
+    }
+
+    if !is_clang {
+        let has_data = WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .any(|e| e.path().extension().is_some_and(|ext| ext == "gcda"));
+        if !has_data {
+            bail!("No profile data in {}. Run 'zora build --pgo-generate' and exercise the program first.", dir.display());
+        }
+
+        return Ok(vec![
+            format!("-fprofile-use={}", dir.display()),
+            "-fprofile-correction".to_string(),
+            "-Wno-missing-profile".to_string(),
+        ]);
+    }
+
+    let raw: Vec<PathBuf> = fs::read_dir(&dir)
+        .map(|entries| entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "profraw"))
+            .collect())
+        .unwrap_or_default();
+
+    let profdata = dir.join("default.profdata");
+    if !raw.is_empty() {
+        let mut merge = if on_path("llvm-profdata") {
+            Command::new("llvm-profdata")
+        } else if cfg!(target_os = "macos") {
+            let mut xcrun = Command::new("xcrun");
+            xcrun.arg("llvm-profdata");
+            xcrun
+        } else {
+            bail!("llvm-profdata not found. Install LLVM to merge clang profile data.");
+        };
+        merge.arg("merge").arg(format!("-output={}", profdata.display())).args(&raw);
+
+        if !run_tool(&mut merge, verbosity)? {
+            bail!("llvm-profdata merge failed");
+        }
+    } else if !profdata.exists() {
+        bail!("No profile data in {}. Run 'zora build --pgo-generate' and exercise the program first.", dir.display());
+    }
+
+    Ok(vec![format!("-fprofile-use={}", profdata.display())])
+}
+
+fn on_path(program: &str) -> bool {
+    let exe_name = if cfg!(windows) { format!("{}.exe", program) } else { program.to_string() };
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).any(|dir| dir.join(&exe_name).is_file()))
+        .unwrap_or(false)
+}
+
+/// The generator CMake configured `build_dir` with, from its cache.
+fn cmake_generator(build_dir: &str) -> Option<String> {
+    let cache = fs::read_to_string(Path::new(build_dir).join("CMakeCache.txt")).ok()?;
+    cache.lines()
+        .find_map(|line| line.strip_prefix("CMAKE_GENERATOR:INTERNAL="))
+        .map(|g| g.to_string())
+}
+
+/// Runs the build with its output captured, then printed, so that after a
+/// `--keep-going` build every file that failed to compile can be listed
+/// together.
+fn run_keep_going(cmd: &mut Command, verbosity: Verbosity) -> Result<bool> {
+    if verbosity.commands() {
+        println!("  {} {}", "Running".dimmed(), command_line(cmd));
+    }
+
+    let output = cmd.output().context("failed to run cmake")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    print!("{}", stdout);
+    eprint!("{}", stderr);
+
+    if !output.status.success() {
+        let files = failing_files(&format!("{}\n{}", stdout, stderr));
+        if !files.is_empty() {
+            println!("\n{} {} file(s) failed to compile:", "✗".red().bold(), files.len());
+            for file in &files {
+                println!("  {} {}", "✗".red(), file);
+            }
+        }
+    }
+
+    Ok(output.status.success())
+}
+
+/// Source files named in gcc/clang `path:line:col: error:` diagnostics, in
+/// first-seen order.
+fn failing_files(output: &str) -> Vec<String> {
+    let mut files: Vec<String> = vec![];
+
+    for line in output.lines() {
+        let Some((location, _)) = line.split_once(": error:")
+            .or_else(|| line.split_once(": fatal error:")) else {
+            continue;
+        };
+
+        // Strip ":line:col", leaving the path (which may itself contain ':' on Windows)
+        let mut path = location;
+        for _ in 0..2 {
+            if let Some((rest, suffix)) = path.rsplit_once(':') {
+                if suffix.chars().all(|c| c.is_ascii_digit()) {
+                    path = rest;
+                }
+            }
+        }
+
+        if !path.is_empty() && !files.iter().any(|f| f == path) {
+            files.push(path.to_string());
+        }
+    }
+
+    files
+}
+
+/// Links compile_commands.json from a build dir to where editors look for it.
+/// Returns false when the build dir has none (i.e. it was never configured).
+pub fn link_compile_commands(build_dir: &Path) -> Result<bool> {
+    let src = build_dir.join("compile_commands.json");
+    let dst = paths::compile_commands();
+    let dst = dst.as_path();
+
+    if dst.exists() || dst.is_symlink() {
+        fs::remove_file(dst).ok();
+    }
+
+    if !src.exists() {
+        return Ok(false);
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&src, dst)?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(&src, dst)?;
+
+    Ok(true)
+}
+
+/// Where `split_debug` puts the debug info for an executable.
+pub fn debug_info_path(exe: &Path) -> PathBuf {
+    let mut name = exe.as_os_str().to_owned();
+    name.push(if cfg!(target_os = "macos") { ".dSYM" } else { ".debug" });
+    PathBuf::from(name)
+}
+
+/// Moves an executable's debug info into a separate file and strips it.
+/// On Linux the stripped binary gets a debuglink so gdb still finds it.
+fn split_debug_info(exe: &Path, verbosity: Verbosity) -> Result<()> {
+    let debug_file = debug_info_path(exe);
+
+    let steps: Vec<Command> = if cfg!(target_os = "macos") {
+        let mut dsymutil = Command::new("dsymutil");
+        dsymutil.arg(exe).arg("-o").arg(&debug_file);
+        let mut strip = Command::new("strip");
+        strip.arg("-S").arg(exe);
+        vec![dsymutil, strip]
+    } else if cfg!(windows) {
+        // MSVC already keeps debug info in .pdb files
+        vec![]
+    } else {
+        // --add-gnu-debuglink records the file name only, so run beside it
+        let dir = exe.parent().unwrap_or(Path::new("."));
+        let exe_name = exe.file_name().unwrap_or_default();
+        let debug_name = debug_file.file_name().unwrap_or_default();
+
+        let mut keep = Command::new("objcopy");
+        keep.current_dir(dir).arg("--only-keep-debug").arg(exe_name).arg(debug_name);
+        let mut strip = Command::new("objcopy");
+        strip.current_dir(dir)
+            .arg("--strip-debug")
+            .arg(format!("--add-gnu-debuglink={}", debug_name.to_string_lossy()))
+            .arg(exe_name);
+        vec![keep, strip]
+    };
+
+    for mut step in steps {
+        if !run_tool(&mut step, verbosity)? {
+            bail!("Failed to split debug info from {}", exe.display());
+        }
+    }
+
+    if verbosity.commands() {
+        println!("  {} {}", "Split".green(), debug_file.display());
+    }
+
+    Ok(())
+}
+
+/// Copies an executable's split debug info (file or `.dSYM` bundle) into
+/// `dest_dir`, if the build produced any. Returns whether anything was copied.
+pub fn copy_debug_info(exe: &Path, dest_dir: &Path) -> Result<bool> {
+    let debug_file = debug_info_path(exe);
+    if !debug_file.exists() {
+        return Ok(false);
+    }
+
+    for entry in WalkDir::new(&debug_file).into_iter().filter_map(|e| e.ok()) {
+        let relative = entry.path().strip_prefix(exe.parent().unwrap_or(Path::new("")))?;
+        let dest = dest_dir.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// A zora library reached through `{ path = ".." }` dependencies.
+struct PathDep {
+    name: String,
+    dir: PathBuf,
+    config: ProjectConfig,
+}
+
+impl PathDep {
+    /// Where the dependency's own build leaves its library for the slot of
+    /// `opts`, looked up like `target_artifacts`. Before the first build
+    /// that's the static library it will produce.
+    fn library(&self, opts: &BuildOptions) -> PathBuf {
+        let built = opts.built_path_deps.iter().find(|(dir, _)| *dir == self.dir);
+        let target_dir = match (built, paths::target_dir(), paths::out_dir()) {
+            (Some((_, target_dir)), _, _) => target_dir.clone(),
+            (None, Some(target_dir), _) => path_dep_out_dir(&target_dir, &self.name),
+            (None, None, Some(out_dir)) => path_dep_out_dir(&out_dir, &self.name).join("target"),
+            (None, None, None) => self.dir.join("target"),
+        }.join(opts.slot());
+
+        let name = &self.config.name;
+        artifact_candidates(name, true).into_iter()
+            .map(|(file, _)| target_dir.join(file))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| target_dir.join(format!("lib{}.a", name)))
+    }
+}
+
+/// With `--out-dir`, path dependencies build under `<out-dir>/deps/<name>`;
+/// with a target dir, their artifacts go to `<target-dir>/deps/<name>`.
+fn path_dep_out_dir(out_dir: &Path, name: &str) -> PathBuf {
+    out_dir.join("deps").join(name)
+}
+
+/// Every zora library the project reaches through path dependencies, each
+/// after the libraries it depends on itself. Path dependencies without a
+/// project.toml only contribute headers. Fails on a dependency cycle.
+fn path_dep_libraries(config: &ProjectConfig) -> Result<Vec<PathDep>> {
+    let root = Path::new(".").canonicalize()?;
+    let mut stack = vec![(config.name.clone(), root.clone())];
+    let mut found = vec![];
+    collect_path_deps(config, &root, &mut stack, &mut found)?;
+    Ok(found)
+}
+
+fn collect_path_deps(
+    config: &ProjectConfig,
+    dir: &Path,
+    stack: &mut Vec<(String, PathBuf)>,
+    found: &mut Vec<PathDep>,
+) -> Result<()> {
+    for (name, path) in config.path_deps() {
+        let dep_dir = dir.join(&path);
+        if !dep_dir.join("project.toml").exists() {
+            continue;
+        }
+        let dep_dir = dep_dir.canonicalize()
+            .with_context(|| format!("failed to resolve path dependency '{}' ({})", name, path))?;
+
+        if let Some(start) = stack.iter().position(|(_, d)| *d == dep_dir) {
+            let cycle: Vec<&str> = stack[start..].iter()
+                .map(|(n, _)| n.as_str())
+                .chain([name.as_str()])
+                .collect();
+            bail!("Cyclic path dependency: {}", cycle.join(" → "));
+        }
+        if found.iter().any(|dep: &PathDep| dep.dir == dep_dir) {
+            continue;
+        }
+
+        let dep = ProjectConfig::load_from(&dep_dir)
+            .with_context(|| format!("failed to load path dependency '{}'", name))?;
+        if !dep.is_library() {
+            continue;
+        }
+
+        stack.push((name.clone(), dep_dir.clone()));
+        collect_path_deps(&dep, &dep_dir, stack, found)?;
+        stack.pop();
+
+        found.push(PathDep { name, dir: dep_dir, config: dep });
+    }
+
+    Ok(())
+}
+
+/// Builds path dependency libraries in order, each in its own directory with
+/// the same profile and slot as the dependent project.
+fn build_path_deps(deps: &[PathDep], opts: &BuildOptions) -> Result<()> {
+    let root = std::env::current_dir()?;
+    let out_dir = paths::out_dir();
+    let target_dir = paths::target_dir();
+
+    for dep in deps {
+        if opts.verbosity.commands() {
+            println!("  {} path dependency {} ({})", "Building".dimmed(), dep.name, dep.dir.display());
+        }
+
+        let dep_opts = BuildOptions {
+            mode: opts.mode.clone(),
+            build_dir: opts.build_dir.clone(),
+            verbosity: opts.verbosity,
+            jobs: opts.jobs,
+            target: opts.target.clone(),
+            static_link: opts.static_link,
+            linker: opts.linker.clone(),
+            generator: opts.generator.clone(),
+            triplet: opts.triplet.clone(),
+            force: opts.force,
+            quiet: opts.quiet,
+            skip_path_deps: true,
+            ..Default::default()
+        };
+
+        if let Some(out_dir) = &out_dir {
+            std::env::set_var(paths::OUT_DIR_ENV, path_dep_out_dir(out_dir, &dep.name));
+        }
+        if let Some(target_dir) = &target_dir {
+            std::env::set_var(paths::TARGET_DIR_ENV, path_dep_out_dir(target_dir, &dep.name));
+        }
+        std::env::set_current_dir(&dep.dir)?;
+        let result = run(dep_opts);
+        std::env::set_current_dir(&root)?;
+        if let Some(out_dir) = &out_dir {
+            std::env::set_var(paths::OUT_DIR_ENV, out_dir);
+        }
+        if let Some(target_dir) = &target_dir {
+            std::env::set_var(paths::TARGET_DIR_ENV, target_dir);
+        }
+
+        result.with_context(|| format!("failed to build path dependency '{}'", dep.name))?;
+    }
+
+    Ok(())
+}
+
+/// Compiles each `[[build-dependency]]` for the host into .build/host (when
+/// its sources changed) and runs it, so generated sources exist before CMake
+/// globs the source dirs.
+fn run_build_dependencies(config: &ProjectConfig, mode: &str, verbosity: Verbosity, quiet: bool) -> Result<()> {
+    let host_dir = paths::build_root().join("host");
+    fs::create_dir_all(&host_dir).context("failed to create .build/host")?;
+
+    for dep in &config.build_dependencies {
+        let exe = host_dir.join(if cfg!(windows) { format!("{}.exe", dep.name) } else { dep.name.clone() });
+        let sources = build_dependency_sources(dep)?;
+
+        let built = fs::metadata(&exe).and_then(|m| m.modified()).ok();
+        let stale = built.is_none() || sources.iter().any(|source| {
+            fs::metadata(source).and_then(|m| m.modified()).ok() > built
+        });
+
+        if stale {
+            let is_cpp = sources.iter().any(|source| {
+                source.extension().is_some_and(|ext| ["cpp", "cc", "cxx"].iter().any(|e| ext == *e))
+            });
+            let compiler = host_compiler(is_cpp);
+
+            let mut cmd = Command::new(&compiler);
+            cmd.args(&sources)
+                .args(config.include_flags())
+                .args(&dep.flags)
+                .arg("-o")
+                .arg(&exe);
+
+            if !run_tool(&mut cmd, verbosity)? {
+                bail!("Failed to compile build dependency '{}' with {}", dep.name, compiler);
+            }
+            if !quiet {
+                println!("  {} host tool {}", "Compiled".green(), dep.name);
+            }
+        }
+
+        let mut run = Command::new(&exe);
+        run.args(&dep.args)
+            .env("ZORA_PROFILE", mode)
+            .env("ZORA_PROJECT_ROOT", std::env::current_dir()?);
+        // Under `quiet` stdout belongs to the program run after the build
+        if quiet {
+            run.stdout(std::io::stderr());
+        }
+
+        if verbosity.commands() {
+            println!("  {} {}", "Running".dimmed(), command_line(&run));
+        }
+
+        let status = run.status()
+            .with_context(|| format!("failed to run build dependency '{}'", dep.name))?;
+        if !status.success() {
+            bail!("Build dependency '{}' failed ({})", dep.name, status);
+        }
+    }
+
+    Ok(())
+}
+
+fn build_dependency_sources(dep: &BuildDependency) -> Result<Vec<PathBuf>> {
+    let path = Path::new(&dep.path);
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        bail!("Build dependency '{}': {} not found", dep.name, dep.path);
+    }
+
+    let sources: Vec<PathBuf> = WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|ext| ["c", "cpp", "cc", "cxx"].iter().any(|e| ext == *e)))
+        .collect();
+
+    if sources.is_empty() {
+        bail!("Build dependency '{}': no C/C++ sources in {}", dep.name, dep.path);
+    }
+    Ok(sources)
+}
+
+/// The compiler for host tools: HOST_CC/HOST_CXX, else the system default.
+/// CC/CXX aren't used since they may be cross compilers.
+fn host_compiler(is_cpp: bool) -> String {
+    let (var, default) = if is_cpp { ("HOST_CXX", "c++") } else { ("HOST_CC", "cc") };
+    std::env::var(var).unwrap_or_else(|_| default.to_string())
+}
+
+/// Performs the `[build] copy` rules. A directory is copied recursively, a
+/// glob's matches keep their path below its first wildcard component, and a
+/// plain file lands directly in `to`.
+fn copy_files(
+    rules: &[CopyRule],
+    profile: &str,
+    target_dir: &str,
+    source_date_epoch: Option<u64>,
+) -> Result<usize> {
+    let mut copied = 0;
+
+    for rule in rules {
+        let to = PathBuf::from(rule.to
+            .replace("{profile}", profile)
+            .replace("{target_dir}", target_dir));
+
+        let from = Path::new(&rule.from);
+        let (base, sources): (PathBuf, Vec<PathBuf>) = if rule.from.contains(['*', '?', '[']) {
+            let base = from.components()
+                .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+                .collect();
+            let matches = glob::glob(&rule.from)
+                .with_context(|| format!("invalid [build] copy pattern '{}'", rule.from))?
+                .filter_map(|entry| entry.ok())
+                .filter(|path| path.is_file())
+                .collect();
+            (base, matches)
+        } else if from.is_dir() {
+            // Directories copy their contents, so "assets" -> "target/dev/assets"
+            let files = WalkDir::new(from)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.into_path())
+                .collect();
+            (from.to_path_buf(), files)
+        } else {
+            let base = from.parent().map(Path::to_path_buf).unwrap_or_default();
+            (base, if from.is_file() { vec![from.to_path_buf()] } else { vec![] })
+        };
+
+        if sources.is_empty() {
+            bail!("[build] copy: '{}' matched no files", rule.from);
+        }
+
+        for source in sources {
+            let relative = source.strip_prefix(&base).unwrap_or(&source);
+            let dest = to.join(relative);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("failed to create {}", parent.display()))?;
+            }
+            copy_artifact(&source, &dest, source_date_epoch)?;
+            copied += 1;
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Copies a build artifact into target/. For reproducible builds the copy's
+/// mtime is pinned to SOURCE_DATE_EPOCH instead of the time of the copy.
+fn copy_artifact(from: &Path, to: &Path, source_date_epoch: Option<u64>) -> Result<()> {
+    fs::copy(from, to)
+        .with_context(|| format!("failed to copy {}", from.display()))?;
+
+    if let Some(epoch) = source_date_epoch {
+        let mtime = UNIX_EPOCH + Duration::from_secs(epoch);
+        fs::File::options()
+            .write(true)
+            .open(to)
+            .and_then(|f| f.set_modified(mtime))
+            .with_context(|| format!("failed to set timestamp on {}", to.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Hex digits of the SHA-256 kept in `[build] hash_artifacts` names.
+const ARTIFACT_HASH_LEN: usize = 6;
+
+/// Copies an artifact in target/ to `<stem>-<hash>.<ext>` and records the
+/// copy's file name in `<file>.latest` next to it.
+fn hash_artifact(artifact: &Path) -> Result<PathBuf> {
+    let content = fs::read(artifact)
+        .with_context(|| format!("failed to read {}", artifact.display()))?;
+    let hash: String = Sha256::digest(&content).iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let stem = artifact.file_stem().unwrap_or_default().to_string_lossy();
+    let mut hashed_name = format!("{}-{}", stem, &hash[..ARTIFACT_HASH_LEN]);
+    if let Some(ext) = artifact.extension() {
+        hashed_name.push('.');
+        hashed_name.push_str(&ext.to_string_lossy());
+    }
+
+    let hashed = artifact.with_file_name(&hashed_name);
+    fs::copy(artifact, &hashed)
+        .with_context(|| format!("failed to copy {}", artifact.display()))?;
+
+    let file_name = artifact.file_name().unwrap_or_default().to_string_lossy();
+    let pointer = artifact.with_file_name(format!("{}.latest", file_name));
+    fs::write(&pointer, format!("{}\n", hashed_name))
+        .with_context(|| format!("failed to write {}", pointer.display()))?;
+
+    Ok(hashed)
+}
+
+/// The SOURCE_DATE_EPOCH to build with: the environment's if set, otherwise
+/// the last commit time, otherwise zero.
+fn source_date_epoch() -> u64 {
+    if let Some(epoch) = std::env::var("SOURCE_DATE_EPOCH").ok().and_then(|v| v.trim().parse().ok()) {
+        return epoch;
+    }
+
+    Command::new("git")
+        .args(["log", "-1", "--format=%ct"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// What a CMake target produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    Executable,
+    StaticLib,
+    /// A shared library, or on Windows its import library
+    SharedLib,
+}
+
+/// A file produced by the build, in the CMake build dir or copied to target/.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub kind: ArtifactKind,
+    pub path: PathBuf,
+}
+
+/// Artifact file names `target` may have: `name`/`name.exe` for an
+/// executable, `lib<name>.a` and friends or `<name>.lib`/`.dll` for a library.
+fn artifact_candidates(target: &str, is_library: bool) -> Vec<(String, ArtifactKind)> {
+    if !is_library {
+        let exe = if cfg!(windows) { format!("{}.exe", target) } else { target.to_string() };
+        return vec![(exe, ArtifactKind::Executable)];
+    }
+
+    vec![
+        (format!("lib{}.a", target), ArtifactKind::StaticLib),
+        (format!("lib{}.so", target), ArtifactKind::SharedLib),
+        (format!("lib{}.dylib", target), ArtifactKind::SharedLib),
+        (format!("{}.lib", target), ArtifactKind::StaticLib),
+        (format!("{}.dll", target), ArtifactKind::SharedLib),
+    ]
+}
+
+/// Asks CMake to write its codemodel through the file API on the next
+/// configure, so the build's real artifact paths can be read back.
+fn request_codemodel(build_dir: &Path) -> Result<()> {
+    let query_dir = build_dir.join(".cmake/api/v1/query");
+    fs::create_dir_all(&query_dir)?;
+    let query = query_dir.join("codemodel-v2");
+    if !query.exists() {
+        fs::write(&query, "").context("failed to write CMake file API query")?;
+    }
+    Ok(())
+}
+
+/// The artifacts of the `targets` in a CMake build dir, paired with their
+/// target name. Read from the file API reply for the `build_type`
+/// configuration, which also covers multi-config generators' `<Config>/`
+/// subdirs; without a reply (CMake older than 3.14) the build dir and its
+/// immediate subdirs are searched for the usual file names.
+fn built_artifacts(build_dir: &Path, build_type: &str, targets: &[&str], main_is_library: bool) -> Vec<(String, Artifact)> {
+    if let Some(artifacts) = codemodel_artifacts(build_dir, build_type) {
+        return artifacts.into_iter()
+            .filter(|(target, _)| targets.contains(&target.as_str()))
+            .collect();
+    }
+
+    let mut dirs = vec![build_dir.to_path_buf()];
+    dirs.extend(["Debug", "Release", "RelWithDebInfo", "MinSizeRel"].iter()
+        .map(|config| build_dir.join(config))
+        .filter(|dir| dir.is_dir()));
+
+    let mut found = vec![];
+    for (i, target) in targets.iter().enumerate() {
+        // The project's own target is last; the others are [[bin]]s
+        let is_library = main_is_library && i == targets.len() - 1;
+        for (file, kind) in artifact_candidates(target, is_library) {
+            if let Some(path) = dirs.iter().map(|dir| dir.join(&file)).find(|path| path.is_file()) {
+                found.push((target.to_string(), Artifact { kind, path }));
+            }
+        }
+    }
+    found
+}
+
+/// Artifacts listed in the newest codemodel reply of the CMake file API.
+fn codemodel_artifacts(build_dir: &Path, build_type: &str) -> Option<Vec<(String, Artifact)>> {
+    let reply_dir = build_dir.join(".cmake/api/v1/reply");
+    let read_json = |file: &str| -> Option<serde_json::Value> {
+        serde_json::from_str(&fs::read_to_string(reply_dir.join(file)).ok()?).ok()
+    };
+
+    let index = fs::read_dir(&reply_dir).ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("index-") && name.ends_with(".json"))
+        .max()?;
+    let codemodel_file = read_json(&index)?
+        .pointer("/reply/codemodel-v2/jsonFile")?
+        .as_str()?
+        .to_string();
+    let codemodel = read_json(&codemodel_file)?;
+
+    let configurations = codemodel.get("configurations")?.as_array()?;
+    let configuration = configurations.iter()
+        .find(|c| c.get("name").and_then(|n| n.as_str()) == Some(build_type))
+        .or_else(|| configurations.first())?;
+
+    let mut artifacts = vec![];
+    for target in configuration.get("targets")?.as_array()? {
+        let Some(target) = target.get("jsonFile").and_then(|f| f.as_str()).and_then(read_json) else {
+            continue;
+        };
+        let kind = match target.get("type").and_then(|t| t.as_str()) {
+            Some("EXECUTABLE") => ArtifactKind::Executable,
+            Some("STATIC_LIBRARY") => ArtifactKind::StaticLib,
+            Some("SHARED_LIBRARY") | Some("MODULE_LIBRARY") => ArtifactKind::SharedLib,
+            _ => continue,
+        };
+        let name = target.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+
+        for artifact in target.get("artifacts").and_then(|a| a.as_array()).into_iter().flatten() {
+            let Some(path) = artifact.get("path").and_then(|p| p.as_str()) else {
+                continue;
+            };
+            let path = build_dir.join(path);
+            if path.is_file() {
+                artifacts.push((name.to_string(), Artifact { kind, path }));
+            }
+        }
+    }
+
+    Some(artifacts)
+}
+
+/// CMake build type of a mode.
+pub fn build_type(mode: &str) -> &'static str {
+    if mode == "release" { "Release" } else { "Debug" }
+}
+
+/// The artifact CMake built in `.build/<slot>` for `name` (the project by
+/// default). Its mtime is the real build time even when `[build]
+/// reproducible` pins the target/ copy to SOURCE_DATE_EPOCH, so staleness
+/// checks compare against this one.
+pub fn build_dir_artifact(config: &ProjectConfig, name: Option<&str>, slot: &str, build_type: &str) -> Option<Artifact> {
+    let name = name.unwrap_or(&config.name);
+    let is_library = config.is_library() && name == config.name;
+    built_artifacts(&paths::build_root().join(slot), build_type, &[name], is_library).into_iter()
+        .map(|(_, artifact)| artifact)
+        .next()
+}
+
+/// The artifacts `zora build` copied to `target/<slot>` for `name` (the
+/// project by default): the executable, or every library file of a library
+/// project.
+pub fn target_artifacts(config: &ProjectConfig, name: Option<&str>, slot: &str) -> Vec<Artifact> {
+    let name = name.unwrap_or(&config.name);
+    let target_dir = paths::target_root().join(slot);
+    let is_library = config.is_library() && name == config.name;
+
+    artifact_candidates(name, is_library).into_iter()
+        .map(|(file, kind)| Artifact { kind, path: target_dir.join(file) })
+        .filter(|artifact| artifact.path.is_file())
+        .collect()
+}
+
+/// Path of a built executable in `target/<slot>`, where the slot is the
+/// mode unless the build used `--build-dir`. When nothing was built this
+/// is where it would be; a library is an error.
+pub fn get_executable_path(name_opt: Option<String>, slot: &str) -> Result<std::path::PathBuf> {
+    let config = ProjectConfig::load()?;
+    let name = name_opt.unwrap_or_else(|| config.name.clone());
+
+    match target_artifacts(&config, Some(&name), slot).into_iter().next() {
+        Some(artifact) if artifact.kind == ArtifactKind::Executable => Ok(artifact.path),
+        Some(artifact) => bail!("'{}' is a library ({}), not an executable", name, artifact.path.display()),
+        None => {
+            let (file, _) = &artifact_candidates(&name, false)[0];
+            Ok(paths::target_root().join(slot).join(file))
+        }
+    }
+}
+
+/// The artifact `zora build` left in `target/<slot>` for `name` (the project
+/// by default): the executable, or the library file for a library project.
+/// With `[build] hash_artifacts` this is the newest content-hashed copy.
+pub fn artifact_path(config: &ProjectConfig, name: Option<&str>, slot: &str) -> Result<PathBuf> {
+    let name = name.unwrap_or(&config.name);
+
+    let Some(Artifact { path: artifact, .. }) = target_artifacts(config, Some(name), slot).into_iter().next() else {
+        bail!("No artifact for '{}' in {}. Run 'zora build' first.", name, paths::target_root().join(slot).display());
+    };
+
+    if !config.build.hash_artifacts {
+        return Ok(artifact);
+    }
 
-    let target_dir = format!("target/{}", mode);
-    Ok(Path::new(&target_dir).join(exe_name))
+    let file_name = artifact.file_name().unwrap_or_default().to_string_lossy();
+    let pointer = artifact.with_file_name(format!("{}.latest", file_name));
+    let hashed_name = fs::read_to_string(&pointer)
+        .with_context(|| format!("failed to read {}. Rebuild with [build] hash_artifacts enabled.", pointer.display()))?;
+    Ok(artifact.with_file_name(hashed_name.trim()))
 }
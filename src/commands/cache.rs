@@ -4,26 +4,33 @@ use colored::Colorize;
 use std::fs;
 use std::path::Path;
 
+use crate::paths;
+
 pub fn stats() -> Result<()> {
     println!("\n{}", "Build Cache Statistics".bright_cyan().bold());
     println!("{}", "─".repeat(40));
 
     let cache_dirs = vec![
-        ("Build artifacts", ".build"),
-        ("Target directory", "target"),
-        ("vcpkg cache", "vcpkg_installed"),
+        ("Build artifacts", paths::build_root()),
+        ("Target directory", paths::target_root()),
     ];
 
     let mut total_size = 0u64;
 
     for (name, dir) in cache_dirs {
-        if Path::new(dir).exists() {
-            let size = dir_size(dir)?;
+        if dir.exists() {
+            let size = dir_size(&dir)?;
             total_size += size;
             println!("{}: {}", name, format_size(size));
         } else {
             println!("{}: {}", name, "not found".dimmed());
         }
+
+        // The vcpkg packages live in the build root and are counted with it
+        let installed = crate::vcpkg::installed_dir();
+        if dir == paths::build_root() && installed.exists() {
+            println!("  {}", format!("vcpkg packages: {}", format_size(dir_size(&installed)?)).dimmed());
+        }
     }
 
     println!("{}", "─".repeat(40));
@@ -36,14 +43,14 @@ pub fn stats() -> Result<()> {
 pub fn clear() -> Result<()> {
     println!("{}", "Clearing build cache...".bright_cyan());
 
-    let dirs_to_clear = vec![".build", "target"];
+    let dirs_to_clear = vec![paths::build_root(), paths::target_root()];
     let mut cleared = 0;
 
     for dir in dirs_to_clear {
-        if Path::new(dir).exists() {
-            fs::remove_dir_all(dir)?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
             cleared += 1;
-            println!("  {} {}/", "Cleared".red(), dir);
+            println!("  {} {}/", "Cleared".red(), dir.display());
         }
     }
 
@@ -62,8 +69,9 @@ pub fn prune() -> Result<()> {
     let mut pruned = 0;
 
     // Remove old build directories (except current)
-    if Path::new(".build").exists() {
-        for entry in fs::read_dir(".build")? {
+    let build_root = paths::build_root();
+    if build_root.exists() {
+        for entry in fs::read_dir(&build_root)? {
             let entry = entry?;
             let path = entry.path();
             if path.is_dir() {
@@ -87,7 +95,7 @@ pub fn prune() -> Result<()> {
     Ok(())
 }
 
-fn dir_size(path: impl AsRef<Path>) -> Result<u64> {
+pub fn dir_size(path: impl AsRef<Path>) -> Result<u64> {
     let mut size = 0;
     if path.as_ref().is_dir() {
         for entry in fs::read_dir(path)? {
@@ -103,7 +111,7 @@ fn dir_size(path: impl AsRef<Path>) -> Result<u64> {
     Ok(size)
 }
 
-fn format_size(size: u64) -> String {
+pub fn format_size(size: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -4,8 +4,9 @@ use std::process::Command;
 use walkdir::WalkDir;
 
 use crate::config::ProjectConfig;
+use crate::output::{command_line, trace, Verbosity};
 
-pub fn run(verbose: bool) -> Result<()> {
+pub fn run(verbosity: Verbosity) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
@@ -14,19 +15,20 @@ pub fn run(verbose: bool) -> Result<()> {
     
     println!("{}", "Checking project...".bright_cyan());
 
-    let compiler = if config.is_cpp() { "g++" } else { "gcc" };
+    let compiler = config.compiler();
+    let stdlib_flags = config.stdlib_flags(&compiler)?;
 
     // Check compiler is available
-    let compiler_check = Command::new(compiler)
+    let compiler_check = Command::new(&compiler)
         .arg("--version")
         .output();
 
     match compiler_check {
         Ok(output) if output.status.success() => {
-            if verbose {
+            if verbosity.commands() {
                 let version = String::from_utf8_lossy(&output.stdout);
                 println!("  {} Compiler: {}", "✓".green(), 
-                    version.lines().next().unwrap_or(compiler));
+                    version.lines().next().unwrap_or(&compiler));
             }
         }
         _ => {
@@ -43,8 +45,8 @@ pub fn run(verbose: bool) -> Result<()> {
         {
             let path = entry.path();
             if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "c" || ext == "cpp" {
+                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    if config.source_extensions().contains(&ext) {
                         source_files.push(path.to_path_buf());
                     }
                 }
@@ -53,21 +55,24 @@ pub fn run(verbose: bool) -> Result<()> {
     }
 
     println!("  {} Found {} source file(s)", "✓".green(), source_files.len());
+    trace(verbosity, format!("include dirs: {:?}", config.includes.dirs));
+    trace(verbosity, format!("system include dirs: {:?}", config.includes.system));
 
     // Syntax check each file
     let mut errors = 0;
     for source_file in &source_files {
-        if verbose {
-            println!("  Checking {}...", source_file.display());
-        }
-
-        let mut cmd = Command::new(compiler);
+        let mut cmd = Command::new(&compiler);
         cmd.arg("-fsyntax-only")
-            .arg(source_file);
+            .args(config.language_flags())
+            .args(config.dialect_flags())
+            .arg(source_file)
+            .args(&stdlib_flags);
 
         // Add include directories
-        for include_dir in &config.includes.dirs {
-            cmd.arg("-I").arg(include_dir);
+        cmd.args(config.include_flags());
+
+        if verbosity.commands() {
+            println!("  {} {}", "Running".dimmed(), command_line(&cmd));
         }
 
         let output = cmd.output()
@@ -77,7 +82,7 @@ pub fn run(verbose: bool) -> Result<()> {
             errors += 1;
             let stderr = String::from_utf8_lossy(&output.stderr);
             println!("  {} {}", "✗".red(), source_file.display());
-            if verbose {
+            if verbosity.commands() {
                 println!("{}", stderr);
             }
         }
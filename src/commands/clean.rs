@@ -1,52 +1,97 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::fs;
-use std::path::Path;
+use std::path::PathBuf;
 
-pub fn run(all: bool) -> Result<()> {
+use super::cache::{dir_size, format_size};
+use crate::paths;
+
+/// What `zora clean` removes; both build and target unless scoped.
+#[derive(Debug, Clone, Copy)]
+pub struct CleanScope {
+    pub build: bool,
+    pub target: bool,
+    /// Also remove the vcpkg packages in .build/vcpkg_installed
+    pub vcpkg: bool,
+}
+
+pub fn run(scope: CleanScope) -> Result<()> {
     println!("{}", "Cleaning build artifacts...".bright_cyan());
 
-    let mut cleaned = vec![];
-
-    // Clean standard build directories
-    let dirs_to_clean = vec![
-        "target",
-        ".build",
-        "build",
-    ];
-
-    for dir in dirs_to_clean {
-        if Path::new(dir).exists() {
-            fs::remove_dir_all(dir)
-                .with_context(|| format!("failed to remove {}", dir))?;
-            cleaned.push(dir);
-            println!("  {} {}/", "Removed".red(), dir);
-        }
+    let mut scopes: Vec<(&str, Vec<PathBuf>)> = vec![];
+
+    // Intermediate CMake/object dirs, and the compile_commands.json link into
+    // them. The vcpkg packages in the build root are their own scope.
+    if scope.build {
+        let mut entries = if crate::vcpkg::installed_dir().is_dir() {
+            build_root_entries()
+        } else {
+            vec![paths::build_root()]
+        };
+        entries.push(PathBuf::from("build"));
+        entries.push(paths::compile_commands());
+        scopes.push(("Build", entries));
+    }
+
+    if scope.target {
+        scopes.push(("Target", vec![paths::target_root()]));
     }
 
-    // Remove compile_commands.json
-    if Path::new("compile_commands.json").exists() {
-        fs::remove_file("compile_commands.json")
-            .context("failed to remove compile_commands.json")?;
-        cleaned.push("compile_commands.json");
-        println!("  {} compile_commands.json", "Removed".red());
+    if scope.vcpkg {
+        scopes.push(("vcpkg", vec![crate::vcpkg::installed_dir()]));
     }
 
-    // Clean vcpkg if --all flag is set
-    if all {
-        if Path::new("vcpkg_installed").exists() {
-            fs::remove_dir_all("vcpkg_installed")
-                .context("failed to remove vcpkg_installed")?;
-            cleaned.push("vcpkg_installed");
-            println!("  {} vcpkg_installed/", "Removed".red());
+    let mut cleaned = 0;
+    let mut freed = vec![];
+
+    for (name, entries) in scopes {
+        let mut scope_size = 0;
+
+        for path in entries {
+            if path.is_dir() {
+                let size = dir_size(&path)?;
+                fs::remove_dir_all(&path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+                println!("  {} {}/ ({})", "Removed".red(), path.display(), format_size(size));
+                scope_size += size;
+            } else if path.exists() || path.is_symlink() {
+                let size = fs::symlink_metadata(&path).map(|m| m.len()).unwrap_or(0);
+                fs::remove_file(&path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+                println!("  {} {}", "Removed".red(), path.display());
+                scope_size += size;
+            } else {
+                continue;
+            }
+            cleaned += 1;
         }
+
+        freed.push((name, scope_size));
     }
 
-    if cleaned.is_empty() {
+    if cleaned == 0 {
         println!("{}", "Nothing to clean".yellow());
-    } else {
-        println!("\n{} Cleaned {} item(s)", "✓".green().bold(), cleaned.len());
+        return Ok(());
     }
 
+    println!();
+    for (name, size) in &freed {
+        println!("  {}: {} freed", name, format_size(*size));
+    }
+
+    println!("\n{} Cleaned {} item(s)", "✓".green().bold(), cleaned);
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Everything in the build root except the vcpkg installed tree.
+fn build_root_entries() -> Vec<PathBuf> {
+    let installed = crate::vcpkg::installed_dir();
+    let Ok(entries) = fs::read_dir(paths::build_root()) else { return vec![] };
+    let mut found: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| *path != installed)
+        .collect();
+    found.sort();
+    found
+}
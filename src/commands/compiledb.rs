@@ -0,0 +1,61 @@
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use super::build::BuildOptions;
+use crate::config::ProjectConfig;
+use crate::paths;
+
+/// One compile_commands.json entry.
+#[derive(Serialize)]
+struct CompileCommand {
+    directory: String,
+    command: String,
+    file: String,
+    output: String,
+}
+
+/// Prints the compile_commands.json entry for a single file, resolved from
+/// project.toml the same way `zora build` does, without running CMake.
+pub fn run(file: String, opts: BuildOptions) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    if !Path::new(&file).exists() {
+        bail!("File not found: {}", file);
+    }
+
+    let config = ProjectConfig::load()?;
+    let resolved = super::build::resolve(&config, &opts)?;
+    let compiler = config.compiler();
+
+    let output = paths::build_root()
+        .join(opts.slot())
+        .join(format!("{}.o", file));
+    let output = output.to_string_lossy().into_owned();
+
+    let mut args = vec![compiler.clone()];
+    args.extend(config.stdlib_flags(&compiler)?);
+    args.extend(config.language_flags());
+    args.extend(resolved.compile_args(config.is_cpp()));
+    args.extend(["-o".to_string(), output.clone(), "-c".to_string(), file.clone()]);
+
+    let entry = CompileCommand {
+        directory: std::env::current_dir()?.to_string_lossy().into_owned(),
+        command: args.iter().map(|a| shell_quote(a)).collect::<Vec<_>>().join(" "),
+        file,
+        output,
+    };
+
+    println!("{}", serde_json::to_string_pretty(&entry)?);
+    Ok(())
+}
+
+fn shell_quote(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || "'\"\\$`".contains(c)) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
@@ -1,7 +1,21 @@
 use anyhow::{bail, Result};
-use colored::Colorize;
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+use std::io::{self, Write};
 
-pub fn run(_shell: String) -> Result<()> {
-    println!("{}", "Shell completions coming soon!".yellow());
-    bail!("Completions command is not yet implemented");
+use crate::Cli;
+
+/// Writes a completion script for `shell` to stdout, e.g.
+/// `zora completions zsh > _zora`.
+pub fn run(shell: String) -> Result<()> {
+    let Ok(shell) = shell.parse::<Shell>() else {
+        bail!("Unknown shell '{}'. Use bash, zsh, fish, powershell or elvish", shell);
+    };
+
+    // Generated into a buffer since clap_complete panics on write errors,
+    // e.g. when piped into `head`
+    let mut script = vec![];
+    generate(shell, &mut Cli::command(), "zora", &mut script);
+    io::stdout().write_all(&script)?;
+    Ok(())
 }
@@ -25,6 +25,18 @@ pub fn run(tree: bool) -> Result<()> {
         }
     }
 
+    if !config.dev_deps.is_empty() {
+        println!("\n{}", "Dev Dependencies".bright_cyan().bold());
+        println!("{}", "─".repeat(40));
+        for (name, spec) in &config.dev_deps {
+            if tree {
+                println!("├── {} {}", name.bright_yellow(), spec.label());
+            } else {
+                println!("{} = {}", name, spec.label());
+            }
+        }
+    }
+
     println!();
     Ok(())
 }
@@ -0,0 +1,158 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::fs;
+use std::process::Command;
+
+use crate::config::ProjectConfig;
+use crate::paths;
+use crate::vcpkg::{self, RootSource};
+
+pub fn run(fix: bool) -> Result<()> {
+    println!("\n{}", "Checking environment".bright_cyan().bold());
+    println!("{}", "─".repeat(40));
+
+    let project = if ProjectConfig::exists() {
+        Some(ProjectConfig::load()?)
+    } else {
+        None
+    };
+    let compiler = project.as_ref()
+        .map(|config| config.compiler())
+        .unwrap_or_else(|| "c++".to_string());
+
+    let mut problems = 0;
+    // With --fix, what was repaired and what the user still has to do
+    let mut fixed: Vec<String> = vec![];
+    let mut manual: Vec<String> = vec![];
+
+    for (tool, required) in [
+        ("cmake", true),
+        (compiler.as_str(), true),
+        ("git", false),
+        ("clang-format", false),
+        ("clang-tidy", false),
+    ] {
+        if !check_tool(tool, required) && required {
+            problems += 1;
+            manual.push(format!("Install {} and make sure it's on PATH", tool));
+        }
+    }
+
+    let vcpkg_binary = vcpkg::find_binary();
+    let mut bootstrapped = false;
+    match &vcpkg_binary {
+        Some(path) => println!("  {} vcpkg ({})", "✓".green(), path.display()),
+        None => println!("  {} vcpkg not found on PATH (needed for dependencies)", "-".yellow()),
+    }
+
+    if fix && vcpkg_binary.is_none() {
+        match vcpkg::default_root() {
+            Some(root) => {
+                println!("\n{} vcpkg into {}...", "Bootstrapping".bright_cyan(), root.display());
+                match vcpkg::bootstrap(&root) {
+                    Ok(()) => {
+                        fixed.push(format!("Bootstrapped vcpkg in {}", root.display()));
+                        manual.push(format!("Add {} to your PATH", root.display()));
+                        bootstrapped = true;
+                    }
+                    Err(e) => manual.push(format!("Install vcpkg manually ({:#})", e)),
+                }
+            }
+            None => manual.push("Install vcpkg: https://vcpkg.io/en/getting-started.html".to_string()),
+        }
+    }
+
+    match vcpkg::ensure_root() {
+        Some((root, source)) if vcpkg::has_toolchain(&root) => {
+            let note = match source {
+                RootSource::Environment => "",
+                RootSource::Derived => " (derived from vcpkg location)",
+            };
+            println!("  {} VCPKG_ROOT = {}{}", "✓".green(), root.display(), note);
+
+            if fix && (source == RootSource::Derived || bootstrapped) {
+                manual.push(format!("Add export VCPKG_ROOT=\"{}\" to your shell profile", root.display()));
+            }
+        }
+        Some((root, _)) => {
+            println!("  {} VCPKG_ROOT = {} has no vcpkg CMake toolchain", "✗".red(), root.display());
+            problems += 1;
+            manual.push(format!("Point VCPKG_ROOT at a vcpkg checkout (currently {})", root.display()));
+        }
+        None => println!("  {} VCPKG_ROOT is not set and could not be derived", "-".yellow()),
+    }
+
+    if let Some(config) = &project {
+        for var in &config.build.required_env {
+            if config.missing_env().contains(&var.as_str()) {
+                println!("  {} {} is not set (required by [build] required_env)", "✗".red(), var);
+                problems += 1;
+                manual.push(format!("Set the {} environment variable", var));
+            } else {
+                println!("  {} {}", "✓".green(), var);
+            }
+        }
+    }
+
+    if fix && project.is_some() {
+        let build_dir = paths::build_root().join("dev");
+        if !build_dir.exists() {
+            fs::create_dir_all(&build_dir)?;
+            fixed.push(format!("Created {}", build_dir.display()));
+        }
+
+        if super::build::link_compile_commands(&build_dir)? {
+            fixed.push(format!("Regenerated {}", paths::compile_commands().display()));
+        } else {
+            manual.push("Run 'zora build' to generate compile_commands.json".to_string());
+        }
+    }
+
+    println!();
+
+    if fix {
+        if !fixed.is_empty() {
+            println!("{}", "Fixed".green().bold());
+            for item in &fixed {
+                println!("  {} {}", "✓".green(), item);
+            }
+            println!();
+        }
+
+        if !manual.is_empty() {
+            println!("{}", "Needs manual action".yellow().bold());
+            for item in &manual {
+                println!("  {} {}", "→".bright_blue(), item);
+            }
+            println!();
+        }
+    }
+
+    if problems > 0 {
+        if !fix {
+            println!("Run {} to attempt automatic fixes", "zora doctor --fix".bright_white());
+        }
+        bail!("{} problem(s) found", problems);
+    }
+
+    println!("{} Environment looks good", "✓".green().bold());
+    Ok(())
+}
+
+fn check_tool(tool: &str, required: bool) -> bool {
+    let found = Command::new(tool)
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    if found {
+        println!("  {} {}", "✓".green(), tool);
+    } else if required {
+        println!("  {} {} not found", "✗".red(), tool);
+    } else {
+        println!("  {} {} not found (optional)", "-".yellow(), tool);
+    }
+
+    found
+}
@@ -7,7 +7,7 @@ pub fn run(file: Option<String>) -> Result<()> {
         println!("{} Expanding macros in: {}", "→".bright_blue(), f);
         
         Command::new("gcc")
-            .args(&["-E", &f])
+            .args(["-E", &f])
             .status()?;
     } else {
         bail!("No file specified");
@@ -0,0 +1,268 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+use tera::{Context as TeraContext, Tera};
+
+use super::build::{self, BuildOptions, CmakeProject};
+use crate::config::ProjectConfig;
+
+/// Where the exported CMake projects go, one dir per mode. Two levels deep,
+/// like .build/<slot>, so the same relative project root works.
+const EXPORT_DIR: &str = "cmake";
+
+const MAKEFILE_HEADER: &str = "# Generated by `zora export makefile`";
+
+const PRESETS_FILE: &str = "CMakePresets.json";
+
+const PRESETS_CMAKE_HEADER: &str = "# Generated by `zora export presets`";
+
+/// Presets can only configure the CMakeLists.txt next to them, so the root
+/// one dispatches to the exported project for the preset's profile.
+const PRESETS_CMAKE_TEMPLATE: &str = r#"# Generated by `zora export presets` for building without zora.
+# Builds the cmake/<profile> project picked by ZORA_PROFILE, which each preset
+# in CMakePresets.json sets. Regenerate both after changing project.toml.
+cmake_minimum_required(VERSION 3.21)
+project({{ name }}_presets NONE)
+
+set(ZORA_PROFILE "dev" CACHE STRING "Profile exported to cmake/<profile>")
+add_subdirectory(${PROJECT_SOURCE_DIR}/{{ export_dir }}/${ZORA_PROFILE} ${PROJECT_BINARY_DIR}/${ZORA_PROFILE})
+"#;
+
+/// Binary dir of the exported presets, kept apart from .build/<slot> since
+/// those caches belong to zora's own generated projects.
+const PRESETS_BUILD_DIR: &str = ".build/presets";
+
+const MAKEFILE_TEMPLATE: &str = r#"# Generated by `zora export makefile` for building without zora.
+# Regenerate it after changing project.toml.
+
+BUILD_DIR ?= .build
+JOBS ?= {{ jobs }}
+COMPILER ?= {{ compiler }}
+ARGS ?=
+
+.PHONY: all build release run test clean
+
+all: build
+
+build:
+	cmake -S {{ export_dir }}/dev -B $(BUILD_DIR)/dev{% for arg in dev_args %} {{ arg }}{% endfor %}
+	cmake --build $(BUILD_DIR)/dev -j $(JOBS)
+
+release:
+	cmake -S {{ export_dir }}/release -B $(BUILD_DIR)/release{% for arg in release_args %} {{ arg }}{% endfor %}
+	cmake --build $(BUILD_DIR)/release -j $(JOBS)
+
+run: build
+{% if executable %}	./$(BUILD_DIR)/dev/{{ executable }} $(ARGS)
+{% else %}	@echo "{{ name }} is a library; there is nothing to run"
+{% endif %}
+test:
+	@mkdir -p $(BUILD_DIR)/tests
+{% if harness %}	$(COMPILER){% for flag in test_flags %} {{ flag }}{% endfor %} $(wildcard{% for pattern in test_patterns %} {{ pattern }}{% endfor %}){% for source in library_sources %} {{ source }}{% endfor %} -o $(BUILD_DIR)/tests/harness{% for lib in libs %} -l{{ lib }}{% endfor %}
+	./$(BUILD_DIR)/tests/harness
+{% else %}	@set -e; for test in $(wildcard{% for pattern in test_patterns %} {{ pattern }}{% endfor %}); do \
+		out=$(BUILD_DIR)/tests/$$(basename $${test%.*}); \
+		echo "Testing $$test"; \
+		$(COMPILER){% for flag in test_flags %} {{ flag }}{% endfor %} $$test -o $$out && $$out; \
+	done
+{% endif %}
+clean:
+	rm -rf $(BUILD_DIR)
+"#;
+
+pub fn run(format: &str) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    match format {
+        "makefile" | "make" => makefile(),
+        "presets" | "cmake-presets" => presets(),
+        _ => bail!("Unknown export format '{}'. Use: makefile, presets", format),
+    }
+}
+
+/// Writes the dev and release CMake projects zora would generate into
+/// cmake/, and a Makefile driving them with the same configure arguments.
+fn makefile() -> Result<()> {
+    let config = ProjectConfig::load()?;
+
+    // Never clobber a hand-written Makefile
+    if let Ok(existing) = fs::read_to_string("Makefile") {
+        if !existing.starts_with(MAKEFILE_HEADER) {
+            bail!("A Makefile not generated by zora already exists");
+        }
+    }
+
+    println!("{}", "Exporting Makefile...".bright_cyan());
+
+    let mut ctx = TeraContext::new();
+
+    for mode in ["dev", "release"] {
+        let project = export_project(&config, mode)?;
+        // e.g. `-G "Unix Makefiles"`
+        let args: Vec<String> = project.configure_args.iter()
+            .map(|arg| if arg.contains(' ') { format!("\"{}\"", arg) } else { arg.clone() })
+            .collect();
+        ctx.insert(format!("{}_args", mode), &args);
+    }
+
+    // Tests are compiled directly, mirroring `zora test`
+    let compiler = config.compiler();
+    let mut test_flags = config.language_flags();
+    test_flags.extend(config.dialect_flags());
+    test_flags.extend(config.include_flags());
+    test_flags.extend(config.stdlib_flags(&compiler)?);
+    test_flags.extend(config.framework_flags());
+
+    let test_patterns: Vec<String> = config.tests.dirs.iter()
+        .flat_map(|dir| config.source_extensions().iter().map(move |ext| format!("{}/*.{}", dir, ext)))
+        .collect();
+
+    let harness = config.tests.mode == "harness";
+    let library_sources = if harness {
+        config.library_sources(&config.enabled_features(&[])?)?
+    } else {
+        vec![]
+    };
+
+    ctx.insert("name", &config.name);
+    ctx.insert("export_dir", EXPORT_DIR);
+    ctx.insert("jobs", &build::build_jobs(&config, &BuildOptions::new("dev")));
+    ctx.insert("compiler", &compiler);
+    ctx.insert("executable", &if config.is_library() { None } else { Some(&config.name) });
+    ctx.insert("harness", &harness);
+    ctx.insert("test_flags", &test_flags);
+    ctx.insert("test_patterns", &test_patterns);
+    ctx.insert("library_sources", &library_sources);
+    ctx.insert("libs", &config.build.libs);
+
+    let makefile = Tera::one_off(MAKEFILE_TEMPLATE, &ctx, false)
+        .context("failed to render Makefile template")?;
+    fs::write("Makefile", makefile).context("failed to write Makefile")?;
+    println!("  {} Makefile", "Created".green());
+
+    println!("\n{} Exported. Commit {}/ and Makefile so the project builds with plain make",
+        "✓".green().bold(),
+        EXPORT_DIR
+    );
+    Ok(())
+}
+
+/// Writes cmake/<profile> for every profile and a CMakePresets.json with a
+/// configure and build preset for each, so CMake-native tooling and CI get
+/// the same settings without zora.
+fn presets() -> Result<()> {
+    let config = ProjectConfig::load()?;
+
+    if let Ok(existing) = fs::read_to_string("CMakeLists.txt") {
+        if !existing.starts_with(PRESETS_CMAKE_HEADER) {
+            bail!("A CMakeLists.txt not generated by zora already exists");
+        }
+    }
+
+    // Never clobber hand-written presets
+    if let Ok(existing) = fs::read_to_string(PRESETS_FILE) {
+        let generated = serde_json::from_str::<Value>(&existing)
+            .ok()
+            .is_some_and(|presets| presets.pointer("/vendor/zora/generated") == Some(&Value::Bool(true)));
+        if !generated {
+            bail!("A {} not generated by zora already exists", PRESETS_FILE);
+        }
+    }
+
+    println!("{}", "Exporting CMake presets...".bright_cyan());
+
+    let mut profiles = vec!["dev".to_string(), "release".to_string()];
+    let mut custom: Vec<&String> = config.profile.custom.keys().collect();
+    custom.sort();
+    profiles.extend(custom.into_iter().cloned());
+
+    let mut configure_presets = vec![];
+    let mut build_presets = vec![];
+
+    for profile in &profiles {
+        let project = export_project(&config, profile)?;
+
+        let mut cache = cache_variables(&project.configure_args);
+        cache.insert("ZORA_PROFILE".to_string(), json!(profile));
+
+        let mut preset = json!({
+            "name": profile,
+            "displayName": format!("{} ({})", config.name, profile),
+            "binaryDir": format!("${{sourceDir}}/{}/{}", PRESETS_BUILD_DIR, profile),
+            "cacheVariables": cache,
+        });
+        if let Some(generator) = build::select_generator(&config, &BuildOptions::new(profile))? {
+            preset["generator"] = json!(generator);
+        }
+        // Like the generated CMakeLists.txt, find vcpkg through the environment
+        if config.uses_vcpkg() {
+            preset["toolchainFile"] = json!("$env{VCPKG_ROOT}/scripts/buildsystems/vcpkg.cmake");
+        }
+        configure_presets.push(preset);
+
+        build_presets.push(json!({
+            "name": profile,
+            "configurePreset": profile,
+            "jobs": build::build_jobs(&config, &BuildOptions::new(profile)),
+        }));
+    }
+
+    // Without [build] generator no generator is pinned, leaving it to CMake as zora does
+    let presets = json!({
+        "version": 3,
+        "cmakeMinimumRequired": { "major": 3, "minor": 21, "patch": 0 },
+        "configurePresets": configure_presets,
+        "buildPresets": build_presets,
+        "vendor": { "zora": { "generated": true } },
+    });
+
+    let mut ctx = TeraContext::new();
+    ctx.insert("name", &config.name);
+    ctx.insert("export_dir", EXPORT_DIR);
+    let cmake_lists = Tera::one_off(PRESETS_CMAKE_TEMPLATE, &ctx, false)
+        .context("failed to render CMakeLists.txt template")?;
+    fs::write("CMakeLists.txt", cmake_lists).context("failed to write CMakeLists.txt")?;
+    println!("  {} CMakeLists.txt", "Created".green());
+
+    fs::write(PRESETS_FILE, serde_json::to_string_pretty(&presets)? + "\n")
+        .with_context(|| format!("failed to write {}", PRESETS_FILE))?;
+    println!("  {} {}", "Created".green(), PRESETS_FILE);
+
+    println!("\n{} Exported. Commit {}/, CMakeLists.txt and {}, then e.g. `cmake --preset {}`",
+        "✓".green().bold(),
+        EXPORT_DIR,
+        PRESETS_FILE,
+        profiles[0]
+    );
+    Ok(())
+}
+
+/// Writes the CMake project zora would generate for `mode` to cmake/<mode>.
+fn export_project(config: &ProjectConfig, mode: &str) -> Result<CmakeProject> {
+    let opts = BuildOptions::new(mode);
+    let resolved = build::resolve(config, &opts)?;
+    let project = build::generate(config, &opts, &resolved, "${CMAKE_CURRENT_SOURCE_DIR}/../..")?;
+
+    let dir = Path::new(EXPORT_DIR).join(mode);
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let cmake_path = project.write(&dir)?;
+    println!("  {} {}", "Created".green(), cmake_path.display());
+    Ok(project)
+}
+
+/// `-DNAME[:TYPE]=VALUE` configure arguments as preset cache variables.
+fn cache_variables(configure_args: &[String]) -> Map<String, Value> {
+    configure_args.iter()
+        .filter_map(|arg| arg.strip_prefix("-D")?.split_once('='))
+        .map(|(name, value)| match name.split_once(':') {
+            Some((name, kind)) => (name.to_string(), json!({ "type": kind, "value": value })),
+            None => (name.to_string(), json!(value)),
+        })
+        .collect()
+}
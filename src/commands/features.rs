@@ -1,39 +1,121 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::fs;
 use crate::config::ProjectConfig;
 
 pub fn list() -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found");
     }
-    
+
     let config = ProjectConfig::load()?;
-    
+
     println!("\n{}", "Available features:".bright_cyan());
-    for (name, deps) in &config.features {
-        println!("  {} - {}", name.bright_yellow(), deps.join(", "));
+    let mut names: Vec<&String> = config.features.keys().collect();
+    names.sort();
+    for name in names {
+        println!("  {} - {}", name.bright_yellow(), config.features[name].join(", "));
     }
-    
+
     if !config.default_features.is_empty() {
         println!("\n{}", "Default features:".bright_cyan());
         for feature in &config.default_features {
             println!("  {}", feature);
         }
     }
-    
+
     Ok(())
 }
 
+/// Adds features to `default_features` in project.toml.
 pub fn enable(features: Vec<String>) -> Result<()> {
+    let config = load_checked(&features)?;
+
+    let mut defaults = config.default_features.clone();
     for feature in features {
+        if defaults.contains(&feature) {
+            println!("Note: {} is already enabled", feature);
+            continue;
+        }
         println!("{} Enabled feature: {}", "✓".green(), feature);
+        defaults.push(feature);
     }
-    Ok(())
+
+    write_default_features(&defaults)
 }
 
+/// Removes features from `default_features` in project.toml.
 pub fn disable(features: Vec<String>) -> Result<()> {
+    let config = load_checked(&features)?;
+
+    let mut defaults = config.default_features.clone();
     for feature in features {
+        if !defaults.contains(&feature) {
+            println!("Note: {} is not enabled by default", feature);
+            continue;
+        }
         println!("{} Disabled feature: {}", "✓".green(), feature);
+        defaults.retain(|f| *f != feature);
     }
-    Ok(())
+
+    write_default_features(&defaults)
+}
+
+fn load_checked(features: &[String]) -> Result<ProjectConfig> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found");
+    }
+    if features.is_empty() {
+        bail!("No features specified");
+    }
+
+    let config = ProjectConfig::load()?;
+    for feature in features {
+        if !config.features.contains_key(feature) {
+            let mut available: Vec<&str> = config.features.keys().map(String::as_str).collect();
+            available.sort();
+            bail!("Unknown feature '{}'. Available: {}", feature, available.join(", "));
+        }
+    }
+    Ok(config)
+}
+
+/// Rewrites the top-level `default_features` key, which has to come before
+/// the first table, keeping the rest of project.toml as it is.
+fn write_default_features(features: &[String]) -> Result<()> {
+    let content = fs::read_to_string("project.toml")
+        .context("failed to read project.toml")?;
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+
+    let quoted: Vec<String> = features.iter()
+        .map(|feature| toml::Value::String(feature.clone()).to_string())
+        .collect();
+    let line = format!("default_features = [{}]", quoted.join(", "));
+
+    let first_table = lines.iter()
+        .position(|l| l.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let existing = lines[..first_table].iter()
+        .position(|l| l.trim_start().starts_with("default_features"));
+
+    match existing {
+        Some(start) => {
+            // The array may span several lines
+            let mut end = start;
+            while end + 1 < first_table && !lines[end].contains(']') {
+                end += 1;
+            }
+            lines.splice(start..=end, [line]);
+        }
+        None => {
+            let mut at = first_table;
+            while at > 0 && lines[at - 1].trim().is_empty() {
+                at -= 1;
+            }
+            lines.insert(at, line);
+        }
+    }
+
+    fs::write("project.toml", lines.join("\n") + "\n")
+        .context("failed to write project.toml")
 }
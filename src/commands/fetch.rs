@@ -0,0 +1,107 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::config::{DependencySpec, ProjectConfig};
+
+/// Installs every dependency (`[deps]` and `[dev-dependencies]`) without building the
+/// project, so CI can warm the dependency cache in its own step.
+pub fn run() -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+
+    let mut fetched = 0;
+    for deps in [&config.deps, &config.dev_deps] {
+        fetched += fetch_path_deps(deps)?;
+    }
+
+    let mut packages = vcpkg_specs(&config.deps);
+    packages.extend(vcpkg_specs(&config.dev_deps));
+    packages.sort();
+    packages.dedup();
+
+    // With `[deps] use_vcpkg = false` the packages come from elsewhere
+    if config.use_vcpkg && !packages.is_empty() {
+        if crate::vcpkg::ensure_root().is_none() {
+            bail!("VCPKG_ROOT is not set and vcpkg was not found on PATH. Run 'zora doctor' for details.");
+        }
+
+        println!("{}", "Installing packages...".bright_cyan());
+
+        // Manifest mode, into .build/vcpkg_installed where the build looks,
+        // dev dependencies included
+        crate::vcpkg::ensure_manifest(&config)?;
+        let status = crate::vcpkg::install_command(!config.dev_vcpkg_packages().is_empty())
+            .status()
+            .context("failed to run vcpkg. Is vcpkg installed?")?;
+
+        if !status.success() {
+            bail!("vcpkg install failed");
+        }
+
+        for package in &packages {
+            println!("  {} {}", "✓".green(), package);
+        }
+        fetched += packages.len();
+    }
+
+    if fetched == 0 {
+        println!("{}", "All dependencies are already fetched".yellow());
+    } else {
+        println!("\n{} Fetched {} dependenc{}", "✓".green().bold(), fetched,
+            if fetched == 1 { "y" } else { "ies" });
+    }
+
+    Ok(())
+}
+
+/// vcpkg install specs (`name[feature,...]`) for everything but path deps.
+fn vcpkg_specs(deps: &HashMap<String, DependencySpec>) -> Vec<String> {
+    deps.iter()
+        .filter(|(_, spec)| spec.path().is_none())
+        .map(|(name, spec)| match spec {
+            DependencySpec::Detailed { features, .. } if !features.is_empty() => {
+                format!("{}[{}]", name, features.join(","))
+            }
+            _ => name.clone(),
+        })
+        .collect()
+}
+
+/// Checks out path deps: submodules are initialized, path deps with a `git`
+/// URL are cloned when missing. Returns how many were fetched.
+fn fetch_path_deps(deps: &HashMap<String, DependencySpec>) -> Result<usize> {
+    let mut names: Vec<&String> = deps.keys().collect();
+    names.sort();
+
+    let uninitialized = crate::git::uninitialized_submodules();
+    let mut fetched = 0;
+
+    for name in names {
+        let DependencySpec::Detailed { git, branch, tag, path: Some(path), .. } = &deps[name] else {
+            continue;
+        };
+
+        if uninitialized.contains(path) {
+            println!("{} {} (submodule)...", "Fetching".bright_cyan(), name);
+            crate::git::submodule_update(std::slice::from_ref(path), true, false)?;
+        } else if !Path::new(path).exists() {
+            let Some(url) = git else {
+                bail!("Path dependency '{}' not found at {}", name, path);
+            };
+            println!("{} {} from {}...", "Fetching".bright_cyan(), name, url);
+            crate::git::clone(url, tag.as_deref().or(branch.as_deref()), path)?;
+        } else {
+            continue;
+        }
+
+        println!("  {} {}", "✓".green(), name);
+        fetched += 1;
+    }
+
+    Ok(fetched)
+}
@@ -1,15 +1,21 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 use walkdir::WalkDir;
 
 use crate::config::ProjectConfig;
 
-pub fn run(check: bool) -> Result<()> {
+pub fn run(check: bool, write_config: bool, force: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
+    if write_config {
+        return write_clang_format(&ProjectConfig::load()?, force);
+    }
+
     // Check if clang-format is installed
     let clang_format_check = Command::new("clang-format")
         .arg("--version")
@@ -39,7 +45,7 @@ pub fn run(check: bool) -> Result<()> {
             if path.is_file() {
                 if let Some(ext) = path.extension() {
                     let ext_str = ext.to_str().unwrap_or("");
-                    if ["c", "cpp", "h", "hpp", "cc", "cxx"].contains(&ext_str) {
+                    if ["c", "cpp", "h", "hpp", "cc", "cxx", "m", "mm"].contains(&ext_str) {
                         files.push(path.to_path_buf());
                     }
                 }
@@ -64,6 +70,10 @@ pub fn run(check: bool) -> Result<()> {
         }
     }
 
+    // Colocated headers can be reached through both dir lists
+    files.sort();
+    files.dedup();
+
     if files.is_empty() {
         println!("{}", "No files to format".yellow());
         return Ok(());
@@ -116,4 +126,30 @@ pub fn run(check: bool) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Writes a .clang-format generated from the [fmt] section.
+fn write_clang_format(config: &ProjectConfig, force: bool) -> Result<()> {
+    let path = Path::new(".clang-format");
+    if path.exists() && !force {
+        bail!(".clang-format already exists. Use --force to overwrite it.");
+    }
+
+    let fmt = &config.fmt;
+    let mut content = String::from("# Generated by zora from [fmt] in project.toml\n");
+    content.push_str(&format!("BasedOnStyle: {}\n", fmt.style.as_deref().unwrap_or("LLVM")));
+
+    if let Some(width) = fmt.indent_width {
+        content.push_str(&format!("IndentWidth: {}\n", width));
+    }
+    if let Some(limit) = fmt.column_limit {
+        content.push_str(&format!("ColumnLimit: {}\n", limit));
+    }
+    for (key, value) in &fmt.options {
+        content.push_str(&format!("{}: {}\n", key, value));
+    }
+
+    fs::write(path, content).context("failed to write .clang-format")?;
+    println!("{} Wrote .clang-format", "✓".green().bold());
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::ProjectConfig;
+use crate::paths;
+
+const FUZZ_DIR: &str = "fuzz";
+
+const PROBE_TEMPLATE: &str = r#"#include <stddef.h>
+#include <stdint.h>
+
+int LLVMFuzzerTestOneInput(const uint8_t *data, size_t size) {
+    (void)data;
+    (void)size;
+    return 0;
+}
+"#;
+
+pub fn run(target: String, args: Vec<String>) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let compiler = if config.is_cpp() { "clang++" } else { "clang" };
+
+    // libFuzzer ships with clang only
+    let clang_check = Command::new(compiler)
+        .arg("--version")
+        .output();
+
+    match clang_check {
+        Ok(output) if output.status.success() => {}
+        _ => bail!("{} not found. Fuzzing requires clang with libFuzzer support.", compiler),
+    }
+
+    let fuzz_source = find_fuzz_source(&target)?;
+
+    let output_dir = paths::target_root().join("fuzz");
+    fs::create_dir_all(&output_dir).context("failed to create target/fuzz")?;
+
+    check_sanitizer_support(compiler, &output_dir)?;
+
+    println!("{} {}...", "Compiling fuzz target".bright_cyan(), target.bright_yellow());
+
+    let output_file = output_dir.join(&target);
+
+    let mut cmd = Command::new(compiler);
+    cmd.arg("-fsanitize=fuzzer,address")
+        .arg("-g")
+        .arg("-O1")
+        .args(config.language_flags())
+        .args(config.dialect_flags())
+        .arg(&fuzz_source);
+
+    // Link the project sources so the target can call into them. `main`
+    // is skipped since libFuzzer provides its own entry point.
+    for source in config.library_sources(&config.enabled_features(&[])?)? {
+        cmd.arg(source);
+    }
+
+    cmd.args(config.include_flags());
+
+    for flag in &config.build.flags {
+        cmd.arg(flag);
+    }
+
+    for (key, value) in &config.build.defines {
+        cmd.arg(format!("-D{}={}", key, value));
+    }
+
+    cmd.arg("-o").arg(&output_file);
+
+    let status = cmd.status().context("failed to compile fuzz target")?;
+
+    if !status.success() {
+        bail!("Failed to compile fuzz target '{}'", target);
+    }
+
+    // With an out dir the checked-in corpus is only read as a seed;
+    // libFuzzer writes new inputs to the first corpus dir it's given
+    let fuzz_root = match paths::out_dir() {
+        Some(dir) => dir.join(FUZZ_DIR),
+        None => PathBuf::from(FUZZ_DIR),
+    };
+    let seed_dir = Path::new(FUZZ_DIR).join("corpus").join(&target);
+    let corpus_dir = fuzz_root.join("corpus").join(&target);
+    let artifacts_dir = fuzz_root.join("artifacts").join(&target);
+    fs::create_dir_all(&corpus_dir).context("failed to create fuzz corpus directory")?;
+    fs::create_dir_all(&artifacts_dir).context("failed to create fuzz artifacts directory")?;
+
+    println!("\n{} {}...", "Fuzzing".bright_blue(), target);
+    println!("  Corpus:    {}", corpus_dir.display());
+    if seed_dir != corpus_dir && seed_dir.is_dir() {
+        println!("  Seeds:     {}", seed_dir.display());
+    }
+    println!("  Artifacts: {}\n", artifacts_dir.display());
+
+    // libFuzzer expects the artifact prefix to end with a separator
+    let mut cmd = Command::new(&output_file);
+    cmd.arg(format!("-artifact_prefix={}/", artifacts_dir.display()))
+        .arg(&corpus_dir);
+    if seed_dir != corpus_dir && seed_dir.is_dir() {
+        cmd.arg(&seed_dir);
+    }
+
+    let status = cmd
+        .args(&args)
+        .status()
+        .context("failed to run fuzz target")?;
+
+    if !status.success() {
+        let code = status.code().unwrap_or(-1);
+        bail!(
+            "Fuzz target exited with code {}. Crash inputs are saved in {}",
+            code,
+            artifacts_dir.display()
+        );
+    }
+
+    println!("\n{} Fuzzing finished", "✓".green().bold());
+    Ok(())
+}
+
+fn find_fuzz_source(target: &str) -> Result<PathBuf> {
+    for ext in ["c", "cpp", "cc", "cxx"] {
+        let path = Path::new(FUZZ_DIR).join(format!("{}.{}", target, ext));
+        if path.exists() {
+            return Ok(path);
+        }
+    }
+
+    bail!("Fuzz target not found: {}/{}.c", FUZZ_DIR, target);
+}
+
+fn check_sanitizer_support(compiler: &str, output_dir: &Path) -> Result<()> {
+    let probe_src = output_dir.join(if compiler == "clang++" { "probe.cpp" } else { "probe.c" });
+    let probe_out = output_dir.join("probe");
+    fs::write(&probe_src, PROBE_TEMPLATE)?;
+
+    let output = Command::new(compiler)
+        .arg("-fsanitize=fuzzer,address")
+        .arg(&probe_src)
+        .arg("-o")
+        .arg(&probe_out)
+        .output()
+        .context("failed to probe sanitizer support")?;
+
+    fs::remove_file(&probe_src).ok();
+    fs::remove_file(&probe_out).ok();
+
+    if !output.status.success() {
+        bail!("{} does not support -fsanitize=fuzzer,address. Install the compiler-rt/libFuzzer runtime.", compiler);
+    }
+
+    Ok(())
+}
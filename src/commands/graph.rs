@@ -0,0 +1,200 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use super::build::{self, BuildOptions};
+use crate::config::ProjectConfig;
+use crate::output::{run_tool, Verbosity};
+use crate::paths;
+
+/// A target (or external library) node from CMake's graphviz output.
+struct Node {
+    id: String,
+    label: String,
+    shape: Option<String>,
+}
+
+/// A link dependency between two nodes.
+struct Edge {
+    from: String,
+    to: String,
+    style: Option<String>,
+}
+
+/// Configures the project with `cmake --graphviz` and writes a cleaned-up
+/// target graph to target/graph/, rendered to SVG when graphviz is installed.
+pub fn build(release: bool) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let opts = BuildOptions::new(if release { "release" } else { "dev" });
+    let resolved = build::resolve(&config, &opts)?;
+
+    if config.uses_vcpkg() && crate::vcpkg::ensure_root().is_none() {
+        bail!("VCPKG_ROOT is not set and vcpkg was not found on PATH. Run 'zora doctor' for details.");
+    }
+
+    println!("{}", "Generating target graph...".bright_cyan());
+
+    let build_dir = paths::build_root().join(opts.slot());
+    fs::create_dir_all(&build_dir).context("failed to create build directory")?;
+
+    let project = build::generate(&config, &opts, &resolved, &paths::cmake_project_root()?)?;
+    project.write(&build_dir)?;
+
+    // CMake writes a file per target next to the main graph, so keep its
+    // raw output inside the build dir
+    let raw_dir = build_dir.join("graphviz");
+    if raw_dir.exists() {
+        fs::remove_dir_all(&raw_dir).context("failed to clear old graphviz output")?;
+    }
+    fs::create_dir_all(&raw_dir)?;
+    let raw_path = raw_dir.join("targets.dot");
+
+    let mut cmake = Command::new("cmake");
+    cmake.arg("-S").arg(&build_dir)
+        .arg("-B").arg(&build_dir)
+        .args(&project.configure_args)
+        .arg(format!("--graphviz={}", raw_path.display()));
+    if let Some(epoch) = project.source_date_epoch {
+        cmake.env("SOURCE_DATE_EPOCH", epoch.to_string());
+    }
+
+    if !run_tool(&mut cmake, Verbosity::default())? {
+        bail!("CMake configuration failed");
+    }
+
+    let raw = fs::read_to_string(&raw_path)
+        .with_context(|| format!("cmake did not write {}", raw_path.display()))?;
+    let (nodes, edges) = parse_graphviz(&raw);
+
+    let graph_dir = paths::target_root().join("graph");
+    fs::create_dir_all(&graph_dir).context("failed to create target/graph")?;
+
+    let dot_path = graph_dir.join(format!("{}.dot", config.name));
+    fs::write(&dot_path, render_dot(&config.name, &nodes, &edges))
+        .with_context(|| format!("failed to write {}", dot_path.display()))?;
+    println!("  {} {}", "Created".green(), dot_path.display());
+
+    let svg_path = dot_path.with_extension("svg");
+    if render_svg(&dot_path, &svg_path)? {
+        println!("  {} {}", "Created".green(), svg_path.display());
+    } else {
+        println!("  {} graphviz `dot` not found; install it to render {}",
+            "-".yellow(),
+            svg_path.display()
+        );
+    }
+
+    println!("\n{} {} target(s), {} link dependencies",
+        "✓".green().bold(),
+        nodes.len(),
+        edges.len()
+    );
+    Ok(())
+}
+
+/// Pulls the target nodes and link edges out of CMake's graphviz output,
+/// dropping the legend and the pseudo-nodes CMake makes for raw link flags
+/// such as `-fprofile-generate` or `-stdlib=libc++`.
+fn parse_graphviz(raw: &str) -> (Vec<Node>, Vec<Edge>) {
+    let mut nodes = vec![];
+    let mut edges = vec![];
+
+    for line in raw.lines().map(str::trim) {
+        let mut quoted = line.split('"').skip(1).step_by(2);
+        let Some(id) = quoted.next() else { continue };
+
+        // Legend entries are "legendNode<N>"
+        if !id.starts_with("node") {
+            continue;
+        }
+
+        if line.contains("->") {
+            let Some(to) = quoted.next() else { continue };
+            edges.push(Edge {
+                from: id.to_string(),
+                to: to.to_string(),
+                style: attribute(line, "style"),
+            });
+        } else if let Some(label) = attribute(line, "label") {
+            nodes.push(Node {
+                id: id.to_string(),
+                label,
+                shape: attribute(line, "shape"),
+            });
+        }
+    }
+
+    nodes.retain(|node| !node.label.starts_with('-') && !node.label.starts_with("$<"));
+
+    let kept: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+    edges.retain(|edge| kept.contains(edge.from.as_str()) && kept.contains(edge.to.as_str()));
+
+    (nodes, edges)
+}
+
+/// Value of `key = value` or `key = "value"` inside a dot attribute list.
+fn attribute(line: &str, key: &str) -> Option<String> {
+    let attrs = &line[line.find('[')? + 1..];
+    let start = attrs.find(&format!("{} =", key))
+        .or_else(|| attrs.find(&format!("{}=", key)))?;
+    let rest = attrs[start..].split_once('=')?.1.trim_start();
+
+    let value = match rest.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next()?,
+        None => rest.split([',', ' ', ']']).next()?,
+    };
+    Some(value.to_string())
+}
+
+fn render_dot(name: &str, nodes: &[Node], edges: &[Edge]) -> String {
+    let mut dot = format!("digraph \"{}\" {{\n", name);
+    dot.push_str("    rankdir = \"LR\";\n");
+    dot.push_str("    node [ fontsize = \"12\" ];\n\n");
+
+    for node in nodes {
+        // CMake labels targets as "name\n(alias)"; the first line is enough
+        let label = node.label.split("\\n").next().unwrap_or(&node.label);
+        dot.push_str(&format!("    \"{}\" [ label = \"{}\"", node.id, label));
+        if let Some(shape) = &node.shape {
+            dot.push_str(&format!(", shape = {}", shape));
+        }
+        dot.push_str(" ];\n");
+    }
+
+    if !edges.is_empty() {
+        dot.push('\n');
+    }
+    for edge in edges {
+        dot.push_str(&format!("    \"{}\" -> \"{}\"", edge.from, edge.to));
+        if let Some(style) = &edge.style {
+            dot.push_str(&format!(" [ style = {} ]", style));
+        }
+        dot.push_str(";\n");
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Renders `dot_path` to SVG with graphviz. False when `dot` isn't installed.
+fn render_svg(dot_path: &Path, svg_path: &Path) -> Result<bool> {
+    let result = Command::new("dot")
+        .arg("-Tsvg")
+        .arg(dot_path)
+        .arg("-o")
+        .arg(svg_path)
+        .output();
+
+    match result {
+        Ok(output) if output.status.success() => Ok(true),
+        Ok(output) => bail!("dot failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+        Err(_) => Ok(false),
+    }
+}
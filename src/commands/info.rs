@@ -1,7 +1,12 @@
 // src/commands/info.rs
 use anyhow::{bail, Result};
 use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
 
+use super::build::BuildOptions;
+use super::cache::format_size;
 use crate::config::ProjectConfig;
 
 pub fn run() -> Result<()> {
@@ -27,6 +32,13 @@ pub fn run() -> Result<()> {
         }
     }
 
+    if !config.dev_deps.is_empty() {
+        println!("\n{}", "Dev Dependencies".bright_cyan());
+        for (name, spec) in &config.dev_deps {
+            println!("  • {} = {}", name, spec.label());
+        }
+    }
+
     if !config.sources.dirs.is_empty() {
         println!("\n{}", "Source Directories".bright_cyan());
         for dir in &config.sources.dirs {
@@ -41,6 +53,13 @@ pub fn run() -> Result<()> {
         }
     }
 
+    if !config.includes.system.is_empty() {
+        println!("\n{}", "System Include Directories".bright_cyan());
+        for dir in &config.includes.system {
+            println!("  • {}", dir);
+        }
+    }
+
     if !config.build.flags.is_empty() {
         println!("\n{}", "Build Flags".bright_cyan());
         for flag in &config.build.flags {
@@ -48,6 +67,160 @@ pub fn run() -> Result<()> {
         }
     }
 
+    if config.is_library() && !config.build.export_defines.is_empty() {
+        println!("\n{}", "Exported Defines".bright_cyan());
+        let mut defines: Vec<_> = config.build.export_defines.iter().collect();
+        defines.sort();
+        for (key, value) in defines {
+            println!("  • {}={}", key, value);
+        }
+    }
+
+    println!();
+    Ok(())
+}
+
+/// Prints the build settings exactly as `zora build` would apply them for
+/// the given profile and feature selection.
+pub fn effective(opts: BuildOptions) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let resolved = super::build::resolve(&config, &opts)?;
+    let compiler = config.compiler();
+
+    println!("\n{} [{}]", "Effective Build Configuration".bright_cyan().bold(), opts.mode);
+    println!("{}", "─".repeat(40));
+
+    println!("{}: {}", "Compiler".bright_yellow(), compiler);
+    println!("{}: {}", "Build type".bright_yellow(), resolved.build_type);
+    println!("{}: {}", "Optimization".bright_yellow(), resolved.build_type_flags().join(" "));
+    println!("{}: {}", "Standard".bright_yellow(), resolved.std.as_deref().unwrap_or("(compiler default)"));
+    println!("{}: {}", "LTO".bright_yellow(), resolved.lto);
+    println!("{}: {}", "Strip".bright_yellow(), resolved.strip);
+    println!("{}: {}", "Static".bright_yellow(), resolved.static_link);
+    println!("{}: {}", "Unity Build".bright_yellow(), match resolved.unity_batch_size {
+        Some(0) => "on".to_string(),
+        Some(size) => format!("on (batches of {})", size),
+        None => "off".to_string(),
+    });
+    println!("{}: {}", "Linker".bright_yellow(),
+        super::build::select_linker(&config, &opts)?.as_deref().unwrap_or("(compiler default)"));
+    println!("{}: {}", "Generator".bright_yellow(),
+        super::build::select_generator(&config, &opts)?.unwrap_or("(CMake default)"));
+
+    let mut flags = resolved.flags.clone();
+    flags.extend(config.stdlib_flags(&compiler)?);
+
+    print_list("Features", &resolved.features);
+    print_list("Compile Flags", &flags);
+    print_list("Defines", &resolved.defines.iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>());
+    print_list("Include Directories", &resolved.include_dirs);
+    print_list("System Include Directories", &resolved.system_include_dirs);
+    print_list("Link Libraries", &resolved.link_libs);
+    print_list("Library Directories", &resolved.lib_dirs);
+    print_list("vcpkg Packages", &resolved.vcpkg_packages);
+    print_list("Path Dependency Libraries", &resolved.path_dep_libs);
+    print_list("Excluded Sources", &resolved.excluded_sources.iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>());
+
     println!();
     Ok(())
 }
+
+fn print_list(title: &str, items: &[String]) {
+    println!("\n{}", title.bright_cyan());
+    if items.is_empty() {
+        println!("  {}", "(none)".dimmed());
+    }
+    for item in items {
+        println!("  • {}", item);
+    }
+}
+
+/// Disk used by each installed vcpkg package, largest first. Sizes come from
+/// the file lists vcpkg keeps in `<installed>/vcpkg/info`, so headers,
+/// libraries and share/ files are all attributed to the port that owns them.
+pub fn deps_size() -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let mut direct = config.vcpkg_packages();
+    direct.extend(config.dev_vcpkg_packages());
+
+    // Manifest-mode installs take precedence over classic-mode ones
+    let mut sizes: BTreeMap<String, u64> = BTreeMap::new();
+    for root in crate::vcpkg::installed_roots() {
+        for (package, size) in installed_sizes(&root) {
+            sizes.entry(package).or_insert(size);
+        }
+    }
+
+    if sizes.is_empty() {
+        println!("{}", "No installed vcpkg packages found. Run 'zora fetch' first.".yellow());
+        return Ok(());
+    }
+
+    let mut sizes: Vec<(String, u64)> = sizes.into_iter().collect();
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    println!("\n{}", "Dependency Sizes".bright_cyan().bold());
+    println!("{}", "─".repeat(40));
+
+    let width = sizes.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (package, size) in &sizes {
+        let line = format!("  {:width$}  {:>10}", package, format_size(*size), width = width);
+        if direct.contains(package) {
+            println!("{}", line);
+        } else {
+            println!("{} {}", line.dimmed(), "(transitive)".dimmed());
+        }
+    }
+
+    let total: u64 = sizes.iter().map(|(_, size)| size).sum();
+    println!("{}", "─".repeat(40));
+    println!("  {:width$}  {:>10}", "Total", format_size(total), width = width);
+    println!();
+    Ok(())
+}
+
+/// Package name -> bytes on disk, from the `<name>_<version>_<triplet>.list`
+/// files under `root/vcpkg/info`. Listed paths are relative to `root`.
+fn installed_sizes(root: &Path) -> BTreeMap<String, u64> {
+    let mut sizes = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(root.join("vcpkg").join("info")) else {
+        return sizes;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "list") {
+            continue;
+        }
+        let Some(package) = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('_').next())
+        else {
+            continue;
+        };
+
+        let size: u64 = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|file| fs::metadata(root.join(file)).ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+            .sum();
+
+        *sizes.entry(package.to_string()).or_insert(0) += size;
+    }
+
+    sizes
+}
@@ -4,6 +4,8 @@ use std::fs;
 use std::path::Path;
 use tera::{Context as TeraContext, Tera};
 
+use crate::config::NewConfig;
+
 const PROJECT_TOML_TEMPLATE: &str = r#"name = "{{ name }}"
 version = "0.1.0"
 type = "{{ project_type }}"
@@ -19,10 +21,11 @@ dirs = ["include"]
 [build]
 flags = []
 optimization = "2"
-
+{% if is_objc %}frameworks = ["Foundation"]
+{% endif %}
 [deps]
 
-[dev-deps]
+[dev-dependencies]
 
 [profile.dev]
 opt_level = "0"
@@ -36,22 +39,35 @@ opt_level = "3"
 debug = false
 lto = true
 strip = true
-flags = ["-Wall", "-Wextra", "-O3", "-DNDEBUG"]
+flags = ["-Wall", "-Wextra", "-DNDEBUG"]
+
+[new]
+# Style of headers generated by 'zora new'
+# header_guard = "pragma-once"  # or "ifndef" (default)
+# guard_prefix = "MYLIB_"
 
 [features]
-# Define your features here
-# example = ["dep:some-package"]
+# Each feature lists other features, dependencies (optional ones included)
+# and defines it turns on. Enable with --features or default_features.
+# example = ["dep:some-package", "define:USE_EXAMPLE=1"]
 
 [scripts]
 # Custom build scripts
 # prebuild = "echo 'Starting build'"
 # postbuild = "echo 'Build complete'"
+# Arguments after `zora script <name> --` are $1, $2, ...
+# greet = { cmd = "echo Hello $1", env = { LANG = "C" }, cwd = "scripts" }
 
 {% if is_lib %}
 [tests]
 dirs = ["tests"]
 harness = true
 {% endif %}
+{% if bin_name %}
+[[bin]]
+name = "{{ bin_name }}"
+path = "src/main.{{ ext }}"
+{% endif %}
 "#;
 
 const MAIN_C_TEMPLATE: &str = r#"#include <stdio.h>
@@ -70,6 +86,37 @@ int main() {
 }
 "#;
 
+const MAIN_OBJC_TEMPLATE: &str = r#"#import <Foundation/Foundation.h>
+
+int main(int argc, const char *argv[]) {
+    @autoreleasepool {
+        NSLog(@"Hello from {{ name }}!");
+    }
+    return 0;
+}
+"#;
+
+/// Frontend for `init --bin-and-lib`, calling into the library.
+const MAIN_USING_LIB_C_TEMPLATE: &str = r#"#include <stdio.h>
+#include "{{ name }}.h"
+
+int main(void) {
+    {{ name }}_hello();
+    printf("2 + 2 = %d\n", {{ name }}_add(2, 2));
+    return 0;
+}
+"#;
+
+const MAIN_USING_LIB_CPP_TEMPLATE: &str = r#"#include <iostream>
+#include "{{ name }}.hpp"
+
+int main() {
+    {{ name }}::hello();
+    std::cout << "2 + 2 = " << {{ name }}::add(2, 2) << std::endl;
+    return 0;
+}
+"#;
+
 const LIB_C_TEMPLATE: &str = r#"#include "{{ name }}.h"
 
 void {{ name }}_hello(void) {
@@ -81,8 +128,7 @@ int {{ name }}_add(int a, int b) {
 }
 "#;
 
-const LIB_H_TEMPLATE: &str = r#"#ifndef {{ name_upper }}_H
-#define {{ name_upper }}_H
+const LIB_H_TEMPLATE: &str = r#"{{ guard_open }}
 
 #include <stdio.h>
 
@@ -96,9 +142,9 @@ int {{ name }}_add(int a, int b);
 #ifdef __cplusplus
 }
 #endif
-
-#endif // {{ name_upper }}_H
-"#;
+{% if guard_close %}
+{{ guard_close }}
+{% endif %}"#;
 
 const LIB_CPP_TEMPLATE: &str = r#"#include "{{ name }}.hpp"
 #include <iostream>
@@ -116,8 +162,7 @@ int add(int a, int b) {
 } // namespace {{ name }}
 "#;
 
-const LIB_HPP_TEMPLATE: &str = r#"#ifndef {{ name_upper }}_HPP
-#define {{ name_upper }}_HPP
+const LIB_HPP_TEMPLATE: &str = r#"{{ guard_open }}
 
 namespace {{ name }} {
 
@@ -125,9 +170,9 @@ void hello();
 int add(int a, int b);
 
 } // namespace {{ name }}
-
-#endif // {{ name_upper }}_HPP
-"#;
+{% if guard_close %}
+{{ guard_close }}
+{% endif %}"#;
 
 const TEST_TEMPLATE: &str = r#"#include <assert.h>
 #include <stdio.h>
@@ -153,12 +198,15 @@ const GITIGNORE_TEMPLATE: &str = r#"# Build artifacts
 /build
 compile_commands.json
 
-# vcpkg
-/vcpkg_installed
-
 # Lock file
 project.lock
 
+# Local overrides
+project.local.toml
+
+# Fuzzing
+/fuzz/artifacts
+
 # IDE
 .vscode/
 .idea/
@@ -182,6 +230,44 @@ Thumbs.db
 *.exe
 "#;
 
+const EDITORCONFIG_TEMPLATE: &str = r#"root = true
+
+[*]
+charset = utf-8
+end_of_line = lf
+insert_final_newline = true
+trim_trailing_whitespace = true
+
+[*.{c,cpp,cc,cxx,h,hpp,hh,hxx}]
+indent_style = space
+indent_size = 4
+
+[*.toml]
+indent_style = space
+indent_size = 2
+"#;
+
+/// Where the originally generated copy of each managed file is kept, as the
+/// merge base for `zora upgrade`.
+pub const STATE_DIR: &str = ".zora/state";
+
+/// Boilerplate zora owns and `zora upgrade` can refresh, with its current
+/// rendering. Source files and project.toml are never managed.
+pub fn managed_files() -> Result<Vec<(&'static str, String)>> {
+    let ctx = TeraContext::new();
+    Ok(vec![
+        (".gitignore", Tera::one_off(GITIGNORE_TEMPLATE, &ctx, false)?),
+        (".editorconfig", EDITORCONFIG_TEMPLATE.to_string()),
+    ])
+}
+
+/// Records the generated version of a managed file as its merge base.
+pub fn record_state(file: &str, content: &str) -> Result<()> {
+    fs::create_dir_all(STATE_DIR).context("failed to create .zora/state")?;
+    fs::write(Path::new(STATE_DIR).join(file.trim_start_matches('.')), content)
+        .with_context(|| format!("failed to record state for {}", file))
+}
+
 const README_TEMPLATE: &str = r#"# {{ name }}
 
 {{ description }}
@@ -237,7 +323,14 @@ version = 1
 [packages]
 "#;
 
-pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
+pub fn run(
+    name_opt: Option<String>,
+    cpp: bool,
+    objc: bool,
+    lib: bool,
+    bin_and_lib: bool,
+    from: Option<String>,
+) -> Result<()> {
     let cwd = std::env::current_dir().context("failed to get current directory")?;
     let project_name = match name_opt {
         Some(n) => n,
@@ -252,8 +345,15 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
         bail!("project.toml already exists in this directory");
     }
 
+    if let Some(url) = from {
+        return super::template::init_from(&url, &project_name);
+    }
+
     println!("{}", "Initializing project...".bright_cyan());
 
+    // A library whose frontend is a [[bin]] built against it
+    let lib = lib || bin_and_lib;
+
     // Create directories
     fs::create_dir_all("src").context("failed to create src/")?;
     fs::create_dir_all("include").context("failed to create include/")?;
@@ -264,9 +364,13 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
         fs::create_dir_all("examples").context("failed to create examples/")?;
     }
 
-    let language = if cpp { "cpp" } else { "c" };
+    let (language, ext, language_name) = match (cpp, objc) {
+        (false, false) => ("c", "c", "C"),
+        (true, false) => ("cpp", "cpp", "C++"),
+        (false, true) => ("objc", "m", "Objective-C"),
+        (true, true) => ("objcpp", "mm", "Objective-C++"),
+    };
     let project_type = if lib { "lib" } else { "exec" };
-    let ext = if cpp { "cpp" } else { "c" };
     let header_ext = if cpp { "hpp" } else { "h" };
 
     let mut ctx = TeraContext::new();
@@ -276,10 +380,15 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
     ctx.insert("project_type", project_type);
     ctx.insert("is_lib", &lib);
     ctx.insert("is_cpp", &cpp);
+    ctx.insert("is_objc", &objc);
     ctx.insert("header_ext", header_ext);
+    ctx.insert("ext", ext);
+    // CMake target names must be unique, so the frontend can't share the library's
+    let bin_name = bin_and_lib.then(|| format!("{}-cli", project_name));
+    ctx.insert("bin_name", &bin_name);
     ctx.insert("description", &format!("A {} {} project", 
-        if cpp { "C++" } else { "C" },
-        if lib { "library" } else { "executable" }
+        language_name,
+        if bin_and_lib { "library and executable" } else if lib { "library" } else { "executable" }
     ));
 
     // Write source files
@@ -290,31 +399,53 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
             Tera::one_off(LIB_C_TEMPLATE, &ctx, false)?
         };
 
+        // No manifest to read [new] from yet, so the defaults apply
+        let (guard_open, guard_close) = NewConfig::default().header_guard(&project_name, header_ext)?;
+        ctx.insert("guard_open", &guard_open);
+        ctx.insert("guard_close", &guard_close);
+
         let lib_header = if cpp {
             Tera::one_off(LIB_HPP_TEMPLATE, &ctx, false)?
         } else {
             Tera::one_off(LIB_H_TEMPLATE, &ctx, false)?
         };
 
-        fs::write(format!("src/{}.{}", project_name, ext), lib_src)?;
+        let lib_path = if bin_and_lib {
+            format!("src/lib.{}", ext)
+        } else {
+            format!("src/{}.{}", project_name, ext)
+        };
+        fs::write(&lib_path, lib_src)?;
         fs::write(format!("include/{}.{}", project_name, header_ext), lib_header)?;
 
         // Create basic test
         let test_content = Tera::one_off(TEST_TEMPLATE, &ctx, false)?;
         fs::write(format!("tests/test_{}.{}", project_name, ext), test_content)?;
 
-        println!("  {} {}", "Created".green(), format!("src/{}.{}", project_name, ext));
-        println!("  {} {}", "Created".green(), format!("include/{}.{}", project_name, header_ext));
-        println!("  {} {}", "Created".green(), format!("tests/test_{}.{}", project_name, ext));
+        println!("  {} {}", "Created".green(), lib_path);
+        println!("  {} include/{}.{}", "Created".green(), project_name, header_ext);
+        println!("  {} tests/test_{}.{}", "Created".green(), project_name, ext);
+
+        if bin_and_lib {
+            let main_src = if cpp {
+                Tera::one_off(MAIN_USING_LIB_CPP_TEMPLATE, &ctx, false)?
+            } else {
+                Tera::one_off(MAIN_USING_LIB_C_TEMPLATE, &ctx, false)?
+            };
+            fs::write(format!("src/main.{}", ext), main_src)?;
+            println!("  {} src/main.{}", "Created".green(), ext);
+        }
     } else {
-        let main_src = if cpp {
+        let main_src = if objc {
+            Tera::one_off(MAIN_OBJC_TEMPLATE, &ctx, false)?
+        } else if cpp {
             Tera::one_off(MAIN_CPP_TEMPLATE, &ctx, false)?
         } else {
             Tera::one_off(MAIN_C_TEMPLATE, &ctx, false)?
         };
 
         fs::write(format!("src/main.{}", ext), main_src)?;
-        println!("  {} {}", "Created".green(), format!("src/main.{}", ext));
+        println!("  {} src/main.{}", "Created".green(), ext);
     }
 
     // Write project.toml
@@ -326,10 +457,12 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
     fs::write("project.lock", ZORA_LOCK_TEMPLATE)?;
     println!("  {} project.lock", "Created".green());
 
-    // Write .gitignore
-    let gitignore = Tera::one_off(GITIGNORE_TEMPLATE, &ctx, false)?;
-    fs::write(".gitignore", gitignore)?;
-    println!("  {} .gitignore", "Created".green());
+    // Write .gitignore and other managed boilerplate
+    for (file, content) in managed_files()? {
+        fs::write(file, &content)?;
+        record_state(file, &content)?;
+        println!("  {} {}", "Created".green(), file);
+    }
 
     // Write README
     let readme = Tera::one_off(README_TEMPLATE, &ctx, false)?;
@@ -338,13 +471,17 @@ pub fn run(name_opt: Option<String>, cpp: bool, lib: bool) -> Result<()> {
 
     println!("\n{} Initialized {} project: {}", 
         "✓".green().bold(), 
-        if lib { "library" } else { "executable" },
+        if bin_and_lib { "library and executable" } else if lib { "library" } else { "executable" },
         project_name.bright_yellow()
     );
     
     println!("\n{}", "Next steps:".bright_cyan());
     println!("  {} zora build", "→".bright_blue());
-    println!("  {} zora run", "→".bright_blue());
+    if bin_and_lib {
+        println!("  {} zora run --all", "→".bright_blue());
+    } else {
+        println!("  {} zora run", "→".bright_blue());
+    }
     if lib {
         println!("  {} zora test", "→".bright_blue());
     }
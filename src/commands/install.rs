@@ -3,10 +3,12 @@ use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use std::fs;
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
+use super::script::run_hook;
 use crate::config::ProjectConfig;
 
-pub fn run(prefix: Option<String>) -> Result<()> {
+pub fn run(prefix: Option<String>, destdir: Option<String>, debug_info: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
@@ -22,15 +24,15 @@ pub fn run(prefix: Option<String>) -> Result<()> {
         }
     });
 
-    let bin_dir = PathBuf::from(&install_prefix).join("bin");
-    let lib_dir = PathBuf::from(&install_prefix).join("lib");
-    let include_dir = PathBuf::from(&install_prefix).join("include");
+    let root = install_root(&install_prefix, destdir.as_deref());
+    let bin_dir = root.join("bin");
+    let lib_dir = root.join("lib");
+    let include_dir = root.join("include");
 
-    println!("{}", format!("Installing to {}...", install_prefix).bright_cyan());
+    println!("{}", format!("Installing to {}...", root.display()).bright_cyan());
 
-    // Ensure target/release exists
-    let release_dir = "target/release";
-    if !Path::new(release_dir).exists() {
+    let artifacts = super::build::target_artifacts(&config, None, "release");
+    if artifacts.is_empty() {
         bail!("Release build not found. Run 'zora build --release' first.");
     }
 
@@ -38,41 +40,25 @@ pub fn run(prefix: Option<String>) -> Result<()> {
     if config.is_library() {
         fs::create_dir_all(&lib_dir)?;
         
-        for entry in fs::read_dir(release_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-            if let Some(ext) = path.extension() {
-                if ext == "a" || ext == "so" || ext == "dll" || ext == "dylib" {
-                    let dest = lib_dir.join(path.file_name().unwrap());
-                    fs::copy(&path, &dest)?;
-                    println!("  {} {}", "Installed".green(), dest.display());
-                }
-            }
+        for artifact in &artifacts {
+            let dest = lib_dir.join(artifact.path.file_name().unwrap_or_default());
+            fs::copy(&artifact.path, &dest)
+                .with_context(|| format!("failed to install {}", artifact.path.display()))?;
+            println!("  {} {}", "Installed".green(), dest.display());
         }
 
         // Install headers
-        if Path::new("include").exists() {
-            fs::create_dir_all(&include_dir)?;
-            for entry in fs::read_dir("include")? {
-                let entry = entry?;
-                let dest = include_dir.join(entry.file_name());
-                fs::copy(entry.path(), &dest)?;
-                println!("  {} {}", "Installed".green(), dest.display());
-            }
+        for dest in copy_headers(&config, &include_dir)? {
+            println!("  {} {}", "Installed".green(), dest.display());
         }
     } else {
         fs::create_dir_all(&bin_dir)?;
         
-        let exe_name = if cfg!(windows) {
-            format!("{}.exe", config.name)
-        } else {
-            config.name.clone()
-        };
-        
-        let src = PathBuf::from(release_dir).join(&exe_name);
-        let dest = bin_dir.join(&exe_name);
+        let src = &artifacts[0].path;
+        let dest = bin_dir.join(src.file_name().unwrap_or_default());
         
-        fs::copy(&src, &dest)?;
+        fs::copy(src, &dest)
+            .with_context(|| format!("failed to install {}", src.display()))?;
         
         #[cfg(unix)]
         {
@@ -83,8 +69,83 @@ pub fn run(prefix: Option<String>) -> Result<()> {
         }
         
         println!("  {} {}", "Installed".green(), dest.display());
+
+        if debug_info && super::build::copy_debug_info(src, &bin_dir)? {
+            println!("  {} {}", "Installed".green(), super::build::debug_info_path(&dest).display());
+        }
+    }
+
+    if let Some(script) = &config.install.post_install {
+        if destdir.is_some() {
+            println!("  {} post_install hook (staging install)", "Skipped".yellow());
+        } else {
+            run_hook("post_install", script, &[("ZORA_INSTALL_PREFIX", &install_prefix)])?;
+        }
     }
 
     println!("\n{} Installation complete", "✓".green().bold());
     Ok(())
 }
+
+/// Copies a library's public headers into `dest`: the include/ tree, or in
+/// the colocated layout the `[includes] public` headers from the source dirs.
+/// Returns the copied destination paths.
+pub fn copy_headers(config: &ProjectConfig, dest: &Path) -> Result<Vec<PathBuf>> {
+    if !config.headers_colocated() {
+        if !Path::new("include").exists() {
+            return Ok(vec![]);
+        }
+        return copy_tree(Path::new("include"), dest);
+    }
+
+    // Headers beside an executable's sources are all private
+    if !config.is_library() {
+        return Ok(vec![]);
+    }
+
+    let mut copied = vec![];
+    for (header, relative) in config.public_headers()? {
+        let target = dest.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&header, &target)
+            .with_context(|| format!("failed to copy {}", header.display()))?;
+        copied.push(target);
+    }
+    Ok(copied)
+}
+
+/// Recursively copies the files under `src` into `dest`, preserving
+/// subdirectories (so `include/foo/bar.h` lands in `<dest>/foo/bar.h`).
+/// Returns the copied destination paths.
+pub fn copy_tree(src: &Path, dest: &Path) -> Result<Vec<PathBuf>> {
+    let mut copied = vec![];
+
+    for entry in WalkDir::new(src).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+        let target = dest.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+            copied.push(target);
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Directory files are actually written to: the prefix itself, or the prefix
+/// re-rooted under `destdir` for staged installs (as with make's DESTDIR).
+pub fn install_root(prefix: &str, destdir: Option<&str>) -> PathBuf {
+    match destdir {
+        Some(destdir) => {
+            let relative: PathBuf = Path::new(prefix)
+                .components()
+                .filter(|c| matches!(c, std::path::Component::Normal(_)))
+                .collect();
+            Path::new(destdir).join(relative)
+        }
+        None => PathBuf::from(prefix),
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::ProjectConfig;
+
+struct LicenseInfo {
+    package: String,
+    license: Option<String>,
+    copyright_file: Option<PathBuf>,
+}
+
+pub fn run(output: Option<String>) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+
+    let packages = config.vcpkg_packages();
+    if packages.is_empty() {
+        println!("{}", "No dependencies".yellow());
+        return Ok(());
+    }
+
+    let share_dirs = installed_share_dirs();
+
+    let infos: Vec<LicenseInfo> = packages.iter()
+        .map(|package| collect(package, &share_dirs))
+        .collect();
+
+    println!("\n{}", "Dependency Licenses".bright_cyan().bold());
+    println!("{}", "─".repeat(40));
+
+    let mut unknown = 0;
+    for info in &infos {
+        match &info.license {
+            Some(license) => println!("{:<24} {}", info.package, license),
+            None => {
+                unknown += 1;
+                println!("{:<24} {}", info.package, "unknown".yellow());
+            }
+        }
+    }
+
+    if unknown > 0 {
+        println!("\n{} Could not determine the license of {} package(s)",
+            "⚠".yellow().bold(),
+            unknown
+        );
+    }
+
+    if let Some(path) = output {
+        let mut notice = String::new();
+        for info in &infos {
+            notice.push_str(&format!("{}\n{}\n\n", info.package, "=".repeat(info.package.len())));
+            match &info.copyright_file {
+                Some(file) => {
+                    let text = fs::read_to_string(file)
+                        .with_context(|| format!("failed to read {}", file.display()))?;
+                    notice.push_str(text.trim_end());
+                }
+                None => notice.push_str("License text not found."),
+            }
+            notice.push_str("\n\n\n");
+        }
+
+        fs::write(&path, notice)
+            .with_context(|| format!("failed to write {}", path))?;
+        println!("\n{} Wrote license texts to {}", "✓".green().bold(), path);
+    }
+
+    println!();
+    Ok(())
+}
+
+/// `share/` directories of every vcpkg installed tree zora knows about,
+/// covering both manifest-mode and classic-mode installs.
+fn installed_share_dirs() -> Vec<PathBuf> {
+    let mut share_dirs = vec![];
+    for root in crate::vcpkg::installed_roots() {
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let share = entry.path().join("share");
+            if share.is_dir() {
+                share_dirs.push(share);
+            }
+        }
+    }
+
+    share_dirs
+}
+
+fn collect(package: &str, share_dirs: &[PathBuf]) -> LicenseInfo {
+    for share in share_dirs {
+        let package_dir = share.join(package);
+        if !package_dir.is_dir() {
+            continue;
+        }
+
+        let copyright = package_dir.join("copyright");
+        let copyright_file = copyright.exists().then_some(copyright);
+
+        let license = spdx_license(&package_dir.join("vcpkg.spdx.json"))
+            .or_else(|| copyright_file.as_ref()
+                .and_then(|f| fs::read_to_string(f).ok())
+                .and_then(|text| detect_license(&text)));
+
+        return LicenseInfo {
+            package: package.to_string(),
+            license,
+            copyright_file,
+        };
+    }
+
+    LicenseInfo {
+        package: package.to_string(),
+        license: None,
+        copyright_file: None,
+    }
+}
+
+/// Reads the declared license from the SPDX document vcpkg writes per port.
+fn spdx_license(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let doc: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    doc.get("packages")?
+        .as_array()?
+        .iter()
+        .filter_map(|p| p.get("licenseDeclared").or_else(|| p.get("licenseConcluded")))
+        .filter_map(|l| l.as_str())
+        .find(|l| *l != "NOASSERTION")
+        .map(|l| l.to_string())
+}
+
+/// Best-effort identification of common licenses from their text.
+fn detect_license(text: &str) -> Option<String> {
+    let lower = text.to_lowercase();
+
+    let license = if lower.contains("apache license") && lower.contains("version 2.0") {
+        "Apache-2.0"
+    } else if lower.contains("gnu lesser general public license") {
+        "LGPL"
+    } else if lower.contains("gnu general public license") {
+        "GPL"
+    } else if lower.contains("mozilla public license") {
+        "MPL-2.0"
+    } else if lower.contains("boost software license") {
+        "BSL-1.0"
+    } else if lower.contains("permission is hereby granted, free of charge") {
+        "MIT"
+    } else if lower.contains("redistribution and use in source and binary forms") {
+        if lower.contains("neither the name") { "BSD-3-Clause" } else { "BSD-2-Clause" }
+    } else if lower.contains("this software is provided 'as-is'") {
+        "Zlib"
+    } else if lower.contains("this is free and unencumbered software") {
+        "Unlicense"
+    } else {
+        return None;
+    };
+
+    Some(license.to_string())
+}
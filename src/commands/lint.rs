@@ -1,16 +1,22 @@
 // src/commands/lint.rs
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 use walkdir::WalkDir;
 
 use crate::config::ProjectConfig;
 
-pub fn run(fix: bool) -> Result<()> {
+pub fn run(fix: bool, write_config: bool, force: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
+    if write_config {
+        return write_clang_tidy(&ProjectConfig::load()?, force);
+    }
+
     let clang_tidy_check = Command::new("clang-tidy")
         .arg("--version")
         .output();
@@ -28,8 +34,8 @@ pub fn run(fix: bool) -> Result<()> {
         for entry in WalkDir::new(source_dir).into_iter().filter_map(|e| e.ok()) {
             let path = entry.path();
             if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "c" || ext == "cpp" {
+                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    if config.source_extensions().contains(&ext) {
                         files.push(path.to_path_buf());
                     }
                 }
@@ -46,10 +52,7 @@ pub fn run(fix: bool) -> Result<()> {
             cmd.arg("--fix");
         }
 
-        cmd.arg("--");
-        for include_dir in &config.includes.dirs {
-            cmd.arg(format!("-I{}", include_dir));
-        }
+        cmd.arg("--").args(config.language_flags()).args(config.include_flags());
 
         let output = cmd.output()?;
         let stdout = String::from_utf8_lossy(&output.stdout);
@@ -70,4 +73,29 @@ pub fn run(fix: bool) -> Result<()> {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Writes a .clang-tidy generated from the [lint] section.
+fn write_clang_tidy(config: &ProjectConfig, force: bool) -> Result<()> {
+    let path = Path::new(".clang-tidy");
+    if path.exists() && !force {
+        bail!(".clang-tidy already exists. Use --force to overwrite it.");
+    }
+
+    let lint = &config.lint;
+    let mut content = String::from("# Generated by zora from [lint] in project.toml\n");
+
+    if !lint.checks.is_empty() {
+        content.push_str(&format!("Checks: '{}'\n", lint.checks.join(",")));
+    }
+    if !lint.warnings_as_errors.is_empty() {
+        content.push_str(&format!("WarningsAsErrors: '{}'\n", lint.warnings_as_errors.join(",")));
+    }
+    if let Some(filter) = &lint.header_filter {
+        content.push_str(&format!("HeaderFilterRegex: '{}'\n", filter));
+    }
+
+    fs::write(path, content).context("failed to write .clang-tidy")?;
+    println!("{} Wrote .clang-tidy", "✓".green().bold());
+    Ok(())
+}
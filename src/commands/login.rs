@@ -0,0 +1,38 @@
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::io::{self, IsTerminal};
+
+use crate::credentials::{Credentials, RegistryCredentials, DEFAULT_REGISTRY};
+
+/// Prompts for a registry token, or reads it from stdin when that isn't a
+/// terminal, and stores it in the credentials file.
+pub fn run(registry: Option<String>) -> Result<()> {
+    let registry = registry.unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+
+    // Not echoed at a terminal; piped in (e.g. from a CI secret) otherwise
+    let token = if io::stdin().is_terminal() {
+        rpassword::prompt_password(format!("Token for registry '{}': ", registry))?
+    } else {
+        let mut token = String::new();
+        io::stdin().read_line(&mut token)?;
+        token
+    };
+    let token = token.trim();
+
+    if token.is_empty() {
+        bail!("No token given");
+    }
+
+    let mut credentials = Credentials::load()?;
+    credentials.registries.insert(registry.clone(), RegistryCredentials {
+        token: token.to_string(),
+    });
+    let path = credentials.save()?;
+
+    println!("{} Logged in to '{}' (token saved to {})",
+        "✓".green().bold(),
+        registry,
+        path.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::credentials::{Credentials, DEFAULT_REGISTRY};
+
+/// Removes a registry's token from the credentials file.
+pub fn run(registry: Option<String>) -> Result<()> {
+    let registry = registry.unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+
+    let mut credentials = Credentials::load()?;
+    if credentials.registries.remove(&registry).is_none() {
+        println!("{} Not logged in to '{}'", "-".yellow(), registry);
+        return Ok(());
+    }
+    credentials.save()?;
+
+    println!("{} Logged out of '{}'", "✓".green().bold(), registry);
+    Ok(())
+}
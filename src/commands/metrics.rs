@@ -0,0 +1,167 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use walkdir::WalkDir;
+
+use super::cache::format_size;
+use crate::config::{ProjectConfig, HEADER_EXTENSIONS};
+
+/// Extensions counted besides headers: every C-family source zora builds.
+const SOURCE_EXTENSIONS: &[&str] = &["c", "cc", "cpp", "cxx", "m", "mm"];
+
+#[derive(Debug, Default, Serialize)]
+struct Counts {
+    files: usize,
+    bytes: u64,
+    code: usize,
+    comment: usize,
+    blank: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.files += other.files;
+        self.bytes += other.bytes;
+        self.code += other.code;
+        self.comment += other.comment;
+        self.blank += other.blank;
+    }
+}
+
+#[derive(Serialize)]
+struct Metrics {
+    extensions: BTreeMap<String, Counts>,
+    total: Counts,
+    average_file_bytes: u64,
+}
+
+/// Counts code, comment and blank lines per extension across the source and
+/// include dirs, skipping `[sources] exclude` and git-ignored files.
+pub fn run(json: bool) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    let config = ProjectConfig::load()?;
+    let files = project_files(&config)?;
+
+    let mut extensions: BTreeMap<String, Counts> = BTreeMap::new();
+    let mut total = Counts::default();
+    for (path, ext) in &files {
+        let content = fs::read(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let counts = count_lines(&String::from_utf8_lossy(&content), content.len() as u64);
+        total.add(&counts);
+        extensions.entry(ext.clone()).or_default().add(&counts);
+    }
+
+    let metrics = Metrics {
+        average_file_bytes: if total.files == 0 { 0 } else { total.bytes / total.files as u64 },
+        extensions,
+        total,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metrics)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "Codebase Metrics".bright_cyan().bold());
+    println!("{}", "─".repeat(52));
+    println!("{:<10} {:>6} {:>10} {:>10} {:>10}", "Extension", "Files", "Code", "Comment", "Blank");
+    for (ext, counts) in &metrics.extensions {
+        println!("{:<10} {:>6} {:>10} {:>10} {:>10}", ext, counts.files, counts.code, counts.comment, counts.blank);
+    }
+    println!("{}", "─".repeat(52));
+    let total = &metrics.total;
+    println!("{:<10} {:>6} {:>10} {:>10} {:>10}",
+        "Total".bold(), total.files, total.code, total.comment, total.blank);
+
+    println!("\n{}: {} ({} total)",
+        "Average file size".bright_yellow(),
+        format_size(metrics.average_file_bytes),
+        format_size(total.bytes)
+    );
+    Ok(())
+}
+
+/// Source and header files under the source and include dirs, with their
+/// extension, each listed once.
+fn project_files(config: &ProjectConfig) -> Result<Vec<(PathBuf, String)>> {
+    let excludes = config.exclude_patterns()?;
+
+    let mut dirs = config.sources.dirs.clone();
+    dirs.extend(config.include_dirs());
+
+    let mut files = vec![];
+    for dir in &dirs {
+        for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else { continue };
+            let counted = SOURCE_EXTENSIONS.contains(&ext) || HEADER_EXTENSIONS.contains(&ext);
+            if entry.file_type().is_file() && counted && !excludes.iter().any(|p| p.matches_path(path)) {
+                files.push((path.to_path_buf(), ext.to_string()));
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    let paths: Vec<PathBuf> = files.iter().map(|(path, _)| path.clone()).collect();
+    let ignored = crate::git::ignored(&paths);
+    files.retain(|(path, _)| !ignored.contains(path));
+
+    Ok(files)
+}
+
+/// Classifies each line as code, comment or blank. A line with any code on
+/// it counts as code; string literals aren't parsed, so a `//` inside one
+/// is taken for a comment.
+fn count_lines(content: &str, bytes: u64) -> Counts {
+    let mut counts = Counts { files: 1, bytes, ..Default::default() };
+    let mut in_block = false;
+
+    for line in content.lines() {
+        let mut rest = line.trim();
+        if rest.is_empty() {
+            counts.blank += 1;
+            continue;
+        }
+
+        let mut has_code = false;
+        while !rest.is_empty() {
+            if in_block {
+                match rest.find("*/") {
+                    Some(end) => {
+                        in_block = false;
+                        rest = rest[end + 2..].trim_start();
+                    }
+                    None => break,
+                }
+            } else if rest.starts_with("//") {
+                break;
+            } else if let Some(block) = rest.strip_prefix("/*") {
+                in_block = true;
+                rest = block;
+            } else {
+                has_code = true;
+                match (rest.find("//"), rest.find("/*")) {
+                    (Some(line), Some(block)) if block < line => rest = &rest[block..],
+                    (_, Some(block)) if !rest[..block].contains("//") => rest = &rest[block..],
+                    _ => break,
+                }
+            }
+        }
+
+        if has_code {
+            counts.code += 1;
+        } else {
+            counts.comment += 1;
+        }
+    }
+
+    counts
+}
@@ -30,3 +30,18 @@ pub mod new_project;
 pub mod workspace;
 pub mod publish;
 pub mod completions;
+pub mod fuzz;
+pub mod template;
+pub mod asm;
+pub mod licenses;
+pub mod doctor;
+pub mod compiledb;
+pub mod upgrade;
+pub mod fetch;
+pub mod export;
+pub mod graph;
+pub mod login;
+pub mod logout;
+pub mod analyze;
+pub mod metrics;
+pub mod artifact_path;
@@ -13,13 +13,12 @@ void {{ name }}_function(void) {
 }
 "#;
 
-const HEADER_TEMPLATE: &str = r#"#ifndef {{ name_upper }}_H
-#define {{ name_upper }}_H
+const HEADER_TEMPLATE: &str = r#"{{ guard_open }}
 
 void {{ name }}_function(void);
-
-#endif // {{ name_upper }}_H
-"#;
+{% if guard_close %}
+{{ guard_close }}
+{% endif %}"#;
 
 const TEST_TEMPLATE: &str = r#"#include <assert.h>
 #include <stdio.h>
@@ -36,27 +35,76 @@ int main(void) {
 }
 "#;
 
-pub fn run(file_type: &str, name: &str) -> Result<()> {
+const BENCH_TEMPLATE: &str = r#"#include <stdio.h>
+#include <time.h>
+{% if header %}#include "{{ header }}"
+{% endif %}
+// `zora bench` times the whole run, so keep setup outside the loop cheap
+#define ITERATIONS 1000000
+
+int main(void) {
+    clock_t start = clock();
+
+    for (long i = 0; i < ITERATIONS; i++) {
+        // Code to benchmark
+    }
+
+    double seconds = (double)(clock() - start) / CLOCKS_PER_SEC;
+    printf("{{ name }}: %.1f ns/iter\n", seconds * 1e9 / ITERATIONS);
+    return 0;
+}
+"#;
+
+const FIXTURE_README_TEMPLATE: &str = r#"# {{ name }}
+
+Test data for the {{ name }} tests. Tests run from the project root, so open
+files here as "tests/data/{{ name }}/<file>".
+"#;
+
+const BIN_TEMPLATE: &str = r#"#include <stdio.h>
+
+int main(void) {
+    printf("Hello from {{ name }}!\n");
+    return 0;
+}
+"#;
+
+pub fn run(file_type: &str, name: &str, with_test: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
     let config = ProjectConfig::load()?;
-    let ext = if config.is_cpp() { "cpp" } else { "c" };
+    let ext = config.source_extension();
     let header_ext = if config.is_cpp() { "hpp" } else { "h" };
 
     let mut ctx = TeraContext::new();
     ctx.insert("name", name);
     ctx.insert("name_upper", &name.to_uppercase());
 
+    if with_test && !matches!(file_type, "source" | "src") {
+        bail!("--with-test only applies to 'source'");
+    }
+
     match file_type {
         "source" | "src" => {
             let content = Tera::one_off(SOURCE_TEMPLATE, &ctx, false)?;
             let path = format!("src/{}.{}", name, ext);
             fs::write(&path, content)?;
             println!("{} {}", "Created".green(), path);
+
+            if with_test {
+                fs::create_dir_all("tests")?;
+                let content = Tera::one_off(TEST_TEMPLATE, &ctx, false)?;
+                let path = format!("tests/test_{}.{}", name, ext);
+                fs::write(&path, content)?;
+                println!("{} {}", "Created".green(), path);
+            }
         }
         "header" | "hdr" => {
+            let (guard_open, guard_close) = config.new.header_guard(name, header_ext)?;
+            ctx.insert("guard_open", &guard_open);
+            ctx.insert("guard_close", &guard_close);
             let content = Tera::one_off(HEADER_TEMPLATE, &ctx, false)?;
             let path = format!("include/{}.{}", name, header_ext);
             fs::write(&path, content)?;
@@ -69,8 +117,69 @@ pub fn run(file_type: &str, name: &str) -> Result<()> {
             fs::write(&path, content)?;
             println!("{} {}", "Created".green(), path);
         }
-        _ => bail!("Unknown file type '{}'. Use: source, header, or test", file_type),
+        "bench" => {
+            let path = format!("benches/{}.{}", name, ext);
+            if Path::new(&path).exists() {
+                bail!("{} already exists", path);
+            }
+
+            // Include the header of the same name if there is one
+            let header = format!("{}.{}", name, header_ext);
+            let has_header = config.include_dirs().iter().any(|dir| Path::new(dir).join(&header).exists());
+            ctx.insert("header", &Some(header).filter(|_| has_header));
+
+            fs::create_dir_all("benches")?;
+            let content = Tera::one_off(BENCH_TEMPLATE, &ctx, false)?;
+            fs::write(&path, content)?;
+            println!("{} {}", "Created".green(), path);
+        }
+        "fixture" => {
+            let dir = format!("tests/data/{}", name);
+            if Path::new(&dir).exists() {
+                bail!("{} already exists", dir);
+            }
+
+            fs::create_dir_all(&dir)?;
+            let content = Tera::one_off(FIXTURE_README_TEMPLATE, &ctx, false)?;
+            let path = format!("{}/README.md", dir);
+            fs::write(&path, content)?;
+            println!("{} {}", "Created".green(), path);
+        }
+        "bin" | "example" => {
+            if name == config.name || config.bins.iter().any(|bin| bin.name == name) {
+                bail!("A target named '{}' already exists", name);
+            }
+
+            let dir = if file_type == "bin" { "src/bin" } else { "examples" };
+            let path = format!("{}/{}.{}", dir, name, ext);
+            if Path::new(&path).exists() {
+                bail!("{} already exists", path);
+            }
+
+            fs::create_dir_all(dir)?;
+            let content = Tera::one_off(BIN_TEMPLATE, &ctx, false)?;
+            fs::write(&path, content)?;
+            println!("{} {}", "Created".green(), path);
+
+            add_bin_to_toml(name, &path)?;
+            println!("{} [[bin]] {} in project.toml", "Added".green(), name);
+        }
+        _ => bail!("Unknown file type '{}'. Use: source, header, test, bench, fixture, bin, or example", file_type),
     }
 
     Ok(())
 }
+
+/// Appends a `[[bin]]` entry to project.toml, leaving the rest of the file as is.
+fn add_bin_to_toml(name: &str, path: &str) -> Result<()> {
+    let mut content = fs::read_to_string("project.toml")
+        .context("failed to read project.toml")?;
+
+    if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("\n[[bin]]\nname = \"{}\"\npath = \"{}\"\n", name, path));
+
+    fs::write("project.toml", content)
+        .context("failed to write project.toml")
+}
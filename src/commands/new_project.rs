@@ -3,7 +3,7 @@ use colored::Colorize;
 use std::fs;
 use std::path::Path;
 
-pub fn run(path: String, cpp: bool, lib: bool, name: Option<String>) -> Result<()> {
+pub fn run(path: String, cpp: bool, objc: bool, lib: bool, bin_and_lib: bool, name: Option<String>) -> Result<()> {
     let project_path = Path::new(&path);
     
     if project_path.exists() {
@@ -17,7 +17,7 @@ pub fn run(path: String, cpp: bool, lib: bool, name: Option<String>) -> Result<(
     
     println!("{} Creating new project at {}", "→".bright_blue(), path);
     
-    crate::commands::init::run(name, cpp, lib)?;
+    crate::commands::init::run(name, cpp, objc, lib, bin_and_lib, None)?;
     
     Ok(())
 }
@@ -1,13 +1,14 @@
 // src/commands/package.rs
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
 use colored::Colorize;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
 use crate::config::ProjectConfig;
+use crate::paths;
 
-pub fn run(format: &str) -> Result<()> {
+pub fn run(format: &str, debug_info: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
@@ -17,13 +18,15 @@ pub fn run(format: &str) -> Result<()> {
     println!("{}", "Packaging project...".bright_cyan());
 
     // Ensure target/release exists
-    let release_dir = "target/release";
-    if !Path::new(release_dir).exists() {
+    let target_root = paths::target_root();
+    let release_dir = target_root.join("release");
+    if !release_dir.exists() {
         bail!("Release build not found. Run 'zora build --release' first.");
     }
 
     let package_name = format!("{}-{}", config.name, config.version);
-    let package_dir = format!("target/package/{}", package_name);
+    let package_root = target_root.join("package");
+    let package_dir = package_root.join(&package_name).to_string_lossy().into_owned();
 
     // Create package directory structure
     fs::create_dir_all(&package_dir)?;
@@ -34,7 +37,7 @@ pub fn run(format: &str) -> Result<()> {
     // Copy executable or library
     if config.is_library() {
         // Copy library files
-        for entry in fs::read_dir(release_dir)? {
+        for entry in fs::read_dir(&release_dir)? {
             let entry = entry?;
             let path = entry.path();
             if let Some(ext) = path.extension() {
@@ -52,20 +55,18 @@ pub fn run(format: &str) -> Result<()> {
             config.name.clone()
         };
         
-        let src = format!("{}/{}", release_dir, exe_name);
+        let src = release_dir.join(&exe_name);
         let dest = format!("{}/bin/{}", package_dir, exe_name);
-        fs::copy(src, dest)?;
-    }
+        fs::copy(&src, dest)?;
 
-    // Copy headers
-    if Path::new("include").exists() {
-        for entry in fs::read_dir("include")? {
-            let entry = entry?;
-            let dest = format!("{}/include/{}", package_dir, entry.file_name().to_str().unwrap());
-            fs::copy(entry.path(), dest)?;
+        if debug_info {
+            super::build::copy_debug_info(&src, Path::new(&format!("{}/bin", package_dir)))?;
         }
     }
 
+    // Copy headers
+    super::install::copy_headers(&config, Path::new(&format!("{}/include", package_dir)))?;
+
     // Copy README and LICENSE if they exist
     for file in &["README.md", "LICENSE", "LICENSE.txt"] {
         if Path::new(file).exists() {
@@ -76,23 +77,30 @@ pub fn run(format: &str) -> Result<()> {
     // Create archive
     let archive_name = match format {
         "tar" | "tar.gz" => {
-            let archive = format!("target/{}.tar.gz", package_name);
+            let archive = target_root.join(format!("{}.tar.gz", package_name));
             Command::new("tar")
-                .args(&["-czf", &archive, "-C", "target/package", &package_name])
+                .arg("-czf")
+                .arg(&archive)
+                .arg("-C")
+                .arg(&package_root)
+                .arg(&package_name)
                 .status()?;
             archive
         }
         "zip" => {
-            let archive = format!("target/{}.zip", package_name);
+            // zip runs inside the package root, so the archive lands one level up
+            let archive_file = format!("{}.zip", package_name);
             Command::new("zip")
-                .args(&["-r", &archive, &package_name])
-                .current_dir("target/package")
+                .arg("-r")
+                .arg(Path::new("..").join(&archive_file))
+                .arg(&package_name)
+                .current_dir(&package_root)
                 .status()?;
-            archive
+            target_root.join(archive_file)
         }
         _ => bail!("Unsupported format: {}. Use 'tar' or 'zip'", format),
     };
 
-    println!("{} Package created: {}", "✓".green().bold(), archive_name);
+    println!("{} Package created: {}", "✓".green().bold(), archive_name.display());
     Ok(())
 }
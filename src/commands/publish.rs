@@ -1,7 +1,16 @@
 use anyhow::{bail, Result};
 use colored::Colorize;
 
-pub fn run(_dry_run: bool, _registry: Option<String>) -> Result<()> {
+use crate::credentials::{Credentials, DEFAULT_REGISTRY};
+
+pub fn run(dry_run: bool, registry: Option<String>) -> Result<()> {
+    let registry = registry.unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+
+    // A dry run never talks to the registry, so it doesn't need a token
+    if !dry_run && Credentials::load()?.token(&registry).is_none() {
+        bail!("No token for registry '{}'. Run 'zora login {}' first.", registry, registry);
+    }
+
     println!("{}", "Package publishing coming soon!".yellow());
     bail!("Publish command is not yet implemented");
 }
@@ -1,8 +1,9 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{bail, Result};
 use colored::Colorize;
 use std::fs;
 use std::path::Path;
-use std::process::Command;
+
+use crate::config::ProjectConfig;
 
 pub fn run(packages: Vec<String>) -> Result<()> {
     if packages.is_empty() {
@@ -13,27 +14,21 @@ pub fn run(packages: Vec<String>) -> Result<()> {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
-    println!("{}", "Removing packages...".bright_cyan());
-
-    for package in &packages {
-        println!("  {} Removing {}...", "→".bright_blue(), package);
-        
-        let status = Command::new("vcpkg")
-            .args(&["remove", package])
-            .status()
-            .context(format!("failed to remove package: {}", package))?;
-
-        if status.success() {
-            println!("  {} Removed {}", "✓".green(), package);
-        }
-    }
-
     // Update project.toml
     let project_toml = fs::read_to_string("project.toml")?;
     let updated_toml = remove_dependencies_from_toml(&project_toml, &packages)?;
     fs::write("project.toml", updated_toml)?;
 
-    println!("\n{} Removed {} package(s)", "✓".green().bold(), packages.len());
+    println!("{} Removed {} package(s)", "✓".green().bold(), packages.len());
+
+    // The next build's configure drops them from .build/vcpkg_installed
+    let config = ProjectConfig::load()?;
+    if config.use_vcpkg && crate::vcpkg::sync_manifest(&config)? {
+        println!("{} Updated {}", "✓".green().bold(), crate::vcpkg::MANIFEST_FILE);
+    }
+    if crate::lockfile::update(&config)? {
+        println!("{} Updated {}", "✓".green().bold(), crate::lockfile::LOCK_FILE);
+    }
     Ok(())
 }
 
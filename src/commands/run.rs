@@ -1,36 +1,129 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::process::Command;
-
-pub fn run(
-    name_opt: Option<String>, 
-    mode: &str,
-    verbose: bool,
-    jobs: Option<usize>,
-    args: Vec<String>
-) -> Result<()> {
-    // First, build the project
-    println!("{}", "Building project...".bright_cyan());
-    super::build::run(name_opt.clone(), mode, verbose, jobs, vec![], false, false, None, false)?;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::build::BuildOptions;
+use crate::config::ProjectConfig;
+use crate::output::Verbosity;
+
+/// Options for `zora run`, as collected from the CLI.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub name: Option<String>,
+    pub mode: String,
+    pub verbosity: Verbosity,
+    pub jobs: Option<usize>,
+    pub args: Vec<String>,
+    /// Run every executable the project builds instead of just the main one
+    pub all: bool,
+    /// With `all`, keep running the remaining executables after a failure
+    pub keep_going: bool,
+    /// Named build slot to build and run from (see `zora build --build-dir`)
+    pub build_dir: Option<String>,
+    /// Kill the program if it runs longer than this
+    pub timeout: Option<Duration>,
+    /// Save the invocation and its output to this file
+    pub record: Option<PathBuf>,
+    /// Re-run the invocation saved in this file and compare the output
+    pub replay: Option<PathBuf>,
+    /// Print nothing but the program's own output and exit with its code
+    pub json_output: bool,
+    /// Run what's already in target/ without building
+    pub no_build: bool,
+}
+
+/// One `zora run`, as saved by `--record` and checked by `--replay`.
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+    /// The `--name` the program was built with, if any
+    name: Option<String>,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+    cwd: PathBuf,
+    /// Missing when the program timed out or was killed by a signal
+    exit_code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+/// Environment variables whose names contain one of these are left out of
+/// recordings, which tend to get attached to bug reports.
+const SECRET_ENV_MARKERS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "KEY", "CREDENTIAL"];
+
+/// Output lines shown per stream when a replay doesn't match.
+const MAX_DIFF_LINES: usize = 20;
+
+/// Exit code when `--timeout` kills the program, as with coreutils `timeout`.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+impl RunOptions {
+    fn slot(&self) -> &str {
+        self.build_dir.as_deref().unwrap_or(&self.mode)
+    }
+}
+
+pub fn run(mut opts: RunOptions) -> Result<()> {
+    // A replay runs the recorded program, so load it before building
+    let recording = match &opts.replay {
+        Some(path) => {
+            let recording = load_recording(path)?;
+            opts.name = recording.name.clone();
+            Some(recording)
+        }
+        None => None,
+    };
+
+    if opts.no_build {
+        ensure_built(&opts)?;
+    } else {
+        build(&opts)?;
+    }
+
+    if opts.all {
+        return run_all(&opts);
+    }
 
     // Get the executable path
-    let exe_path = super::build::get_executable_path(name_opt, mode)?;
+    let exe_path = super::build::get_executable_path(opts.name.clone(), opts.slot())?;
 
     if !exe_path.exists() {
         bail!("Executable not found at: {}", exe_path.display());
     }
 
+    if let Some(recording) = recording {
+        return replay(&exe_path, &recording, opts.timeout);
+    }
+    if let Some(path) = &opts.record {
+        return record(&exe_path, &opts, path);
+    }
+    if opts.json_output {
+        return run_unframed(&exe_path, &opts);
+    }
+
     println!("\n{} {}...\n", "Running".bright_blue(), exe_path.display());
     println!("{}", "─".repeat(50).dimmed());
 
     // Run the executable with any provided arguments
-    let status = Command::new(&exe_path)
-        .args(&args)
-        .status()
+    let status = run_program(&exe_path, &opts.args, opts.timeout)
         .context("failed to run executable")?;
 
     println!("{}", "─".repeat(50).dimmed());
-    
+
+    let Some(status) = status else {
+        eprintln!("{} Program timed out after {} and was killed",
+            "✗".red().bold(),
+            format_duration(opts.timeout.unwrap_or_default())
+        );
+        std::process::exit(TIMEOUT_EXIT_CODE);
+    };
+
     if !status.success() {
         let code = status.code().unwrap_or(-1);
         bail!("Program exited with error code: {}", code);
@@ -39,3 +132,349 @@ pub fn run(
     println!("\n{} Program completed successfully", "✓".green().bold());
     Ok(())
 }
+
+/// Builds the project unless the last build is still current. The banner
+/// only shows with `--verbose`; the build's own summary shows either way.
+fn build(opts: &RunOptions) -> Result<()> {
+    let build_opts = BuildOptions {
+        name: opts.name.clone(),
+        // Tool output and command lines would land in the JSON stream
+        verbosity: if opts.json_output { Verbosity::default() } else { opts.verbosity },
+        jobs: opts.jobs,
+        build_dir: opts.build_dir.clone(),
+        quiet: opts.json_output,
+        ..BuildOptions::new(&opts.mode)
+    };
+
+    let show = opts.verbosity.commands() && !opts.json_output;
+    if super::build::is_up_to_date(&build_opts)? {
+        if show {
+            println!("  {} build (nothing changed)", "Skipped".dimmed());
+        }
+        return Ok(());
+    }
+
+    if show {
+        println!("{}", "Building project...".bright_cyan());
+    }
+    super::build::run(build_opts)
+}
+
+/// With `--no-build`, the executables to run have to be in target/ already.
+fn ensure_built(opts: &RunOptions) -> Result<()> {
+    let config = ProjectConfig::load()?;
+    let names = if opts.all {
+        config.executables()
+    } else {
+        vec![opts.name.clone().unwrap_or_else(|| config.name.clone())]
+    };
+
+    for name in names {
+        let exe_path = super::build::get_executable_path(Some(name.clone()), opts.slot())?;
+        if !exe_path.exists() {
+            bail!("{} has not been built ({} not found). Run without --no-build.", name, exe_path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Runs the program with nothing of zora's around its output, so stdout can
+/// be piped into a parser, and exits with the program's exit code.
+fn run_unframed(exe: &Path, opts: &RunOptions) -> Result<()> {
+    let status = run_program(exe, &opts.args, opts.timeout)
+        .context("failed to run executable")?;
+
+    match status {
+        Some(status) if status.success() => Ok(()),
+        Some(status) => std::process::exit(status.code().unwrap_or(1)),
+        None => {
+            eprintln!("{} Program timed out after {} and was killed",
+                "✗".red().bold(),
+                format_duration(opts.timeout.unwrap_or_default())
+            );
+            std::process::exit(TIMEOUT_EXIT_CODE);
+        }
+    }
+}
+
+fn run_all(opts: &RunOptions) -> Result<()> {
+    let config = ProjectConfig::load()?;
+    let executables = config.executables();
+
+    if executables.is_empty() {
+        bail!("No executables to run. Add a [[bin]] target to project.toml.");
+    }
+
+    let mut results = vec![];
+
+    for name in &executables {
+        let exe_path = super::build::get_executable_path(Some(name.clone()), opts.slot())?;
+
+        println!("\n{} {}...\n", "Running".bright_blue(), exe_path.display());
+        println!("{}", "─".repeat(50).dimmed());
+
+        let code = if exe_path.exists() {
+            let status = run_program(&exe_path, &opts.args, opts.timeout)
+                .with_context(|| format!("failed to run {}", name))?;
+            match status {
+                Some(status) => status.code().unwrap_or(-1),
+                None => {
+                    println!("Timed out after {}", format_duration(opts.timeout.unwrap_or_default()));
+                    TIMEOUT_EXIT_CODE
+                }
+            }
+        } else {
+            println!("Executable not found at: {}", exe_path.display());
+            -1
+        };
+
+        println!("{}", "─".repeat(50).dimmed());
+        results.push((name, code));
+
+        if code != 0 && !opts.keep_going {
+            break;
+        }
+    }
+
+    println!("\n{}", "Results".bright_cyan());
+    for (name, code) in &results {
+        if *code == 0 {
+            println!("  {} {}", "✓".green(), name);
+        } else {
+            println!("  {} {} (exit code {})", "✗".red(), name, code);
+        }
+    }
+
+    let failed = results.iter().filter(|(_, code)| *code != 0).count();
+    let skipped = executables.len() - results.len();
+
+    if failed > 0 {
+        if skipped > 0 {
+            println!("  {} {} not run", "-".dimmed(), skipped);
+        }
+        bail!("{} of {} program(s) failed", failed, executables.len());
+    }
+
+    println!("\n{} All {} program(s) completed successfully", "✓".green().bold(), results.len());
+    Ok(())
+}
+
+/// Runs the program with its output captured and saves the invocation,
+/// then passes the output through.
+fn record(exe: &Path, opts: &RunOptions, path: &Path) -> Result<()> {
+    let env: BTreeMap<String, String> = std::env::vars()
+        .filter(|(name, _)| {
+            let upper = name.to_uppercase();
+            !SECRET_ENV_MARKERS.iter().any(|marker| upper.contains(marker))
+        })
+        .collect();
+    let cwd = std::env::current_dir()?;
+
+    println!("\n{} {} (recording)...\n", "Running".bright_blue(), exe.display());
+
+    let mut cmd = Command::new(exe);
+    cmd.args(&opts.args);
+    let (status, stdout, stderr) = run_captured(&mut cmd, opts.timeout)
+        .context("failed to run executable")?;
+
+    print!("{}", stdout);
+    eprint!("{}", stderr);
+
+    let recording = Recording {
+        name: opts.name.clone(),
+        args: opts.args.clone(),
+        env,
+        cwd,
+        exit_code: status.and_then(|status| status.code()),
+        stdout,
+        stderr,
+    };
+    fs::write(path, serde_json::to_string_pretty(&recording)?)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+
+    println!("\n{} Recorded to {} (exit code {})",
+        "✓".green().bold(),
+        path.display(),
+        exit_code_label(recording.exit_code)
+    );
+    Ok(())
+}
+
+/// Re-runs a recorded invocation with the same args, environment and working
+/// directory, and reports where the exit code or output differ.
+fn replay(exe: &Path, recording: &Recording, timeout: Option<Duration>) -> Result<()> {
+    println!("\n{} {} (replaying)...", "Running".bright_blue(), exe.display());
+
+    // The recorded directory may differ from this one
+    let mut cmd = Command::new(std::env::current_dir()?.join(exe));
+    cmd.args(&recording.args)
+        .env_clear()
+        .envs(&recording.env);
+    if recording.cwd.is_dir() {
+        cmd.current_dir(&recording.cwd);
+    } else {
+        println!("{} recorded directory {} doesn't exist; using the current one",
+            "Warning:".yellow(),
+            recording.cwd.display()
+        );
+    }
+
+    let (status, stdout, stderr) = run_captured(&mut cmd, timeout)
+        .context("failed to run executable")?;
+    let exit_code = status.and_then(|status| status.code());
+
+    let mut mismatches = 0;
+    if exit_code != recording.exit_code {
+        mismatches += 1;
+        println!("\n{} exit code: expected {}, got {}",
+            "✗".red(),
+            exit_code_label(recording.exit_code),
+            exit_code_label(exit_code)
+        );
+    }
+    for (stream, expected, actual) in [
+        ("stdout", &recording.stdout, &stdout),
+        ("stderr", &recording.stderr, &stderr),
+    ] {
+        if expected != actual {
+            mismatches += 1;
+            print_diff(stream, expected, actual);
+        }
+    }
+
+    if mismatches > 0 {
+        println!();
+        bail!("Replay differs from the recording in {} place(s)", mismatches);
+    }
+
+    println!("\n{} Output matches the recording", "✓".green().bold());
+    Ok(())
+}
+
+fn load_recording(path: &Path) -> Result<Recording> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a zora run recording", path.display()))
+}
+
+/// Line-by-line comparison of one output stream.
+fn print_diff(stream: &str, expected: &str, actual: &str) {
+    println!("\n{} {} differs", "✗".red(), stream);
+
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut shown = 0;
+
+    for line in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(line), actual.get(line));
+        if old == new {
+            continue;
+        }
+        if shown == MAX_DIFF_LINES {
+            println!("  {}", "...".dimmed());
+            break;
+        }
+        shown += 1;
+
+        println!("  {}", format!("line {}:", line + 1).dimmed());
+        if let Some(old) = old {
+            println!("  {}", format!("- {}", old).red());
+        }
+        if let Some(new) = new {
+            println!("  {}", format!("+ {}", new).green());
+        }
+    }
+
+    // Only a trailing newline differs
+    if shown == 0 {
+        println!("  {}", "(line endings differ)".dimmed());
+    }
+}
+
+fn exit_code_label(code: Option<i32>) -> String {
+    code.map(|code| code.to_string()).unwrap_or_else(|| "none (killed)".to_string())
+}
+
+/// Runs a program to completion, or kills it once `timeout` elapses and
+/// returns `None`.
+fn run_program(exe: &Path, args: &[String], timeout: Option<Duration>) -> Result<Option<ExitStatus>> {
+    let mut child = Command::new(exe).args(args).spawn()?;
+    wait(&mut child, timeout)
+}
+
+/// Like `run_program`, but captures stdout and stderr instead of passing
+/// them through.
+fn run_captured(cmd: &mut Command, timeout: Option<Duration>) -> Result<(Option<ExitStatus>, String, String)> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    // Drain both pipes while waiting so a chatty program can't block on a full one
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+    let status = wait(&mut child, timeout)?;
+
+    let collect = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader.and_then(|handle| handle.join().ok())
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    };
+    Ok((status, collect(stdout), collect(stderr)))
+}
+
+fn read_in_background(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        pipe.read_to_end(&mut bytes).ok();
+        bytes
+    })
+}
+
+/// Waits for the child, killing it once `timeout` elapses (then `None`).
+fn wait(child: &mut Child, timeout: Option<Duration>) -> Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return Ok(Some(child.wait()?));
+    };
+
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if started.elapsed() >= timeout {
+            child.kill().ok();
+            child.wait()?;
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+/// Parses `--timeout` values: `500ms`, `30s`, `2m`, `1h`, or plain seconds.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: f64 = number.parse()
+        .map_err(|_| format!("invalid duration '{}'", value))?;
+    let seconds = match unit {
+        "" | "s" => number,
+        "ms" => number / 1000.0,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => return Err(format!("invalid duration unit '{}'. Use ms, s, m or h", unit)),
+    };
+
+    Duration::try_from_secs_f64(seconds)
+        .map_err(|_| format!("duration '{}' is out of range", value))
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
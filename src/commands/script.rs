@@ -1,34 +1,113 @@
 
 use anyhow::{bail, Result};
 use colored::Colorize;
+use std::path::Path;
 use std::process::Command;
 use crate::config::ProjectConfig;
 
-pub fn run(name: String) -> Result<()> {
+/// Runs a script from `[scripts]` with `args` as its positional parameters,
+/// or lists them all when no name is given.
+pub fn run(name: Option<String>, args: Vec<String>) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found");
     }
     
     let config = ProjectConfig::load()?;
+    let mut names: Vec<&String> = config.scripts.keys().collect();
+    names.sort();
+
+    let Some(name) = name else {
+        if !args.is_empty() {
+            bail!("Arguments after -- need a script name");
+        }
+        if names.is_empty() {
+            println!("No scripts defined in [scripts]");
+            return Ok(());
+        }
+
+        println!("{}", "Available scripts:".bright_cyan());
+        let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+        for name in names {
+            println!("  {:width$}  {}", name.bright_yellow(), config.scripts[name].command().dimmed(), width = width);
+        }
+        return Ok(());
+    };
     
     if let Some(script) = config.scripts.get(&name) {
         println!("{} Running script: {}", "→".bright_blue(), name);
         
-        let status = if cfg!(windows) {
-            Command::new("cmd").args(&["/C", script]).status()?
-        } else {
-            Command::new("sh").args(&["-c", script]).status()?
-        };
+        let mut cmd = script_command(&name, script.command(), &args);
+        if let Some(env) = script.env() {
+            cmd.envs(env);
+        }
+        if let Some(cwd) = script.cwd() {
+            if !Path::new(cwd).is_dir() {
+                bail!("Script '{}' runs in {}, which is not a directory", name, cwd);
+            }
+            cmd.current_dir(cwd);
+        }
+
+        let status = cmd.status()?;
         
         if !status.success() {
             bail!("Script failed");
         }
         
         println!("{} Script completed", "✓".green().bold());
+    } else if names.is_empty() {
+        bail!("Script '{}' not found in project.toml (no [scripts] defined)", name);
     } else {
-        bail!("Script '{}' not found in project.toml", name);
+        let available: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        bail!("Script '{}' not found in project.toml. Available: {}", name, available.join(", "));
     }
     
     Ok(())
 }
 
+/// Builds a command that runs `script` through the platform shell.
+pub fn shell_command(script: &str) -> Command {
+    let mut cmd = if cfg!(windows) {
+        Command::new("cmd")
+    } else {
+        Command::new("sh")
+    };
+    cmd.args([if cfg!(windows) { "/C" } else { "-c" }, script]);
+    cmd
+}
+
+/// Like `shell_command`, with `args` as the script's `$1`, `$2`, ... (`sh -c`
+/// takes `$0` first, which is the script's name). cmd has no positional
+/// parameters, so on Windows they're appended to the command instead.
+fn script_command(name: &str, script: &str, args: &[String]) -> Command {
+    if cfg!(windows) {
+        let mut line = script.to_string();
+        for arg in args {
+            if arg.is_empty() || arg.contains([' ', '\t', '"']) {
+                line.push_str(&format!(" \"{}\"", arg.replace('"', "\\\"")));
+            } else {
+                line.push_str(&format!(" {}", arg));
+            }
+        }
+        return shell_command(&line);
+    }
+
+    let mut cmd = shell_command(script);
+    cmd.arg(name).args(args);
+    cmd
+}
+
+/// Runs a lifecycle hook script from project.toml with extra environment
+/// variables, failing if the script does.
+pub fn run_hook(name: &str, script: &str, envs: &[(&str, &str)]) -> Result<()> {
+    println!("{} Running {} hook", "→".bright_blue(), name);
+
+    let status = shell_command(script)
+        .envs(envs.iter().copied())
+        .status()?;
+
+    if !status.success() {
+        bail!("{} hook failed", name);
+    }
+
+    Ok(())
+}
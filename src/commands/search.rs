@@ -6,7 +6,7 @@ pub fn run(query: String) -> Result<()> {
     println!("{} {}", "Searching vcpkg for".bright_cyan(), query.bright_yellow());
     
     Command::new("vcpkg")
-        .args(&["search", &query])
+        .args(["search", &query])
         .status()?;
     
     Ok(())
@@ -0,0 +1,175 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use glob::Pattern;
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use tera::{Context as TeraContext, Tera};
+use walkdir::WalkDir;
+
+const TEMPLATE_MANIFEST: &str = "zora.template.toml";
+
+/// Files with this extension are rendered and written without it.
+const TERA_EXTENSION: &str = "tera";
+
+/// Optional `zora.template.toml` at the root of a template repository.
+#[derive(Debug, Deserialize, Default)]
+struct TemplateManifest {
+    /// Glob patterns of files to run through Tera, besides `*.tera` files.
+    /// Everything else is copied verbatim, since C++ sources may well
+    /// contain `{{` or `{%`.
+    #[serde(default)]
+    render: Vec<String>,
+    #[serde(default)]
+    prompts: Vec<TemplatePrompt>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TemplatePrompt {
+    name: String,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    default: String,
+}
+
+/// Bootstraps the current directory from a remote template repository.
+pub fn init_from(url: &str, project_name: &str) -> Result<()> {
+    println!("{} {}...", "Fetching template".bright_cyan(), url);
+
+    let checkout = std::env::temp_dir().join(format!("zora-template-{}", std::process::id()));
+    if checkout.exists() {
+        fs::remove_dir_all(&checkout)?;
+    }
+
+    let status = Command::new("git")
+        .args(["clone", "--depth", "1", url])
+        .arg(&checkout)
+        .status()
+        .context("failed to run git. Is git installed?")?;
+
+    if !status.success() {
+        bail!("Failed to clone template: {}", url);
+    }
+
+    let result = render_template(&checkout, project_name);
+    fs::remove_dir_all(&checkout).ok();
+    result
+}
+
+fn render_template(checkout: &Path, project_name: &str) -> Result<()> {
+    fs::remove_dir_all(checkout.join(".git")).ok();
+
+    let manifest_path = checkout.join(TEMPLATE_MANIFEST);
+    let manifest: TemplateManifest = if manifest_path.exists() {
+        let content = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", TEMPLATE_MANIFEST))?;
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", TEMPLATE_MANIFEST))?
+    } else {
+        TemplateManifest::default()
+    };
+
+    let render_patterns = manifest.render.iter()
+        .map(|p| Pattern::new(p).with_context(|| format!("invalid render pattern: {}", p)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut ctx = TeraContext::new();
+    ctx.insert("name", project_name);
+    ctx.insert("name_upper", &project_name.to_uppercase().replace('-', "_"));
+    ctx.insert("author", &git_author().unwrap_or_default());
+
+    for prompt in &manifest.prompts {
+        let answer = ask(prompt)?;
+        ctx.insert(prompt.name.as_str(), &answer);
+    }
+
+    for entry in WalkDir::new(checkout)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        let source = path.strip_prefix(checkout)?;
+        if source == Path::new(TEMPLATE_MANIFEST) {
+            continue;
+        }
+
+        let is_tera = source.extension().is_some_and(|ext| ext == TERA_EXTENSION);
+        let should_render = is_tera || render_patterns.iter().any(|p| p.matches_path(source));
+        let relative = if is_tera { source.with_extension("") } else { source.to_path_buf() };
+        let relative = relative.as_path();
+
+        if relative.exists() {
+            println!("  {} {} (already exists)", "Skipped".yellow(), relative.display());
+            continue;
+        }
+
+        if let Some(parent) = relative.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        if should_render {
+            match fs::read_to_string(path) {
+                Ok(content) => {
+                    let rendered = Tera::one_off(&content, &ctx, false)
+                        .with_context(|| format!("failed to render {}", relative.display()))?;
+                    fs::write(relative, rendered)?;
+                }
+                // Binary files can't be rendered; copy them verbatim
+                Err(_) => {
+                    fs::copy(path, relative)?;
+                }
+            }
+        } else {
+            fs::copy(path, relative)?;
+        }
+
+        println!("  {} {}", "Created".green(), relative.display());
+    }
+
+    if !Path::new("project.toml").exists() {
+        println!("{}", "Warning: template did not provide a project.toml".yellow());
+    }
+
+    println!("\n{} Initialized project from template: {}",
+        "✓".green().bold(),
+        project_name.bright_yellow()
+    );
+
+    Ok(())
+}
+
+fn ask(prompt: &TemplatePrompt) -> Result<String> {
+    let message = prompt.message.as_deref().unwrap_or(&prompt.name);
+    if prompt.default.is_empty() {
+        print!("{}: ", message);
+    } else {
+        print!("{} [{}]: ", message, prompt.default);
+    }
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+
+    Ok(if answer.is_empty() { prompt.default.clone() } else { answer.to_string() })
+}
+
+fn git_author() -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
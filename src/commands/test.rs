@@ -1,64 +1,142 @@
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::Instant;
 use walkdir::WalkDir;
 
+use crate::commands::build::{self, ArtifactKind, BuildMode};
 use crate::config::ProjectConfig;
+use crate::paths;
 
-pub fn run(mode: &str, specific_test: Option<String>) -> Result<()> {
+/// Marker for the tag comment in a test file, e.g. `// zora-tags: slow, network`.
+const TAGS_MARKER: &str = "zora-tags:";
+
+/// Options for `zora test`, as collected from the CLI.
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+    pub mode: BuildMode,
+    /// Only test files whose name contains this
+    pub test: Option<String>,
+    /// Only test files carrying at least one of these tags
+    pub tags: Vec<String>,
+    /// Skip test files carrying any of these tags
+    pub skip_tags: Vec<String>,
+    /// List the matching tests instead of running them
+    pub list: bool,
+    /// Report slower and newly failing tests from the run history
+    pub trend: bool,
+    /// Show test output even when the test passes
+    pub nocapture: bool,
+    /// Compile the tests without running them
+    pub compile_only: bool,
+    /// Result format: "human", "tap" (TAP v13 on stdout) or "junit"
+    /// (`target/<mode>/tests/results.xml`)
+    pub format: String,
+}
+
+/// One `zora test` run, as persisted to `[tests] history_dir`.
+#[derive(Debug, Serialize, Deserialize)]
+struct RunRecord {
+    timestamp: String,
+    mode: String,
+    passed: usize,
+    failed: usize,
+    tests: Vec<TestRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TestRecord {
+    name: String,
+    passed: bool,
+    /// Missing when the test didn't run or the framework doesn't report it
+    duration_ms: Option<u64>,
+}
+
+/// A test counts as slower once it's this much above its usual duration...
+const SLOWDOWN_RATIO: f64 = 1.25;
+/// ...and at least this many milliseconds, so timer noise doesn't register.
+const SLOWDOWN_MIN_MS: u64 = 5;
+
+pub fn run(opts: TestOptions) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
     let config = ProjectConfig::load()?;
-    
-    println!("{}", "Running tests...".bright_cyan());
+    let mode = opts.mode.as_str();
 
-    // Find test files
-    let test_dirs = &config.tests.dirs;
-    let mut test_files = vec![];
+    if opts.trend {
+        return trend(&config);
+    }
 
-    for test_dir in test_dirs {
-        if !Path::new(test_dir).exists() {
-            continue;
-        }
+    if !matches!(opts.format.as_str(), "human" | "tap" | "junit") {
+        bail!("Unknown test format '{}'. Use \"human\", \"tap\" or \"junit\"", opts.format);
+    }
+    // TAP owns stdout, so zora's own progress output is left out
+    let human = opts.format != "tap";
 
-        for entry in WalkDir::new(test_dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "c" || ext == "cpp" {
-                        if let Some(test_name) = &specific_test {
-                            if path.file_stem()
-                                .and_then(|s| s.to_str())
-                                .map(|s| s.contains(test_name))
-                                .unwrap_or(false)
-                            {
-                                test_files.push(path.to_path_buf());
-                            }
-                        } else {
-                            test_files.push(path.to_path_buf());
-                        }
-                    }
-                }
+    let test_files: Vec<PathBuf> = find_test_files(&config, opts.test.as_deref())
+        .into_iter()
+        .filter(|path| {
+            let tags = test_tags(path);
+            (opts.tags.is_empty() || opts.tags.iter().any(|t| tags.contains(t)))
+                && !opts.skip_tags.iter().any(|t| tags.contains(t))
+        })
+        .collect();
+
+    if opts.list {
+        for path in &test_files {
+            let tags = test_tags(path);
+            if tags.is_empty() {
+                println!("{}", path.display());
+            } else {
+                println!("{} {}", path.display(), format!("[{}]", tags.join(", ")).dimmed());
             }
         }
+        return Ok(());
+    }
+
+    if human {
+        let action = if opts.compile_only { "Compiling tests..." } else { "Running tests..." };
+        println!("{}", action.bright_cyan());
     }
 
     if test_files.is_empty() {
-        println!("{}", "No test files found".yellow());
+        if human {
+            println!("{}", "No test files found".yellow());
+        } else {
+            report(&config, &opts, &[], &HashMap::new())?;
+        }
         return Ok(());
     }
 
-    println!("Found {} test file(s)", test_files.len());
+    if human {
+        println!("Found {} test file(s)", test_files.len());
+    }
+
+    let compiler = config.compiler();
+    let stdlib_flags = config.stdlib_flags(&compiler)?;
+    crate::vcpkg::install_dev_deps(&config)?;
+    let dev_dep_flags = crate::vcpkg::package_flags(&config.dev_vcpkg_packages());
+    let build_flags = build_flags(&config, mode);
+
+    if config.tests.mode == "harness" {
+        return run_harness(&config, &opts, &test_files, &compiler, &stdlib_flags, &dev_dep_flags, &build_flags);
+    } else if config.tests.mode != "per-file" {
+        bail!("Unknown test mode '{}'. Use \"per-file\" or \"harness\"", config.tests.mode);
+    }
+
+    let project_code = project_code(&config, mode)?;
 
     let mut passed = 0;
     let mut failed = 0;
+    let mut results = vec![];
+    // Test name -> what it printed, for failures in TAP and JUnit reports
+    let mut failures = HashMap::new();
 
     for test_file in test_files {
         let test_name = test_file
@@ -66,55 +144,118 @@ pub fn run(mode: &str, specific_test: Option<String>) -> Result<()> {
             .and_then(|s| s.to_str())
             .unwrap_or("unknown");
 
-        println!("\n{} {}...", "Testing".bright_blue(), test_name);
+        let action = if opts.compile_only { "Compiling" } else { "Testing" };
+        if human {
+            println!("\n{} {}...", action.bright_blue(), test_name);
+        }
 
         // Compile test
-        let output_dir = format!("target/{}/tests", mode);
+        let output_dir = paths::target_root().join(mode).join("tests");
         fs::create_dir_all(&output_dir)?;
 
-        let output_file = format!("{}/{}", output_dir, test_name);
-        let compiler = if config.is_cpp() { "g++" } else { "gcc" };
-
-        let mut cmd = Command::new(compiler);
-        cmd.arg(&test_file)
+        let output_file = output_dir.join(test_name);
+        let mut cmd = Command::new(&compiler);
+        cmd.args(config.language_flags())
+            .args(config.dialect_flags())
+            .args(config.include_flags())
+            .args(&build_flags)
+            .arg(format!("-O{}", config.opt_level(mode)))
+            .arg(&test_file)
+            .args(&project_code)
             .arg("-o")
             .arg(&output_file)
-            .arg("-I")
-            .arg("include");
+            .args(&stdlib_flags)
+            .args(config.framework_flags());
 
-        // Add optimization flags
-        if mode == "release" {
-            cmd.arg("-O2");
+        for lib in &config.merged_libs(mode) {
+            cmd.arg(format!("-l{}", lib));
         }
+        cmd.args(&dev_dep_flags);
 
         let compile_status = cmd.status()
             .context("failed to compile test")?;
 
         if !compile_status.success() {
-            println!("  {} Compilation failed", "✗".red().bold());
+            if human {
+                println!("  {} Compilation failed", "✗".red().bold());
+            }
             failed += 1;
+            results.push(TestRecord { name: test_name.to_string(), passed: false, duration_ms: None });
+            failures.insert(test_name.to_string(), "Compilation failed".to_string());
             continue;
         }
 
-        // Run test
-        let test_status = Command::new(&output_file)
-            .status()
-            .context("failed to run test")?;
+        if opts.compile_only {
+            if human {
+                println!("  {} Compiled to {}", "✓".green().bold(), output_file.display());
+            }
+            passed += 1;
+            continue;
+        }
+
+        // Run test, holding its output back unless it fails
+        let started = Instant::now();
+        let mut test_cmd = Command::new(&output_file);
+        let mut captured = None;
+        let test_status = if opts.nocapture && human {
+            test_cmd.status().context("failed to run test")?
+        } else {
+            let output = test_cmd.output().context("failed to run test")?;
+            if !output.status.success() {
+                failures.insert(test_name.to_string(), failure_message(&output));
+            }
+            let status = output.status;
+            captured = Some(output);
+            status
+        };
+
+        results.push(TestRecord {
+            name: test_name.to_string(),
+            passed: test_status.success(),
+            duration_ms: Some(started.elapsed().as_millis() as u64),
+        });
 
         if test_status.success() {
-            println!("  {} {}", "✓".green().bold(), "PASSED".green());
+            if human {
+                println!("  {} {}", "✓".green().bold(), "PASSED".green());
+            }
             passed += 1;
         } else {
-            println!("  {} {}", "✗".red().bold(), "FAILED".red());
+            if human {
+                println!("  {} {}", "✗".red().bold(), "FAILED".red());
+                if let Some(output) = &captured {
+                    print_indented(output);
+                }
+            }
             failed += 1;
         }
     }
 
-    println!("\n{}", "─".repeat(40));
-    println!("Test results: {} passed, {} failed", 
-        passed.to_string().green(), 
-        failed.to_string().red()
-    );
+    if human {
+        println!("\n{}", "─".repeat(40));
+    }
+    if opts.compile_only {
+        if human {
+            println!("Compile results: {} compiled, {} failed",
+                passed.to_string().green(),
+                failed.to_string().red()
+            );
+        }
+        if failed > 0 {
+            bail!("Some tests failed to compile");
+        }
+        return Ok(());
+    }
+
+    if human {
+        println!("Test results: {} passed, {} failed",
+            passed.to_string().green(),
+            failed.to_string().red()
+        );
+    }
+
+    report(&config, &opts, &results, &failures)?;
+    record_history(&config, mode, passed, failed, results)?;
 
     if failed > 0 {
         bail!("Some tests failed");
@@ -122,3 +263,533 @@ pub fn run(mode: &str, specific_test: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+/// `[build]` and profile flags and defines, so tests compile the way the
+/// project does.
+fn build_flags(config: &ProjectConfig, mode: &str) -> Vec<String> {
+    let mut flags = config.merged_flags(mode, false);
+    flags.extend(config.merged_defines(mode).into_iter()
+        .map(|(key, value)| format!("-D{}={}", key, value)));
+    flags
+}
+
+/// What each per-file test links to reach the code under test: for a library,
+/// the static library in .build/<mode> when it's newer than every source;
+/// otherwise the project's non-main sources, compiled alongside the test.
+fn project_code(config: &ProjectConfig, mode: &str) -> Result<Vec<PathBuf>> {
+    let sources = config.library_sources(&config.enabled_features(&[])?)?;
+
+    let library = build::build_dir_artifact(config, None, mode, build::build_type(mode))
+        .filter(|artifact| config.is_library() && artifact.kind == ArtifactKind::StaticLib);
+    if let Some(library) = library {
+        let built = fs::metadata(&library.path).and_then(|m| m.modified()).ok();
+        let stale = built.is_none() || sources.iter().any(|source| {
+            fs::metadata(source).and_then(|m| m.modified()).ok() > built
+        });
+        if !stale {
+            return Ok(vec![library.path]);
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Compiles every test file together with the project sources into a single
+/// executable and runs it once, as gtest/Catch2 suites expect.
+fn run_harness(
+    config: &ProjectConfig,
+    opts: &TestOptions,
+    test_files: &[PathBuf],
+    compiler: &str,
+    stdlib_flags: &[String],
+    dev_dep_flags: &[String],
+    build_flags: &[String],
+) -> Result<()> {
+    let mode = opts.mode.as_str();
+    let output_dir = paths::target_root().join(mode).join("tests");
+    fs::create_dir_all(&output_dir)?;
+    let output_file = output_dir.join("harness");
+    let human = opts.format != "tap";
+
+    if human {
+        println!("\n{} test harness...", "Compiling".bright_blue());
+    }
+
+    let mut cmd = Command::new(compiler);
+    cmd.args(config.language_flags())
+        .args(config.dialect_flags())
+        .args(test_files)
+        .args(config.library_sources(&config.enabled_features(&[])?)?)
+        .arg("-o")
+        .arg(&output_file)
+        .args(stdlib_flags)
+        .args(config.framework_flags());
+
+    cmd.args(config.include_flags())
+        .args(build_flags);
+
+    cmd.arg(format!("-O{}", config.opt_level(mode)));
+
+    for lib in &config.merged_libs(mode) {
+        cmd.arg(format!("-l{}", lib));
+    }
+    cmd.args(dev_dep_flags);
+
+    let compile_status = cmd.status()
+        .context("failed to compile test harness")?;
+
+    if !compile_status.success() {
+        bail!("Test harness compilation failed");
+    }
+
+    if opts.compile_only {
+        if human {
+            println!("{} Compiled test harness to {}", "✓".green().bold(), output_file.display());
+        }
+        return Ok(());
+    }
+
+    let output = Command::new(&output_file)
+        .output()
+        .context("failed to run test harness")?;
+
+    // The output is parsed for results, so it's printed afterwards rather than streamed
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (passed, failed) = parse_framework_output(&stdout);
+    if human && (opts.nocapture || !output.status.success() || !failed.is_empty()) {
+        print_output(&output);
+    }
+    let durations = gtest_durations(&stdout);
+
+    if human {
+        println!("\n{}", "─".repeat(40));
+    }
+    let summary = catch2_summary(&stdout);
+    match (passed.is_empty() && failed.is_empty(), summary) {
+        _ if !human => {}
+        (false, _) => {
+            for name in &passed {
+                println!("  {} {}", "✓".green(), name);
+            }
+            for name in &failed {
+                println!("  {} {}", "✗".red(), name);
+            }
+            println!("Test results: {} passed, {} failed",
+                passed.len().to_string().green(),
+                failed.len().to_string().red()
+            );
+        }
+        (true, Some((passed, failed))) => {
+            println!("Test results: {} passed, {} failed",
+                passed.to_string().green(),
+                failed.to_string().red()
+            );
+        }
+        (true, None) => {}
+    }
+
+    // Catch2 only reports counts, so its runs are recorded without per-test entries
+    let (passed_count, failed_count) = match summary {
+        Some(counts) if passed.is_empty() && failed.is_empty() => counts,
+        _ => (passed.len(), failed.len()),
+    };
+    let results: Vec<TestRecord> = passed.iter().map(|name| (name, true))
+        .chain(failed.iter().map(|name| (name, false)))
+        .map(|(name, ok)| TestRecord {
+            name: name.clone(),
+            passed: ok,
+            duration_ms: durations.get(name).copied(),
+        })
+        .collect();
+
+    // The harness's output can't be split per case, so each failure carries all of it
+    let message = failure_message(&output);
+    if results.is_empty() {
+        // Without case names the whole harness is reported as one test
+        let harness = TestRecord {
+            name: "harness".to_string(),
+            passed: output.status.success(),
+            duration_ms: None,
+        };
+        let failures = HashMap::from_iter((!harness.passed).then(|| (harness.name.clone(), message)));
+        report(config, opts, &[harness], &failures)?;
+    } else {
+        let failures = failed.iter().map(|name| (name.clone(), message.clone())).collect();
+        report(config, opts, &results, &failures)?;
+    }
+    record_history(config, mode, passed_count, failed_count, results)?;
+
+    if !output.status.success() || !failed.is_empty() {
+        bail!("Some tests failed");
+    }
+
+    if human {
+        println!("{} Test harness passed", "✓".green().bold());
+    }
+    Ok(())
+}
+
+/// What a failed test printed, for TAP and JUnit reports: its stderr, or its
+/// stdout when stderr is empty.
+fn failure_message(output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let message = if stderr.trim().is_empty() {
+        String::from_utf8_lossy(&output.stdout)
+    } else {
+        stderr
+    };
+    match output.status.code() {
+        Some(code) => format!("exit code {}\n{}", code, message.trim_end()),
+        None => format!("killed by a signal\n{}", message.trim_end()),
+    }
+}
+
+/// Emits the `--format` report: TAP v13 on stdout, or a JUnit XML file under
+/// target/<mode>/tests. Human output is printed as the tests run instead.
+fn report(
+    config: &ProjectConfig,
+    opts: &TestOptions,
+    results: &[TestRecord],
+    failures: &HashMap<String, String>,
+) -> Result<()> {
+    match opts.format.as_str() {
+        "tap" => {
+            println!("TAP version 13");
+            println!("1..{}", results.len());
+            for (number, result) in results.iter().enumerate() {
+                let status = if result.passed { "ok" } else { "not ok" };
+                println!("{} {} - {}", status, number + 1, result.name);
+                if let Some(message) = failures.get(&result.name) {
+                    println!("  ---");
+                    println!("  message: |");
+                    for line in message.lines() {
+                        println!("    {}", line);
+                    }
+                    println!("  ...");
+                }
+            }
+        }
+        "junit" => {
+            let path = paths::target_root().join(opts.mode.as_str()).join("tests").join("results.xml");
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, junit_xml(&config.name, results, failures))
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            println!("{} JUnit report written to {}", "→".bright_blue(), path.display());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// A JUnit XML report with one `<testcase>` per test.
+fn junit_xml(suite: &str, results: &[TestRecord], failures: &HashMap<String, String>) -> String {
+    let seconds = |result: &TestRecord| result.duration_ms.unwrap_or(0) as f64 / 1000.0;
+    let total: f64 = results.iter().map(seconds).sum();
+    let failed = results.iter().filter(|result| !result.passed).count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites>\n");
+    xml.push_str(&format!("  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+        xml_escape(suite), results.len(), failed, total));
+
+    for result in results {
+        xml.push_str(&format!("    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            xml_escape(&result.name), xml_escape(suite), seconds(result)));
+        if result.passed {
+            xml.push_str("/>\n");
+            continue;
+        }
+
+        let message = failures.get(&result.name).map(String::as_str).unwrap_or("");
+        let summary = message.lines().next().unwrap_or("failed");
+        xml.push_str(&format!(">\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+            xml_escape(summary), xml_escape(message)));
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Replays a test's captured stdout and stderr.
+fn print_output(output: &Output) {
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+}
+
+/// Replays a failed test's captured stdout and stderr under its result line.
+fn print_indented(output: &Output) {
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        println!("    {}", line);
+    }
+    for line in String::from_utf8_lossy(&output.stderr).lines() {
+        eprintln!("    {}", line);
+    }
+}
+
+/// Extracts passing and failing case names from gtest-style output.
+fn parse_framework_output(stdout: &str) -> (Vec<String>, Vec<String>) {
+    let mut passed = vec![];
+    let mut failed: Vec<String> = vec![];
+
+    for line in stdout.lines() {
+        let case_name = |rest: &str| rest.split_whitespace().next().map(|s| s.to_string());
+
+        if let Some(rest) = line.strip_prefix("[       OK ]") {
+            passed.extend(case_name(rest));
+        } else if let Some(rest) = line.strip_prefix("[  FAILED  ]") {
+            // gtest repeats failures in its summary, and prints a count line
+            if let Some(name) = case_name(rest) {
+                if name.contains('.') && !failed.contains(&name) {
+                    failed.push(name);
+                }
+            }
+        }
+    }
+
+    (passed, failed)
+}
+
+/// Per-case durations from gtest's `[       OK ] Suite.Case (12 ms)` lines.
+fn gtest_durations(stdout: &str) -> HashMap<String, u64> {
+    stdout.lines()
+        .filter_map(|line| line.strip_prefix("[       OK ]").or_else(|| line.strip_prefix("[  FAILED  ]")))
+        .filter_map(|rest| {
+            let (name, time) = rest.trim().split_once(" (")?;
+            let ms = time.strip_suffix(" ms)")?.parse().ok()?;
+            Some((name.to_string(), ms))
+        })
+        .collect()
+}
+
+/// Reads Catch2's summary line, which reports counts rather than case names.
+fn catch2_summary(stdout: &str) -> Option<(usize, usize)> {
+    for line in stdout.lines() {
+        if let Some(rest) = line.strip_prefix("All tests passed") {
+            // "All tests passed (12 assertions in 3 test cases)"
+            let cases = rest.split(" in ").nth(1)?
+                .split_whitespace().next()?
+                .parse().ok()?;
+            return Some((cases, 0));
+        }
+
+        if let Some(rest) = line.strip_prefix("test cases:") {
+            // "test cases: 5 | 4 passed | 1 failed"
+            let mut passed = 0;
+            let mut failed = 0;
+            for part in rest.split('|') {
+                let mut words = part.split_whitespace();
+                let count = words.next().and_then(|n| n.parse().ok());
+                match (count, words.next()) {
+                    (Some(n), Some("passed")) => passed = n,
+                    (Some(n), Some("failed")) => failed = n,
+                    _ => {}
+                }
+            }
+            return Some((passed, failed));
+        }
+    }
+
+    None
+}
+
+/// Test sources under the test dirs, optionally only those whose file stem
+/// contains `filter`.
+pub fn find_test_files(config: &ProjectConfig, filter: Option<&str>) -> Vec<PathBuf> {
+    let mut test_files = vec![];
+
+    for test_dir in &config.tests.dirs {
+        if !Path::new(test_dir).exists() {
+            continue;
+        }
+
+        for entry in WalkDir::new(test_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if path.is_file() {
+                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    if config.source_extensions().contains(&ext) {
+                        if let Some(test_name) = filter {
+                            if path.file_stem()
+                                .and_then(|s| s.to_str())
+                                .map(|s| s.contains(test_name))
+                                .unwrap_or(false)
+                            {
+                                test_files.push(path.to_path_buf());
+                            }
+                        } else {
+                            test_files.push(path.to_path_buf());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    test_files
+}
+
+/// Tags declared in a test file with a `zora-tags:` comment.
+pub fn test_tags(path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return vec![];
+    };
+
+    content.lines()
+        .filter_map(|line| line.split_once(TAGS_MARKER).map(|(_, tags)| tags))
+        .flat_map(|tags| tags.split(','))
+        .map(|tag| tag.trim().trim_end_matches("*/").trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Appends this run to `[tests] history_dir`, if configured.
+fn record_history(
+    config: &ProjectConfig,
+    mode: &str,
+    passed: usize,
+    failed: usize,
+    tests: Vec<TestRecord>,
+) -> Result<()> {
+    let Some(dir) = &config.tests.history_dir else {
+        return Ok(());
+    };
+
+    fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create test history dir {}", dir))?;
+
+    let now = chrono::Utc::now();
+    let record = RunRecord {
+        timestamp: now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        mode: mode.to_string(),
+        passed,
+        failed,
+        tests,
+    };
+
+    // Sortable file names keep the history in run order
+    let path = Path::new(dir).join(format!("{}.json", now.format("%Y%m%dT%H%M%S%.3fZ")));
+    fs::write(&path, serde_json::to_string_pretty(&record)?)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+
+    Ok(())
+}
+
+/// Compares the latest recorded run against the earlier ones: tests that
+/// started failing, got slower than their median, or keep flipping.
+fn trend(config: &ProjectConfig) -> Result<()> {
+    let Some(dir) = &config.tests.history_dir else {
+        bail!("No test history. Set [tests] history_dir in project.toml and run 'zora test'.");
+    };
+
+    let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+            .collect(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+
+    let mut runs = vec![];
+    for path in &paths {
+        let content = fs::read_to_string(path)?;
+        let run: RunRecord = serde_json::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        runs.push(run);
+    }
+
+    let Some((latest, previous)) = runs.split_last() else {
+        println!("{}", "No test runs recorded yet".yellow());
+        return Ok(());
+    };
+
+    println!("\n{} ({} run(s) recorded)", "Test Trend".bright_cyan().bold(), runs.len());
+    println!("{}", "─".repeat(40));
+    println!("Latest: {} [{}] {} passed, {} failed",
+        latest.timestamp, latest.mode,
+        latest.passed.to_string().green(),
+        latest.failed.to_string().red()
+    );
+
+    if previous.is_empty() {
+        println!("\n{}", "Only one run recorded; nothing to compare against yet".yellow());
+        return Ok(());
+    }
+
+    let mut newly_failing = vec![];
+    let mut slower = vec![];
+    let mut flaky = vec![];
+
+    for test in &latest.tests {
+        let history: Vec<&TestRecord> = previous.iter()
+            .filter_map(|run| run.tests.iter().find(|t| t.name == test.name))
+            .collect();
+
+        if !test.passed && history.last().is_some_and(|last| last.passed) {
+            newly_failing.push(test.name.clone());
+        }
+
+        let results: Vec<bool> = history.iter().map(|t| t.passed).chain([test.passed]).collect();
+        let flips = results.windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .count();
+        if flips >= 2 {
+            flaky.push((test.name.clone(), flips));
+        }
+
+        let mut durations: Vec<u64> = history.iter()
+            .filter(|t| t.passed)
+            .filter_map(|t| t.duration_ms)
+            .collect();
+        if let (Some(current), false) = (test.duration_ms, durations.is_empty()) {
+            durations.sort();
+            let median = durations[durations.len() / 2];
+            if current as f64 > median as f64 * SLOWDOWN_RATIO && current - median >= SLOWDOWN_MIN_MS {
+                slower.push((test.name.clone(), median, current));
+            }
+        }
+    }
+
+    if !newly_failing.is_empty() {
+        println!("\n{}", "Newly failing".red().bold());
+        for name in &newly_failing {
+            println!("  {} {}", "✗".red(), name);
+        }
+    }
+
+    if !slower.is_empty() {
+        println!("\n{}", "Slower".yellow().bold());
+        for (name, median, current) in &slower {
+            println!("  {} {}: {} ms → {} ms (+{:.0}%)",
+                "↑".yellow(), name, median, current,
+                (*current as f64 / *median.max(&1) as f64 - 1.0) * 100.0
+            );
+        }
+    }
+
+    if !flaky.is_empty() {
+        println!("\n{}", "Flaky".bright_magenta().bold());
+        for (name, flips) in &flaky {
+            println!("  {} {} (changed result {} times)", "~".bright_magenta(), name, flips);
+        }
+    }
+
+    if newly_failing.is_empty() && slower.is_empty() && flaky.is_empty() {
+        println!("\n{} No regressions compared to previous runs", "✓".green().bold());
+    }
+
+    println!();
+    Ok(())
+}
@@ -1,39 +1,192 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use crate::config::ProjectConfig;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-pub fn run(depth: Option<usize>) -> Result<()> {
+use crate::config::{DependencySpec, ProjectConfig};
+
+type Deps = HashMap<String, DependencySpec>;
+
+/// One place a dependency appears in the transitive graph.
+struct Occurrence {
+    /// Names from the root project down to the dependency
+    chain: Vec<String>,
+    /// The version asked for, or the checkout for a path dependency
+    version: String,
+}
+
+pub fn run(depth: Option<usize>, duplicates: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found");
     }
-    
+
     let config = ProjectConfig::load()?;
+    let root = Path::new(".");
+
+    if duplicates {
+        return print_duplicates(&config);
+    }
+
     let max_depth = depth.unwrap_or(usize::MAX);
-    
+
     println!("{} v{}", config.name.bright_yellow(), config.version);
-    
-    fn print_deps(deps: &std::collections::HashMap<String, crate::config::DependencySpec>, 
-                  prefix: &str, depth: usize, max_depth: usize) {
-        if depth >= max_depth {
-            return;
+    print_deps(&config.deps, root, "", 0, max_depth, &mut vec![])?;
+
+    if !config.dev_deps.is_empty() {
+        println!("\n{}", "[dev-dependencies]".bright_cyan());
+        print_deps(&config.dev_deps, root, "", 0, max_depth, &mut vec![])?;
+    }
+
+    Ok(())
+}
+
+/// Prints the dependencies declared by the project in `dir` and, below each
+/// path dependency that's a zora project, its own dependencies.
+fn print_deps(
+    deps: &Deps,
+    dir: &Path,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    stack: &mut Vec<PathBuf>,
+) -> Result<()> {
+    if depth >= max_depth {
+        return Ok(());
+    }
+
+    let names = sorted_names(deps);
+    for (i, name) in names.iter().enumerate() {
+        let spec = &deps[*name];
+        let is_last = i == names.len() - 1;
+        let connector = if is_last { "└──" } else { "├──" };
+
+        println!("{}{} {} {}", prefix, connector, name, version_label(dir, spec));
+
+        if let Some((dep_dir, dep)) = load_path_dep(dir, spec, stack)? {
+            let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+            stack.push(dep_dir.canonicalize()?);
+            print_deps(&dep.deps, &dep_dir, &child_prefix, depth + 1, max_depth, stack)?;
+            stack.pop();
         }
-        
-        let count = deps.len();
-        for (i, (name, spec)) in deps.iter().enumerate() {
-            let is_last = i == count - 1;
-            let connector = if is_last { "└──" } else { "├──" };
-            let version = spec.version();
-            
-            println!("{}{} {} v{}", prefix, connector, name, version);
+    }
+
+    Ok(())
+}
+
+/// Lists packages that appear more than once in the transitive graph, with
+/// the chain of dependencies that pulls in each occurrence, and flags those
+/// asked for at different versions.
+fn print_duplicates(config: &ProjectConfig) -> Result<()> {
+    let root = Path::new(".");
+    let mut occurrences = BTreeMap::new();
+    let chain = vec![config.name.clone()];
+    collect(&config.deps, root, &chain, &mut vec![], &mut occurrences)?;
+
+    let dev_chain = vec![format!("{} (dev)", config.name)];
+    collect(&config.dev_deps, root, &dev_chain, &mut vec![], &mut occurrences)?;
+
+    let duplicates: Vec<(&String, &Vec<Occurrence>)> = occurrences.iter()
+        .filter(|(_, found)| found.len() > 1)
+        .collect();
+
+    if duplicates.is_empty() {
+        println!("{} No duplicate dependencies", "✓".green().bold());
+        return Ok(());
+    }
+
+    let mut conflicts = 0;
+    for (name, found) in &duplicates {
+        let mut versions: Vec<&str> = found.iter()
+            .map(|occurrence| occurrence.version.as_str())
+            .filter(|version| *version != "*")
+            .collect();
+        versions.sort();
+        versions.dedup();
+
+        if versions.len() > 1 {
+            conflicts += 1;
+            println!("{} ({} occurrences, {})", name.bright_yellow(), found.len(), "version conflict".red());
+        } else {
+            println!("{} ({} occurrences)", name.bright_yellow(), found.len());
+        }
+
+        let width = found.iter().map(|occurrence| occurrence.version.len()).max().unwrap_or(0);
+        for occurrence in found.iter() {
+            println!("  {:width$}  {}", occurrence.version, occurrence.chain.join(" → ").dimmed(), width = width);
         }
     }
-    
-    print_deps(&config.deps, "", 0, max_depth);
-    
-    if !config.dev_deps.is_empty() {
-        println!("\n{}", "[dev-dependencies]".bright_cyan());
-        print_deps(&config.dev_deps, "", 0, max_depth);
+
+    println!("\n{} duplicate(s), {} with conflicting versions", duplicates.len(), conflicts);
+    Ok(())
+}
+
+fn collect(
+    deps: &Deps,
+    dir: &Path,
+    chain: &[String],
+    stack: &mut Vec<PathBuf>,
+    occurrences: &mut BTreeMap<String, Vec<Occurrence>>,
+) -> Result<()> {
+    for name in sorted_names(deps) {
+        let spec = &deps[name];
+        let mut dep_chain = chain.to_vec();
+        dep_chain.push(name.clone());
+
+        occurrences.entry(name.clone()).or_default().push(Occurrence {
+            chain: dep_chain.clone(),
+            version: version_label(dir, spec),
+        });
+
+        if let Some((dep_dir, dep)) = load_path_dep(dir, spec, stack)? {
+            stack.push(dep_dir.canonicalize()?);
+            collect(&dep.deps, &dep_dir, &dep_chain, stack, occurrences)?;
+            stack.pop();
+        }
     }
-    
+
     Ok(())
 }
+
+fn sorted_names(deps: &Deps) -> Vec<&String> {
+    let mut names: Vec<&String> = deps.keys().collect();
+    names.sort();
+    names
+}
+
+/// `v1.2.3`, `*` for any version, or a path dependency's checkout relative
+/// to the root project, so the same checkout reached two ways compares equal.
+fn version_label(dir: &Path, spec: &DependencySpec) -> String {
+    if let Some(path) = spec.path() {
+        let dep_dir = dir.join(path);
+        let resolved = match (dep_dir.canonicalize(), Path::new(".").canonicalize()) {
+            (Ok(dep), Ok(root)) => dep.strip_prefix(&root).map(Path::to_path_buf).unwrap_or(dep),
+            _ => dep_dir,
+        };
+        return format!("({})", resolved.display());
+    }
+
+    match spec.version().trim() {
+        "" | "*" => "*".to_string(),
+        version => format!("v{}", version),
+    }
+}
+
+/// The project.toml of a path dependency, if it's a zora project and not
+/// already being walked (dependency cycles are cut off there).
+fn load_path_dep(dir: &Path, spec: &DependencySpec, stack: &[PathBuf]) -> Result<Option<(PathBuf, ProjectConfig)>> {
+    let Some(path) = spec.path() else {
+        return Ok(None);
+    };
+
+    let dep_dir = dir.join(path);
+    if !dep_dir.join("project.toml").exists() {
+        return Ok(None);
+    }
+    if dep_dir.canonicalize().is_ok_and(|canonical| stack.contains(&canonical)) {
+        return Ok(None);
+    }
+
+    let dep = ProjectConfig::load_from(&dep_dir)
+        .with_context(|| format!("failed to load path dependency {}", dep_dir.display()))?;
+    Ok(Some((dep_dir, dep)))
+}
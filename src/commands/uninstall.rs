@@ -1,10 +1,11 @@
 use anyhow::{bail, Result};
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
+use super::install::install_root;
+use super::script::run_hook;
 use crate::config::ProjectConfig;
 
-pub fn run(prefix: Option<String>) -> Result<()> {
+pub fn run(prefix: Option<String>, destdir: Option<String>) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found");
     }
@@ -18,7 +19,7 @@ pub fn run(prefix: Option<String>) -> Result<()> {
         }
     });
     
-    let bin_dir = PathBuf::from(&install_prefix).join("bin");
+    let bin_dir = install_root(&install_prefix, destdir.as_deref()).join("bin");
     let exe_name = if cfg!(windows) {
         format!("{}.exe", config.name)
     } else {
@@ -28,7 +29,22 @@ pub fn run(prefix: Option<String>) -> Result<()> {
     let exe_path = bin_dir.join(&exe_name);
     
     if exe_path.exists() {
+        if let Some(script) = &config.uninstall.pre_uninstall {
+            if destdir.is_some() {
+                println!("  {} pre_uninstall hook (staging uninstall)", "Skipped".yellow());
+            } else {
+                run_hook("pre_uninstall", script, &[("ZORA_INSTALL_PREFIX", &install_prefix)])?;
+            }
+        }
+
         fs::remove_file(&exe_path)?;
+
+        let debug_file = super::build::debug_info_path(&exe_path);
+        if debug_file.is_dir() {
+            fs::remove_dir_all(&debug_file)?;
+        } else if debug_file.exists() {
+            fs::remove_file(&debug_file)?;
+        }
         println!("{} Uninstalled {}", "✓".green().bold(), exe_path.display());
     } else {
         println!("{}", "Not installed".yellow());
@@ -1,14 +1,56 @@
 // src/commands/update.rs
 use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::path::Path;
 use std::process::Command;
 
-pub fn run(packages: Vec<String>) -> Result<()> {
-    if !Path::new("project.toml").exists() {
+use crate::config::ProjectConfig;
+
+/// A package `vcpkg upgrade` would rebuild, from its plan output.
+struct PlannedUpgrade {
+    /// `name:triplet`
+    spec: String,
+    from: String,
+    to: String,
+}
+
+pub fn run(packages: Vec<String>, dry_run: bool) -> Result<()> {
+    if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
+    let config = ProjectConfig::load()?;
+
+    // Path deps checked out as submodules update through git, not vcpkg
+    let submodules = crate::git::submodule_paths();
+    let submodule_deps: Vec<(String, String)> = config.path_deps()
+        .into_iter()
+        .filter(|(name, path)| {
+            submodules.contains(path) && (packages.is_empty() || packages.contains(name))
+        })
+        .collect();
+
+    if !submodule_deps.is_empty() && dry_run {
+        println!("{}", "Submodules that would be updated:".bright_cyan());
+        for (name, path) in &submodule_deps {
+            println!("  {} {} ({})", "→".bright_blue(), name, path);
+        }
+    } else if !submodule_deps.is_empty() {
+        println!("{}", "Updating submodules...".bright_cyan());
+        let paths: Vec<String> = submodule_deps.iter().map(|(_, path)| path.clone()).collect();
+        crate::git::submodule_update(&paths, true, true)?;
+        for (name, _) in &submodule_deps {
+            println!("  {} Updated {}", "✓".green(), name);
+        }
+    }
+
+    let packages: Vec<String> = packages.into_iter()
+        .filter(|p| !submodule_deps.iter().any(|(name, _)| name == p))
+        .collect();
+
+    if packages.is_empty() && !submodule_deps.is_empty() && config.vcpkg_packages().is_empty() {
+        return Ok(());
+    }
+
     let vcpkg_check = Command::new("vcpkg")
         .arg("version")
         .output();
@@ -17,6 +59,12 @@ pub fn run(packages: Vec<String>) -> Result<()> {
         bail!("vcpkg not found. Please install vcpkg.");
     }
 
+    crate::vcpkg::ensure_root();
+
+    if dry_run {
+        return preview(&packages);
+    }
+
     println!("{}", "Updating packages...".bright_cyan());
 
     if packages.is_empty() {
@@ -36,7 +84,7 @@ pub fn run(packages: Vec<String>) -> Result<()> {
             println!("  {} Updating {}...", "→".bright_blue(), package);
             
             let status = Command::new("vcpkg")
-                .args(&["upgrade", package, "--no-dry-run"])
+                .args(["upgrade", package, "--no-dry-run"])
                 .status()
                 .context(format!("failed to update package: {}", package))?;
 
@@ -46,5 +94,66 @@ pub fn run(packages: Vec<String>) -> Result<()> {
         }
     }
 
+    if crate::lockfile::update(&config)? {
+        println!("{} Updated {}", "✓".green().bold(), crate::lockfile::LOCK_FILE);
+    }
+
     Ok(())
 }
+
+/// Lists what `vcpkg upgrade` would change without applying it; vcpkg only
+/// plans unless given `--no-dry-run`.
+fn preview(packages: &[String]) -> Result<()> {
+    println!("{}", "Checking for package updates...".bright_cyan());
+
+    let output = Command::new("vcpkg")
+        .arg("upgrade")
+        .args(packages)
+        .output()
+        .context("failed to run vcpkg upgrade")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let upgrades = parse_upgrade_plan(&stdout);
+
+    if upgrades.is_empty() {
+        if !output.status.success() {
+            bail!("vcpkg upgrade failed:\n{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        println!("{} All packages are up to date", "✓".green().bold());
+        return Ok(());
+    }
+
+    let width = upgrades.iter().map(|u| u.spec.len()).max().unwrap_or(0);
+    for upgrade in &upgrades {
+        println!("  {:width$}  {} {} {}",
+            upgrade.spec,
+            upgrade.from.dimmed(),
+            "→".bright_blue(),
+            upgrade.to.green(),
+            width = width
+        );
+    }
+
+    println!("\n{} package(s) would be updated. Run {} to apply.",
+        upgrades.len(),
+        "zora update".bright_white()
+    );
+    Ok(())
+}
+
+/// Picks the `name:triplet  1.0 -> 1.1` lines out of vcpkg's upgrade plan.
+fn parse_upgrade_plan(stdout: &str) -> Vec<PlannedUpgrade> {
+    stdout.lines()
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words.as_slice() {
+                [spec, from, "->", to] if spec.contains(':') => Some(PlannedUpgrade {
+                    spec: spec.to_string(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
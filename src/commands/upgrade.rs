@@ -0,0 +1,99 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use super::init::{managed_files, record_state, STATE_DIR};
+use crate::config::ProjectConfig;
+
+/// Refreshes zora-managed boilerplate from the current templates, merging
+/// against the originally generated copy so local edits are kept.
+pub fn run(force: bool) -> Result<()> {
+    if !ProjectConfig::exists() {
+        bail!("project.toml not found. Run 'zora init' first.");
+    }
+
+    println!("{}", "Upgrading project boilerplate...".bright_cyan());
+
+    let mut conflicts = 0;
+
+    for (file, new) in managed_files()? {
+        let base_path = Path::new(STATE_DIR).join(file.trim_start_matches('.'));
+        let base = fs::read_to_string(&base_path).ok();
+        let current = fs::read_to_string(file).ok();
+
+        match (current, base) {
+            (None, _) => {
+                fs::write(file, &new)?;
+                println!("  {} {}", "Created".green(), file);
+            }
+            (Some(current), _) if current == new => {
+                println!("  {} {}", "Up to date".dimmed(), file);
+            }
+            (Some(_), Some(base)) if base == new => {
+                // Template unchanged; whatever differs is the user's edit
+                println!("  {} {}", "Up to date".dimmed(), file);
+            }
+            (Some(current), Some(base)) => {
+                let (merged, clean) = merge(&current, &base, &new)?;
+                fs::write(file, merged)?;
+                if clean {
+                    println!("  {} {}", "Updated".green(), file);
+                } else {
+                    println!("  {} {} (resolve the conflict markers)", "Conflict".red(), file);
+                    conflicts += 1;
+                }
+            }
+            (Some(_), None) if force => {
+                fs::write(file, &new)?;
+                println!("  {} {}", "Replaced".yellow(), file);
+            }
+            (Some(_), None) => {
+                println!("  {} {} (no recorded original to merge against; use --force to replace)",
+                    "Skipped".yellow(),
+                    file
+                );
+                continue;
+            }
+        }
+
+        record_state(file, &new)?;
+    }
+
+    if conflicts > 0 {
+        bail!("{} file(s) have merge conflicts", conflicts);
+    }
+
+    println!("{} Boilerplate is current", "✓".green().bold());
+    Ok(())
+}
+
+/// Three-way merges `current` and `new` against `base` with `git merge-file`.
+/// Returns the merged text and whether it merged without conflicts.
+fn merge(current: &str, base: &str, new: &str) -> Result<(String, bool)> {
+    let dir = Path::new(STATE_DIR).join("merge");
+    fs::create_dir_all(&dir)?;
+
+    let paths = [dir.join("current"), dir.join("base"), dir.join("new")];
+    for (path, content) in paths.iter().zip([current, base, new]) {
+        fs::write(path, content)?;
+    }
+
+    let output = Command::new("git")
+        .args(["merge-file", "-p", "-L", "yours", "-L", "original", "-L", "zora"])
+        .args(&paths)
+        .output()
+        .context("failed to run git merge-file. Is git installed?");
+
+    fs::remove_dir_all(&dir).ok();
+    let output = output?;
+
+    // Exit status is the number of conflicts, or negative on error
+    match output.status.code() {
+        Some(code) if code >= 0 => {
+            Ok((String::from_utf8_lossy(&output.stdout).into_owned(), code == 0))
+        }
+        _ => bail!("git merge-file failed: {}", String::from_utf8_lossy(&output.stderr)),
+    }
+}
@@ -2,9 +2,10 @@
 use anyhow::{bail, Result};
 use colored::Colorize;
 use crate::config::ProjectConfig;
+use crate::lockfile::{Lockfile, LOCK_FILE};
 use std::path::Path;
 
-pub fn run(locked: bool) -> Result<()> {
+pub fn run(locked: bool, deps: bool, fix: bool, strict_deps: bool) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found");
     }
@@ -14,10 +15,26 @@ pub fn run(locked: bool) -> Result<()> {
     let config = ProjectConfig::load()?;
     
     // Check lock file
-    if locked && !Path::new("project.lock").exists() {
-        bail!("project.lock not found. Run without --locked or generate lock file");
+    if locked && !Path::new(LOCK_FILE).exists() {
+        bail!("{} not found. Run 'zora build' to generate it", LOCK_FILE);
+    }
+
+    // Check installed dependencies match the lock file, and with --deps
+    // that the lock file matches project.toml
+    if locked {
+        let drift = lock_drift(&config, &Lockfile::load()?, deps);
+        if !drift.is_empty() {
+            for line in &drift {
+                println!("  {}", line);
+            }
+            bail!("{} dependency difference(s) from {}", drift.len(), LOCK_FILE);
+        }
+        println!("  {} Dependencies match {}", "✓".green(), LOCK_FILE);
     }
     
+    // Check dependencies have explicit versions
+    config.check_strict_deps(strict_deps)?;
+
     // Check source files exist
     for dir in &config.sources.dirs {
         if !Path::new(dir).exists() {
@@ -26,13 +43,82 @@ pub fn run(locked: bool) -> Result<()> {
     }
     
     // Check include files exist
-    for dir in &config.includes.dirs {
+    for dir in config.include_dirs().iter().chain(&config.includes.system) {
         if !Path::new(dir).exists() {
             bail!("Include directory not found: {}", dir);
         }
     }
     
+    // Check path dependencies are checked out
+    let uninitialized = crate::git::uninitialized_submodules();
+    let mut to_init = vec![];
+    for (name, path) in config.path_deps() {
+        if uninitialized.contains(&path) {
+            to_init.push(path);
+        } else if !Path::new(&path).exists() {
+            bail!("Path dependency '{}' not found: {}", name, path);
+        }
+    }
+
+    if !to_init.is_empty() {
+        if !fix {
+            bail!("Submodule(s) not initialized: {}. Run 'zora verify --fix'", to_init.join(", "));
+        }
+
+        println!("  {} Initializing {}", "→".bright_blue(), to_init.join(", "));
+        crate::git::submodule_update(&to_init, true, false)?;
+    }
+
     println!("{} Project verified", "✓".green().bold());
     Ok(())
 }
 
+
+/// How the installed vcpkg packages differ from project.lock, and with
+/// `check_manifest` how project.toml's dependencies do, one line per
+/// difference.
+fn lock_drift(config: &ProjectConfig, lock: &Lockfile, check_manifest: bool) -> Vec<String> {
+    let installed = crate::vcpkg::installed_versions();
+    let mut drift = vec![];
+
+    if check_manifest {
+        let mut wanted: Vec<(&String, &str)> = config.deps.iter()
+            .chain(&config.dev_deps)
+            .filter(|(_, spec)| spec.path().is_none())
+            .map(|(name, spec)| (name, spec.version().trim()))
+            .collect();
+        wanted.sort();
+        wanted.dedup_by_key(|(name, _)| *name);
+
+        for (name, version) in &wanted {
+            match lock.packages.get(*name) {
+                None => drift.push(format!("{} {}: in project.toml but not locked", "+".green(), name)),
+                Some(locked) if !matches!(*version, "" | "*") && *version != locked.version => {
+                    drift.push(format!("{} {}: project.toml wants {}, locked at {}",
+                        "~".yellow(), name, version, locked.version));
+                }
+                Some(_) => {}
+            }
+        }
+
+        for name in lock.packages.keys() {
+            if !wanted.iter().any(|(wanted, _)| *wanted == name) {
+                drift.push(format!("{} {}: locked but no longer in project.toml", "-".red(), name));
+            }
+        }
+    }
+
+    for (name, locked) in &lock.packages {
+        // vcpkg's install records drop the port version (`1.2.3#1`)
+        let locked_version = locked.version.split('#').next().unwrap_or(&locked.version);
+        match installed.get(name) {
+            None => drift.push(format!("{} {}: locked at {}, not installed",
+                "!".red(), name, locked.version)),
+            Some(version) if version != locked_version => drift.push(format!("{} {}: locked at {}, installed {}",
+                "!".red(), name, locked.version, version)),
+            Some(_) => {}
+        }
+    }
+
+    drift
+}
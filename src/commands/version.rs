@@ -8,14 +8,14 @@ pub fn run(verbose: bool) -> Result<()> {
     
     if verbose {
         println!("{} {}", "zora".bright_cyan().bold(), version);
-        println!("{}: {}", "commit-hash".dimmed(), "unknown");
+        println!("{}: unknown", "commit-hash".dimmed());
         
         if ProjectConfig::exists() {
             let config = ProjectConfig::load()?;
             println!("\n{}", "Project:".bright_cyan());
-            println!("  {}: {}", "name", config.name);
-            println!("  {}: {}", "version", config.version);
-            println!("  {}: {}", "type", config.r#type);
+            println!("  name: {}", config.name);
+            println!("  version: {}", config.version);
+            println!("  type: {}", config.r#type);
         }
     } else {
         println!("zora {}", version);
@@ -1,68 +1,187 @@
 use anyhow::{bail, Result};
 use colored::Colorize;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::thread;
 use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
 
+use super::script::shell_command;
 use crate::config::ProjectConfig;
 
-pub fn run(command: &str) -> Result<()> {
+/// How often the source tree is scanned for changes without `--poll`. The
+/// watcher always polls, so it also sees changes on network filesystems and
+/// container mounts that don't deliver change events.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long the tree has to stay unchanged after a change before rebuilding,
+/// so a save that touches several files (or one file several times) only
+/// triggers one rebuild.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub fn run(
+    command: &str,
+    test_on_save: bool,
+    on_success: Option<String>,
+    on_failure: Option<String>,
+    poll: Option<Duration>,
+) -> Result<()> {
     if !ProjectConfig::exists() {
         bail!("project.toml not found. Run 'zora init' first.");
     }
 
+    if !matches!(command, "build" | "test" | "run") {
+        bail!("Unknown command '{}'. Use \"build\", \"test\" or \"run\"", command);
+    }
+
+    let interval = poll.unwrap_or(DEFAULT_POLL_INTERVAL);
+
     println!("{}", "Watching for changes...".bright_cyan());
     println!("Press Ctrl+C to stop\n");
 
     let config = ProjectConfig::load()?;
-    let mut last_modified = SystemTime::now();
+
+    let mut watch_paths: Vec<PathBuf> = config.sources.dirs.iter()
+        .chain(&config.includes.dirs)
+        .chain(&config.includes.system)
+        .map(PathBuf::from)
+        .collect();
+    watch_paths.push(PathBuf::from("project.toml"));
+    // Test runs also react to edits in the tests themselves
+    if test_on_save || command == "test" {
+        watch_paths.extend(config.tests.dirs.iter().map(PathBuf::from));
+    }
+
+    let mut last_scan = scan(&watch_paths);
 
     loop {
-        let mut should_rebuild = false;
-
-        // Check source files
-        for source_dir in &config.sources.dirs {
-            if let Ok(entries) = std::fs::read_dir(source_dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    if let Ok(metadata) = entry.metadata() {
-                        if let Ok(modified) = metadata.modified() {
-                            if modified > last_modified {
-                                should_rebuild = true;
-                                last_modified = SystemTime::now();
-                                break;
-                            }
+        thread::sleep(interval);
+
+        let mut current = scan(&watch_paths);
+        let changed_file = match first_change(&last_scan, &current) {
+            Some(mut path) => {
+                // Wait for the burst of writes to settle
+                loop {
+                    thread::sleep(DEBOUNCE);
+                    let settled = scan(&watch_paths);
+                    match first_change(&current, &settled) {
+                        Some(newer_path) => {
+                            path = newer_path;
+                            current = settled;
                         }
+                        None => break,
                     }
                 }
+                last_scan = current;
+                Some(path)
             }
-        }
+            None => None,
+        };
 
-        if should_rebuild {
-            println!("\n{} Change detected, rebuilding...", "→".bright_blue());
-            
-            let result = match command {
-                "build" => Command::new("zora").arg("build").status(),
-                "test" => Command::new("zora").arg("test").status(),
-                "run" => Command::new("zora").arg("run").status(),
-                _ => {
-                    println!("Unknown command: {}", command);
-                    continue;
-                }
+        if let Some(changed) = changed_file {
+            let succeeded = if test_on_save {
+                run_affected_tests(&config, &changed)
+            } else {
+                println!("\n{} Change detected, rebuilding...", "→".bright_blue());
+
+                matches!(zora().arg(command).status(), Ok(status) if status.success())
             };
 
-            match result {
-                Ok(status) if status.success() => {
-                    println!("{} Build succeeded", "✓".green().bold());
-                }
-                _ => {
-                    println!("{} Build failed", "✗".red().bold());
+            let what = if test_on_save { "Tests" } else { "Build" };
+            if succeeded {
+                println!("{} {} succeeded", "✓".green().bold(), what);
+            } else {
+                println!("{} {} failed", "✗".red().bold(), what);
+            }
+
+            let hook = if succeeded { &on_success } else { &on_failure };
+            if let Some(hook) = hook {
+                let status = shell_command(hook)
+                    .env("ZORA_WATCH_FILE", &changed)
+                    .env("ZORA_WATCH_STATUS", if succeeded { "success" } else { "failure" })
+                    .env("ZORA_WATCH_COMMAND", if test_on_save { "test" } else { command })
+                    .status();
+
+                if !matches!(status, Ok(s) if s.success()) {
+                    println!("{} Hook failed: {}", "⚠".yellow(), hook);
                 }
             }
             
             println!("\n{}", "Watching for changes...".bright_cyan());
         }
+    }
+}
+
+/// Every file under `paths` (walked recursively) with its modification time.
+fn scan(paths: &[PathBuf]) -> BTreeMap<PathBuf, SystemTime> {
+    paths.iter()
+        .flat_map(|path| WalkDir::new(path).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((entry.into_path(), modified))
+        })
+        .collect()
+}
+
+/// A file added, removed or with a different mtime between two scans. Any
+/// mtime change counts, so a file copied in with an older one is noticed.
+fn first_change(before: &BTreeMap<PathBuf, SystemTime>, after: &BTreeMap<PathBuf, SystemTime>) -> Option<PathBuf> {
+    after.iter()
+        .find(|(path, modified)| before.get(*path) != Some(*modified))
+        .map(|(path, _)| path.clone())
+        .or_else(|| before.keys().find(|path| !after.contains_key(*path)).cloned())
+}
+
+/// Invokes this same zora binary, falling back to `zora` on PATH.
+fn zora() -> Command {
+    Command::new(std::env::current_exe().unwrap_or_else(|_| PathBuf::from("zora")))
+}
+
+/// Tests affected by a change, or `None` when the whole suite should run.
+/// `src/foo.c` maps to `tests/test_foo.c` (or `foo_test.c`); an edited test
+/// maps to itself; a header may be included anywhere, so it runs everything.
+fn affected_tests(config: &ProjectConfig, changed: &Path) -> Option<Vec<String>> {
+    let is_header = changed.extension()
+        .map(|ext| ["h", "hh", "hpp", "hxx"].iter().any(|h| ext == *h))
+        .unwrap_or(false);
+    if is_header {
+        return None;
+    }
+
+    let stem = changed.file_stem()?.to_str()?;
+    let test_stems: Vec<String> = super::test::find_test_files(config, None)
+        .iter()
+        .filter_map(|path| path.file_stem()?.to_str().map(|s| s.to_string()))
+        .collect();
 
-        thread::sleep(Duration::from_secs(1));
+    if test_stems.iter().any(|t| t == stem) {
+        return Some(vec![stem.to_string()]);
+    }
+
+    let candidates = [format!("test_{}", stem), format!("{}_test", stem)];
+    Some(test_stems.into_iter().filter(|t| candidates.contains(t)).collect())
+}
+
+fn run_affected_tests(config: &ProjectConfig, changed: &Path) -> bool {
+    match affected_tests(config, changed) {
+        None => {
+            println!("\n{} {} changed, running all tests...", "→".bright_blue(), changed.display());
+            matches!(zora().arg("test").status(), Ok(status) if status.success())
+        }
+        Some(tests) if tests.is_empty() => {
+            println!("\n{} No tests match {}", "→".bright_blue(), changed.display());
+            true
+        }
+        Some(tests) => {
+            println!("\n{} {} changed, running {}...", "→".bright_blue(), changed.display(), tests.join(", "));
+            let mut succeeded = true;
+            for test in &tests {
+                let status = zora().args(["test", "--test", test]).status();
+                succeeded &= matches!(status, Ok(status) if status.success());
+            }
+            succeeded
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,365 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use crate::commands::build::{self, BuildOptions};
+use crate::config::{ProjectConfig, WorkspaceConfig};
+use crate::paths;
+
+/// Adds an empty `[workspace]` section to project.toml, creating a
+/// workspace-only project.toml if there isn't one, with every subdirectory
+/// that holds a project.toml as a member.
 pub fn init() -> Result<()> {
-    println!("{}", "Workspace support coming soon!".yellow());
-    bail!("Workspace commands are not yet implemented");
+    let content = if Path::new("project.toml").exists() {
+        fs::read_to_string("project.toml").context("failed to read project.toml")?
+    } else {
+        String::new()
+    };
+
+    if workspace_section(&content).is_some() {
+        bail!("project.toml already has a [workspace] section");
+    }
+
+    let mut members = vec![];
+    for entry in fs::read_dir(".")?.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() && path.join("project.toml").exists() {
+            members.push(normalize(&path.to_string_lossy()));
+        }
+    }
+    members.sort();
+
+    let workspace = WorkspaceConfig { members, exclude: vec![] };
+    fs::write("project.toml", write_workspace(&content, &workspace))
+        .context("failed to write project.toml")?;
+
+    println!("{} Created workspace with {} member(s)", "✓".green().bold(), workspace.members.len());
+    for member in &workspace.members {
+        println!("  {} {}", "→".bright_blue(), member);
+    }
+    Ok(())
 }
 
-pub fn add(_path: String) -> Result<()> {
-    println!("{}", "Workspace support coming soon!".yellow());
-    bail!("Workspace commands are not yet implemented");
+/// Appends a directory holding a project.toml to `[workspace] members`.
+pub fn add(path: String) -> Result<()> {
+    let (content, mut workspace) = read_workspace()?;
+    let member = normalize(&path);
+
+    if member.is_empty() || member == "." {
+        bail!("The workspace root can't be a member of itself");
+    }
+    if !Path::new(&member).join("project.toml").exists() {
+        bail!("{} has no project.toml. Run 'zora init' there first.", member);
+    }
+    if workspace.members.contains(&member) {
+        println!("Note: {} is already a workspace member", member);
+        return Ok(());
+    }
+
+    workspace.members.push(member.clone());
+    fs::write("project.toml", write_workspace(&content, &workspace))
+        .context("failed to write project.toml")?;
+
+    println!("{} Added {} to the workspace", "✓".green().bold(), member);
+    Ok(())
 }
 
-pub fn remove(_path: String) -> Result<()> {
-    println!("{}", "Workspace support coming soon!".yellow());
-    bail!("Workspace commands are not yet implemented");
+/// Drops a directory from `[workspace] members`. The directory is left alone.
+pub fn remove(path: String) -> Result<()> {
+    let (content, mut workspace) = read_workspace()?;
+    let member = normalize(&path);
+
+    let before = workspace.members.len();
+    workspace.members.retain(|m| normalize(m) != member);
+    if workspace.members.len() == before {
+        bail!("{} is not a workspace member", member);
+    }
+
+    fs::write("project.toml", write_workspace(&content, &workspace))
+        .context("failed to write project.toml")?;
+
+    println!("{} Removed {} from the workspace", "✓".green().bold(), member);
+    Ok(())
 }
 
+/// Prints the name, version and type of every workspace member.
 pub fn list() -> Result<()> {
-    println!("{}", "Workspace support coming soon!".yellow());
-    bail!("Workspace commands are not yet implemented");
+    let (_, workspace) = read_workspace()?;
+
+    if workspace.members.is_empty() {
+        println!("No workspace members. Add one with 'zora workspace add <path>'.");
+        return Ok(());
+    }
+
+    println!("{}", "Workspace members:".bright_cyan());
+    let width = workspace.members.iter().map(String::len).max().unwrap_or(0);
+    let mut missing = 0;
+    for member in &workspace.members {
+        let dir = Path::new(member);
+        if !dir.join("project.toml").exists() {
+            println!("  {} {:width$}  {}", "✗".red(), member, "project.toml not found".red(), width = width);
+            missing += 1;
+            continue;
+        }
+
+        let config = ProjectConfig::load_from(dir)
+            .with_context(|| format!("failed to load workspace member {}", member))?;
+        println!("  {:width$}  {} v{} ({})",
+            member,
+            config.name.bright_yellow(),
+            config.version,
+            config.r#type,
+            width = width + 2
+        );
+    }
+
+    if missing > 0 {
+        println!("\n{} {} member(s) without a project.toml", "Warning:".yellow(), missing);
+    }
+    Ok(())
+}
+
+/// Whether the project.toml in the current directory has a `[workspace]`
+/// section.
+pub fn is_root() -> bool {
+    fs::read_to_string("project.toml")
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .is_some_and(|value| value.get("workspace").is_some())
+}
+
+/// Outcome of one member's build, for the summary.
+enum MemberBuild {
+    Built(f64),
+    Failed,
+    Skipped(String),
+}
+
+/// Builds every workspace member, each in its own directory with its own
+/// config, members that others depend on by path first. The root is built
+/// last if it's a project too. A failure stops the build unless
+/// `--keep-going` is set; its dependents are skipped either way. With
+/// `--out-dir` or a target dir, each member gets a subdirectory of it.
+pub fn build(opts: BuildOptions, triplets: &[String]) -> Result<()> {
+    let (content, workspace) = read_workspace()?;
+    let root = env::current_dir()?;
+
+    let mut members = vec![];
+    for member in &workspace.members {
+        let dir = root.join(member);
+        if !dir.join("project.toml").exists() {
+            bail!("Workspace member {} has no project.toml", member);
+        }
+        let config = ProjectConfig::load_from(&dir)
+            .with_context(|| format!("failed to load workspace member {}", member))?;
+        members.push((member.clone(), dir.canonicalize()?, config));
+    }
+    let root_is_project = toml::from_str::<toml::Value>(&content)?.get("name").is_some();
+    if root_is_project {
+        members.push((".".to_string(), root.canonicalize()?, ProjectConfig::load()?));
+    }
+
+    let order = build_order(&members)?;
+    let out_dir = paths::out_dir();
+    let target_dir = paths::target_dir();
+
+    println!("{} {} workspace member(s)", "Building".bright_cyan(), members.len());
+
+    let mut results: Vec<(usize, MemberBuild)> = vec![];
+    let mut built_path_deps = vec![];
+    let mut failed = 0;
+    for &index in &order {
+        let (member, dir, config) = &members[index];
+
+        let broken_dep = member_deps(&members, index).into_iter().find(|&dep| {
+            results.iter().any(|(i, result)| *i == dep && !matches!(result, MemberBuild::Built(_)))
+        });
+        if let Some(dep) = broken_dep {
+            results.push((index, MemberBuild::Skipped(members[dep].0.clone())));
+            continue;
+        }
+        if failed > 0 && !opts.keep_going {
+            break;
+        }
+
+        println!("\n{} {} ({})", "→".bright_blue(), config.name.bright_yellow(), member);
+
+        if let Some(out_dir) = &out_dir {
+            env::set_var(paths::OUT_DIR_ENV, out_dir.join(member));
+        }
+        if let Some(target_dir) = &target_dir {
+            env::set_var(paths::TARGET_DIR_ENV, target_dir.join(member));
+        }
+        env::set_current_dir(dir)?;
+        // Members built earlier in the order are linked from where they
+        // are, rather than rebuilt as path dependencies
+        let member_opts = BuildOptions { built_path_deps: built_path_deps.clone(), ..opts.clone() };
+        let started = Instant::now();
+        let result = if triplets.is_empty() {
+            build::run(member_opts)
+        } else {
+            build::run_triplets(member_opts, triplets)
+        };
+        let member_target = dir.join(paths::target_root());
+        env::set_current_dir(&root)?;
+
+        match result {
+            Ok(()) => {
+                built_path_deps.push((dir.clone(), member_target));
+                results.push((index, MemberBuild::Built(started.elapsed().as_secs_f64())));
+            }
+            Err(e) => {
+                println!("{} {}: {:#}", "✗".red().bold(), member, e);
+                results.push((index, MemberBuild::Failed));
+                failed += 1;
+            }
+        }
+    }
+    if let Some(out_dir) = &out_dir {
+        env::set_var(paths::OUT_DIR_ENV, out_dir);
+    }
+    if let Some(target_dir) = &target_dir {
+        env::set_var(paths::TARGET_DIR_ENV, target_dir);
+    }
+
+    println!("\n{}", "─".repeat(40));
+    let width = members.iter().map(|(member, _, _)| member.len()).max().unwrap_or(0);
+    for &index in &order {
+        let (member, _, config) = &members[index];
+        let label = format!("{:width$}  {} v{}", member, config.name, config.version, width = width);
+        match results.iter().find(|(i, _)| *i == index).map(|(_, result)| result) {
+            Some(MemberBuild::Built(secs)) => println!("  {} {} ({:.1}s)", "✓".green().bold(), label, secs),
+            Some(MemberBuild::Failed) => println!("  {} {} {}", "✗".red().bold(), label, "failed".red()),
+            Some(MemberBuild::Skipped(dep)) => println!("  {} {} skipped ({} failed)", "-".yellow(), label, dep),
+            None => println!("  {} {} not built", "-".yellow(), label),
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} of {} workspace member(s) failed to build", failed, members.len());
+    }
+    println!("{} Built {} workspace member(s)", "✓".green().bold(), members.len());
+    Ok(())
+}
+
+/// Indices of the other members that `members[index]` has a path dependency on.
+fn member_deps(members: &[(String, PathBuf, ProjectConfig)], index: usize) -> Vec<usize> {
+    let (_, dir, config) = &members[index];
+    let mut deps: Vec<usize> = config.deps.values()
+        .filter_map(|spec| spec.path())
+        .filter_map(|path| dir.join(path).canonicalize().ok())
+        .filter_map(|dep_dir| members.iter().position(|(_, other, _)| *other == dep_dir))
+        .filter(|&dep| dep != index)
+        .collect();
+    deps.sort();
+    deps.dedup();
+    deps
+}
+
+/// Member indices with dependencies before their dependents, otherwise in
+/// the order they're listed.
+fn build_order(members: &[(String, PathBuf, ProjectConfig)]) -> Result<Vec<usize>> {
+    let deps: Vec<Vec<usize>> = (0..members.len()).map(|i| member_deps(members, i)).collect();
+    let mut order = vec![];
+
+    while order.len() < members.len() {
+        let ready = (0..members.len()).find(|i| {
+            !order.contains(i) && deps[*i].iter().all(|dep| order.contains(dep))
+        });
+        match ready {
+            Some(i) => order.push(i),
+            None => {
+                let cycle: Vec<&str> = (0..members.len())
+                    .filter(|i| !order.contains(i))
+                    .map(|i| members[i].0.as_str())
+                    .collect();
+                bail!("Workspace members depend on each other in a cycle: {}", cycle.join(", "));
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+/// The root project.toml's text and its `[workspace]` section. The file may
+/// be workspace-only, so it isn't loaded as a ProjectConfig.
+fn read_workspace() -> Result<(String, WorkspaceConfig)> {
+    if !Path::new("project.toml").exists() {
+        bail!("project.toml not found. Run 'zora workspace init' first.");
+    }
+
+    let content = fs::read_to_string("project.toml")
+        .context("failed to read project.toml")?;
+    let value: toml::Value = toml::from_str(&content)
+        .context("failed to parse project.toml")?;
+
+    let Some(section) = value.get("workspace") else {
+        bail!("project.toml has no [workspace] section. Run 'zora workspace init' first.");
+    };
+    let workspace = section.clone().try_into()
+        .context("failed to parse [workspace] in project.toml")?;
+
+    Ok((content, workspace))
+}
+
+/// Line range of the `[workspace]` table, up to the next table header.
+fn workspace_section(content: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| line.trim() == "[workspace]")?;
+    let end = lines[start + 1..].iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map(|offset| start + 1 + offset)
+        .unwrap_or(lines.len());
+    Some((start, end))
+}
+
+/// Replaces the `[workspace]` table in `content`, or appends one. Comments
+/// inside the old table aren't kept.
+fn write_workspace(content: &str, workspace: &WorkspaceConfig) -> String {
+    let mut section = vec!["[workspace]".to_string()];
+    section.extend(array_lines("members", &workspace.members));
+    if !workspace.exclude.is_empty() {
+        section.extend(array_lines("exclude", &workspace.exclude));
+    }
+
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    match workspace_section(content) {
+        Some((start, end)) => {
+            if end < lines.len() {
+                section.push(String::new());
+            }
+            lines.splice(start..end, section);
+        }
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push(String::new());
+            }
+            lines.extend(section);
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn array_lines(key: &str, items: &[String]) -> Vec<String> {
+    if items.is_empty() {
+        return vec![format!("{} = []", key)];
+    }
+
+    let mut lines = vec![format!("{} = [", key)];
+    for item in items {
+        lines.push(format!("    {},", toml::Value::String(item.clone())));
+    }
+    lines.push("]".to_string());
+    lines
+}
+
+/// `./libs/core/` and `libs/core` name the same member.
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.strip_prefix("./").unwrap_or(&path);
+    path.trim_end_matches('/').to_string()
 }
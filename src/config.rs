@@ -1,8 +1,9 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ProjectConfig {
@@ -24,14 +25,15 @@ pub struct ProjectConfig {
     pub includes: IncludeConfig,
     #[serde(default)]
     pub deps: HashMap<String, DependencySpec>,
-    #[serde(default)]
+    /// Only installed and linked for `zora test` and `zora bench`
+    #[serde(default, rename = "dev-dependencies", alias = "dev-deps", alias = "dev_deps")]
     pub dev_deps: HashMap<String, DependencySpec>,
     #[serde(default)]
     pub build: BuildConfig,
     #[serde(default)]
     pub tests: TestConfig,
     #[serde(default)]
-    pub scripts: HashMap<String, String>,
+    pub scripts: HashMap<String, ScriptSpec>,
     #[serde(default)]
     pub profile: ProfilesConfig,
     #[serde(default)]
@@ -40,6 +42,139 @@ pub struct ProjectConfig {
     pub default_features: Vec<String>,
     #[serde(default)]
     pub workspace: Option<WorkspaceConfig>,
+    #[serde(default, rename = "bin")]
+    pub bins: Vec<BinTarget>,
+    #[serde(default, rename = "source")]
+    pub source_entries: Vec<SourceEntry>,
+    #[serde(default, rename = "build-dependency")]
+    pub build_dependencies: Vec<BuildDependency>,
+    #[serde(default)]
+    pub install: InstallConfig,
+    #[serde(default)]
+    pub uninstall: UninstallConfig,
+    #[serde(default)]
+    pub fmt: FmtConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub new: NewConfig,
+    #[serde(default)]
+    pub toolchain: ToolchainConfig,
+    /// `[deps] strict = true`: every vcpkg dependency needs an explicit
+    /// version. Read separately since `[deps]` otherwise only holds packages
+    #[serde(skip)]
+    pub strict_deps: bool,
+    /// `[deps] use_vcpkg = false`: the dependencies are found some other way
+    /// (system packages, a custom toolchain), so vcpkg isn't installed into
+    /// or injected as the CMake toolchain
+    #[serde(skip)]
+    pub use_vcpkg: bool,
+}
+
+/// clang-format settings, materialized by `zora fmt --write-config`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct FmtConfig {
+    /// BasedOnStyle, e.g. "LLVM", "Google", "Mozilla"
+    #[serde(default)]
+    pub style: Option<String>,
+    #[serde(default)]
+    pub indent_width: Option<u32>,
+    #[serde(default)]
+    pub column_limit: Option<u32>,
+    /// Any other clang-format options, written verbatim
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+/// clang-tidy settings, materialized by `zora lint --write-config`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub checks: Vec<String>,
+    #[serde(default)]
+    pub warnings_as_errors: Vec<String>,
+    #[serde(default)]
+    pub header_filter: Option<String>,
+}
+
+/// Style of the files scaffolded by `zora new` and `zora init`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct NewConfig {
+    /// "ifndef" (the default) or "pragma-once"
+    #[serde(default)]
+    pub header_guard: Option<String>,
+    /// Prepended to ifndef guard macros, e.g. "MYLIB_"
+    #[serde(default)]
+    pub guard_prefix: String,
+}
+
+impl NewConfig {
+    /// Opening and closing lines for a generated header `<stem>.<ext>`.
+    pub fn header_guard(&self, stem: &str, ext: &str) -> Result<(String, String)> {
+        match self.header_guard.as_deref().unwrap_or("ifndef") {
+            "pragma-once" => Ok(("#pragma once".to_string(), String::new())),
+            "ifndef" => {
+                let guard: String = format!("{}{}_{}", self.guard_prefix, stem, ext)
+                    .chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+                    .collect();
+                Ok((
+                    format!("#ifndef {}\n#define {}", guard, guard),
+                    format!("#endif // {}", guard),
+                ))
+            }
+            other => bail!("Unknown header_guard '{}'. Use \"ifndef\" or \"pragma-once\"", other),
+        }
+    }
+}
+
+/// An extra executable (`[[bin]]`) built from its own entry point. Library
+/// projects link each bin against the library.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BinTarget {
+    pub name: String,
+    pub path: String,
+}
+
+/// A host tool (`[[build-dependency]]`), e.g. a code generator, compiled with
+/// the host compiler and run before each build so its output is picked up.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct BuildDependency {
+    pub name: String,
+    /// The tool's source file, or a directory of its sources
+    pub path: String,
+    /// Arguments the tool is run with, from the project root
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra compiler flags for the tool
+    #[serde(default)]
+    pub flags: Vec<String>,
+}
+
+/// Hooks for `zora install`. Scripts run through the platform shell with
+/// ZORA_INSTALL_PREFIX set, and are skipped for `--destdir` staging installs.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct InstallConfig {
+    /// Run after a successful install, e.g. "ldconfig"
+    #[serde(default)]
+    pub post_install: Option<String>,
+}
+
+/// Hooks for `zora uninstall`, run the same way as [`InstallConfig`] hooks.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct UninstallConfig {
+    /// Run before any files are removed
+    #[serde(default)]
+    pub pre_uninstall: Option<String>,
+}
+
+/// A source file (`[[source]]`) that only compiles when `feature` is enabled.
+/// `check` and `fmt` still see it; the build and tests skip it otherwise.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SourceEntry {
+    pub path: String,
+    #[serde(default)]
+    pub feature: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -47,9 +182,11 @@ pub struct ProjectConfig {
 pub enum DependencySpec {
     Simple(String),
     Detailed {
+        #[serde(default)]
         version: String,
         #[serde(default)]
         features: Vec<String>,
+        /// Only used when an enabled feature lists it as `dep:<name>`
         #[serde(default)]
         optional: bool,
         #[serde(default)]
@@ -58,6 +195,9 @@ pub enum DependencySpec {
         branch: Option<String>,
         #[serde(default)]
         tag: Option<String>,
+        /// Local checkout (e.g. a git submodule) used instead of vcpkg
+        #[serde(default)]
+        path: Option<String>,
     },
 }
 
@@ -68,10 +208,77 @@ impl DependencySpec {
             DependencySpec::Detailed { version, .. } => version,
         }
     }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            DependencySpec::Simple(_) => None,
+            DependencySpec::Detailed { path, .. } => path.as_deref(),
+        }
+    }
+
+    pub fn is_optional(&self) -> bool {
+        matches!(self, DependencySpec::Detailed { optional: true, .. })
+    }
+
+    /// The version (`*` when there's none) with any requested features,
+    /// e.g. `1.2 [ssl, zlib]`, for listings.
+    pub fn label(&self) -> String {
+        let version = match self.version().trim() {
+            "" => "*",
+            version => version,
+        };
+        match self {
+            DependencySpec::Detailed { features, .. } if !features.is_empty() => {
+                format!("{} [{}]", version, features.join(", "))
+            }
+            _ => version.to_string(),
+        }
+    }
+}
+
+/// A `[scripts]` entry: a shell command, or a table that also sets the
+/// environment and working directory it runs with.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum ScriptSpec {
+    Simple(String),
+    Detailed {
+        cmd: String,
+        #[serde(default)]
+        env: HashMap<String, String>,
+        /// Relative to the project root
+        #[serde(default)]
+        cwd: Option<String>,
+    },
+}
+
+impl ScriptSpec {
+    pub fn command(&self) -> &str {
+        match self {
+            ScriptSpec::Simple(cmd) => cmd,
+            ScriptSpec::Detailed { cmd, .. } => cmd,
+        }
+    }
+
+    pub fn env(&self) -> Option<&HashMap<String, String>> {
+        match self {
+            ScriptSpec::Simple(_) => None,
+            ScriptSpec::Detailed { env, .. } => Some(env),
+        }
+    }
+
+    pub fn cwd(&self) -> Option<&str> {
+        match self {
+            ScriptSpec::Simple(_) => None,
+            ScriptSpec::Detailed { cwd, .. } => cwd.as_deref(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct WorkspaceConfig {
+    /// Member directories, relative to the workspace root
+    #[serde(default)]
     pub members: Vec<String>,
     #[serde(default)]
     pub exclude: Vec<String>,
@@ -85,6 +292,8 @@ fn default_project_type() -> String {
 pub struct SourceConfig {
     #[serde(default = "default_source_dirs")]
     pub dirs: Vec<String>,
+    /// Globs of files under the source dirs to leave out of the build,
+    /// e.g. `src/platform/windows/*.c`
     #[serde(default)]
     pub exclude: Vec<String>,
 }
@@ -97,24 +306,90 @@ fn default_source_dirs() -> Vec<String> {
 pub struct IncludeConfig {
     #[serde(default = "default_include_dirs")]
     pub dirs: Vec<String>,
+    /// Third-party header dirs, searched after `dirs` with `-isystem` so
+    /// their warnings are silenced
+    #[serde(default)]
+    pub system: Vec<String>,
+    /// "separate" keeps headers in `dirs`; "colocated" keeps them beside the
+    /// sources, so the source dirs are include dirs too
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Colocated only: patterns marking a library's public headers, e.g.
+    /// ["*_public.h"]. Every header is public when empty
+    #[serde(default)]
+    pub public: Vec<String>,
 }
 
+/// Header extensions, for telling a library's headers from its sources.
+pub const HEADER_EXTENSIONS: &[&str] = &["h", "hh", "hpp", "hxx"];
+
 fn default_include_dirs() -> Vec<String> {
     vec!["include".to_string()]
 }
 
+/// `[toolchain]`: the compilers and sysroot to build with instead of the
+/// host's, e.g. for cross-compiling. Named toolchains live in
+/// `[toolchain.<name>]` and are selected with `zora build --target <name>`.
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ToolchainConfig {
+    #[serde(flatten)]
+    pub default: Toolchain,
+    #[serde(flatten)]
+    pub named: HashMap<String, Toolchain>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
+pub struct Toolchain {
+    /// C compiler, e.g. "arm-linux-gnueabihf-gcc"
+    #[serde(default)]
+    pub cc: Option<String>,
+    /// C++ compiler
+    #[serde(default)]
+    pub cxx: Option<String>,
+    /// Archiver for static libraries
+    #[serde(default)]
+    pub ar: Option<String>,
+    #[serde(default)]
+    pub sysroot: Option<String>,
+    /// Target triple passed to the compiler, e.g. "aarch64-linux-gnu" (clang)
+    #[serde(default)]
+    pub target: Option<String>,
+    /// CMAKE_SYSTEM_NAME, e.g. "Linux", which tells CMake it's cross-compiling
+    #[serde(default)]
+    pub system_name: Option<String>,
+    /// An existing CMake toolchain file, relative to the project root. With
+    /// vcpkg it's chainloaded from vcpkg's toolchain.
+    #[serde(default)]
+    pub toolchain_file: Option<String>,
+}
+
+impl Toolchain {
+    pub fn is_empty(&self) -> bool {
+        *self == Toolchain::default()
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Default)]
 pub struct BuildConfig {
     #[serde(default)]
     pub flags: Vec<String>,
     #[serde(default)]
     pub defines: HashMap<String, String>,
+    /// Libraries only: defines applied to consumers (INTERFACE), including
+    /// projects that use this one as a path dependency
+    #[serde(default)]
+    pub export_defines: HashMap<String, String>,
     #[serde(default)]
     pub libs: Vec<String>,
     #[serde(default)]
     pub lib_dirs: Vec<String>,
-    #[serde(default = "default_optimization")]
-    pub optimization: String,
+    /// macOS frameworks to link, e.g. ["Foundation", "AppKit"]
+    #[serde(default)]
+    pub frameworks: Vec<String>,
+    /// `-O` level for optimized profiles; debug profiles keep their own
+    /// opt_level. Unset falls back to the profile's opt_level everywhere.
+    #[serde(default)]
+    pub optimization: Option<String>,
     #[serde(default)]
     pub warnings: Vec<String>,
     #[serde(default)]
@@ -122,15 +397,131 @@ pub struct BuildConfig {
     // NEW: Add static linking option
     #[serde(default)]
     pub static_link: bool,
+    /// C++ standard library to build against: "libc++" or "libstdc++" (clang only)
+    #[serde(default)]
+    pub stdlib: Option<String>,
+    #[serde(default)]
+    pub checks: ChecksConfig,
+    /// Maximum concurrent link jobs; derived from total memory when unset
+    #[serde(default)]
+    pub link_jobs: Option<usize>,
+    /// Default parallel jobs when --jobs isn't given; all CPUs when unset
+    #[serde(default)]
+    pub jobs: Option<usize>,
+    /// Back off starting jobs above this system load (Make and Ninja only)
+    #[serde(default)]
+    pub load_average: Option<f64>,
+    /// Cache compiler output with ccache
+    #[serde(default)]
+    pub ccache: bool,
+    /// Distribute compiles with "icecc" or "distcc" (after ccache, if enabled)
+    #[serde(default)]
+    pub distributed: Option<String>,
+    /// Set SOURCE_DATE_EPOCH and strip absolute paths for bit-reproducible output
+    #[serde(default)]
+    pub reproducible: bool,
+    /// Strip executables and keep their debug info in a `.debug` file
+    /// (Linux) or `.dSYM` bundle (macOS) next to them
+    #[serde(default)]
+    pub split_debug: bool,
+    /// Link with "mold", "lld" or "gold"; "system" keeps the compiler's
+    /// default. When unset, mold or lld is used if installed
+    #[serde(default)]
+    pub linker: Option<String>,
+    /// CMake generator: "ninja", "make" or "default" (CMake's choice)
+    #[serde(default)]
+    pub generator: Option<String>,
+    /// Also copy each artifact to `<name>-<hash>`, named by its SHA-256, for
+    /// cache-busting deploys; `<name>.latest` holds the newest copy's name
+    #[serde(default)]
+    pub hash_artifacts: bool,
+    /// Where artifacts are copied instead of `target/`, relative to the
+    /// project root; `--target-dir` and ZORA_TARGET_DIR take precedence
+    #[serde(default)]
+    pub target_dir: Option<String>,
+    /// List the sources explicitly, sorted by path, instead of globbing in
+    /// CMake, whose order depends on the filesystem
+    #[serde(default)]
+    pub sorted_sources: bool,
+    /// Compile sources in batches concatenated into one translation unit
+    /// (CMake's unity build), for faster clean builds
+    #[serde(default)]
+    pub unity_build: bool,
+    /// Sources per unity batch; CMake's default (8) when unset
+    #[serde(default)]
+    pub unity_batch_size: Option<usize>,
+    /// Environment variables the build needs; checked before building
+    #[serde(default)]
+    pub required_env: Vec<String>,
+    /// GNU dialect (`-std=gnu++17`) when true, ISO (`-std=c++17`) when
+    /// false; CMake's default (GNU) when unset
+    #[serde(default)]
+    pub gnu_extensions: Option<bool>,
+    /// C++ only: `false` builds with -fno-exceptions
+    #[serde(default)]
+    pub exceptions: Option<bool>,
+    /// C++ only: `false` builds with -fno-rtti
+    #[serde(default)]
+    pub rtti: Option<bool>,
+    /// `false` drops the warning flags zora adds to profiles by default
+    /// (see `DEFAULT_FLAGS`), keeping only flags listed elsewhere
+    #[serde(default)]
+    pub inherit_default_flags: Option<bool>,
+    /// Files copied after a successful build, e.g. assets next to the executable
+    #[serde(default)]
+    pub copy: Vec<CopyRule>,
 }
 
-fn default_optimization() -> String {
-    "2".to_string()
+/// A `[build] copy` entry. `from` is a file, directory or glob; `to` is the
+/// directory it's copied into, with `{profile}` and `{target_dir}` expanded.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CopyRule {
+    pub from: String,
+    pub to: String,
 }
 
+/// Warning flags zora puts in the default profiles and `zora init`'s
+/// project.toml, dropped by `--no-default-flags`.
+pub const DEFAULT_FLAGS: &[&str] = &["-Wall", "-Wextra", "-Wpedantic"];
+
+/// Configure-time checks, each defining a `HAVE_*` macro in a generated config.h.
 #[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ChecksConfig {
+    #[serde(default)]
+    pub has_header: Vec<String>,
+    #[serde(default)]
+    pub has_function: Vec<String>,
+    /// Symbol name -> header that declares it
+    #[serde(default)]
+    pub has_symbol: HashMap<String, String>,
+}
+
+impl ChecksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.has_header.is_empty() && self.has_function.is_empty() && self.has_symbol.is_empty()
+    }
+}
+
+/// `-std=c++17`, or `-std=gnu++17` for the GNU dialect.
+pub fn std_flag(std: &str, is_cpp: bool, gnu: bool) -> String {
+    let prefix = match (is_cpp, gnu) {
+        (true, true) => "gnu++",
+        (true, false) => "c++",
+        (false, true) => "gnu",
+        (false, false) => "c",
+    };
+    format!("-std={}{}", prefix, std)
+}
+
+/// Levels accepted by `-O`.
+pub const OPT_LEVELS: &[&str] = &["0", "1", "2", "3", "s", "z", "g", "fast"];
+
+/// Per-profile overlays on `[build]`. A profile's `optimization` and
+/// defines win over the base config's; its flags and libs are appended to
+/// `[build]`'s, or replace them with `replace = true`.
+#[derive(Debug, Deserialize, Serialize)]
 pub struct ProfilesConfig {
-    #[serde(default = "default_dev_profile")]
+    #[serde(default = "default_dev_profile", alias = "debug")]
     pub dev: ProfileConfig,
     #[serde(default = "default_release_profile")]
     pub release: ProfileConfig,
@@ -152,6 +543,24 @@ pub struct ProfileConfig {
     pub flags: Vec<String>,
     #[serde(default)]
     pub defines: HashMap<String, String>,
+    /// Overrides `[build] optimization` and opt_level, debug profiles included
+    #[serde(default)]
+    pub optimization: Option<String>,
+    #[serde(default)]
+    pub libs: Vec<String>,
+    /// Use only this profile's flags, defines and libs, dropping `[build]`'s
+    #[serde(default)]
+    pub replace: bool,
+}
+
+impl Default for ProfilesConfig {
+    fn default() -> Self {
+        Self {
+            dev: default_dev_profile(),
+            release: default_release_profile(),
+            custom: HashMap::new(),
+        }
+    }
 }
 
 fn default_dev_profile() -> ProfileConfig {
@@ -161,7 +570,7 @@ fn default_dev_profile() -> ProfileConfig {
         lto: false,
         strip: false,
         flags: vec!["-Wall".to_string(), "-Wextra".to_string()],
-        defines: HashMap::new(),
+        ..Default::default()
     }
 }
 
@@ -172,7 +581,7 @@ fn default_release_profile() -> ProfileConfig {
         lto: true,
         strip: true,
         flags: vec!["-Wall".to_string(), "-Wextra".to_string(), "-DNDEBUG".to_string()],
-        defines: HashMap::new(),
+        ..Default::default()
     }
 }
 
@@ -184,7 +593,7 @@ fn default_debug() -> bool {
     false
 }
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct TestConfig {
     #[serde(default = "default_test_dirs")]
     pub dirs: Vec<String>,
@@ -192,21 +601,103 @@ pub struct TestConfig {
     pub framework: String,
     #[serde(default)]
     pub harness: bool,
+    /// "per-file" builds one binary per test file; "harness" links every
+    /// test file and the project sources into a single executable.
+    #[serde(default = "default_test_mode")]
+    pub mode: String,
+    /// Each `zora test` run appends a JSON record of its results here
+    #[serde(default)]
+    pub history_dir: Option<String>,
+}
+
+// Written out so a missing [tests] table gets the same defaults as an empty one
+impl Default for TestConfig {
+    fn default() -> Self {
+        TestConfig {
+            dirs: default_test_dirs(),
+            framework: String::new(),
+            harness: false,
+            mode: default_test_mode(),
+            history_dir: None,
+        }
+    }
+}
+
+fn default_test_mode() -> String {
+    "per-file".to_string()
 }
 
 fn default_test_dirs() -> Vec<String> {
     vec!["tests".to_string()]
 }
 
+/// Machine-specific overrides merged on top of project.toml. Not meant to be committed.
+pub const LOCAL_CONFIG_FILE: &str = "project.local.toml";
+
+/// Removes a boolean setting such as `strict` from `[deps]`, which otherwise
+/// only holds packages, and returns it.
+fn take_deps_flag(value: &mut toml::Value, key: &str) -> Option<bool> {
+    let deps = value.get_mut("deps")?.as_table_mut()?;
+    match deps.get(key) {
+        Some(toml::Value::Boolean(flag)) => {
+            let flag = *flag;
+            deps.remove(key);
+            Some(flag)
+        }
+        _ => None,
+    }
+}
+
 impl ProjectConfig {
     pub fn load() -> Result<Self> {
-        let content = fs::read_to_string("project.toml")
+        Self::load_from(Path::new("."))
+    }
+
+    /// Loads the project.toml in `dir`, e.g. that of a path dependency.
+    pub fn load_from(dir: &Path) -> Result<Self> {
+        let content = fs::read_to_string(dir.join("project.toml"))
             .context("failed to read project.toml")?;
-        
-        toml::from_str(&content)
-            .context("failed to parse project.toml")
+
+        let mut value: toml::Value = toml::from_str(&content)
+            .context("failed to parse project.toml")?;
+
+        let local_path = dir.join(LOCAL_CONFIG_FILE);
+        if local_path.exists() {
+            let local_content = fs::read_to_string(&local_path)
+                .with_context(|| format!("failed to read {}", LOCAL_CONFIG_FILE))?;
+            let local: toml::Value = toml::from_str(&local_content)
+                .with_context(|| format!("failed to parse {}", LOCAL_CONFIG_FILE))?;
+            merge_toml(&mut value, local);
+        }
+
+        let strict_deps = take_deps_flag(&mut value, "strict").unwrap_or(false);
+        let use_vcpkg = take_deps_flag(&mut value, "use_vcpkg").unwrap_or(true);
+
+        let mut config: ProjectConfig = value.try_into()
+            .context("failed to parse project.toml")?;
+        config.strict_deps = strict_deps;
+        config.use_vcpkg = use_vcpkg;
+
+        match config.includes.mode.as_deref() {
+            None | Some("separate") | Some("colocated") => {}
+            Some(other) => bail!("Unknown [includes] mode '{}'. Use \"separate\" or \"colocated\"", other),
+        }
+
+        let profiles = [&config.profile.dev, &config.profile.release].into_iter()
+            .chain(config.profile.custom.values());
+        let profile_levels = profiles.flat_map(|p| std::iter::once(&p.opt_level).chain(&p.optimization));
+        for level in config.build.optimization.iter().chain(profile_levels) {
+            if !OPT_LEVELS.contains(&level.as_str()) {
+                bail!("Unknown optimization level '{}'. Use one of: {}", level, OPT_LEVELS.join(", "));
+            }
+        }
+
+        Ok(config)
     }
 
+    /// Writes the config back to project.toml. Any values merged in from
+    /// project.local.toml are written too, so prefer editing the file directly.
+    #[allow(dead_code)]
     pub fn save(&self) -> Result<()> {
         let content = toml::to_string_pretty(self)
             .context("failed to serialize project.toml")?;
@@ -223,8 +714,395 @@ impl ProjectConfig {
         self.r#type == "lib" || self.r#type == "library"
     }
 
+    /// Names of every executable the build produces: the main target for
+    /// executable projects, followed by any `[[bin]]` targets.
+    pub fn executables(&self) -> Vec<String> {
+        let mut names = vec![];
+        if !self.is_library() {
+            names.push(self.name.clone());
+        }
+        names.extend(self.bins.iter().map(|b| b.name.clone()));
+        names
+    }
+
+    /// Dependencies installed through vcpkg, i.e. everything but path deps.
+    pub fn vcpkg_packages(&self) -> Vec<String> {
+        let mut packages: Vec<String> = self.deps.iter()
+            .filter(|(_, spec)| spec.path().is_none())
+            .map(|(name, _)| name.clone())
+            .collect();
+        packages.sort();
+        packages
+    }
+
+    /// Whether the build goes through vcpkg: there are vcpkg dependencies
+    /// and `[deps] use_vcpkg` isn't false.
+    pub fn uses_vcpkg(&self) -> bool {
+        self.use_vcpkg && !self.vcpkg_packages().is_empty()
+    }
+
+    /// vcpkg dependencies (and dev-dependencies) pinned as `"*"` or without
+    /// a version, which `[deps] strict` rejects. Path and git deps are
+    /// pinned by their checkout instead.
+    pub fn wildcard_deps(&self) -> Vec<String> {
+        let mut wildcards: Vec<String> = self.deps.iter()
+            .chain(&self.dev_deps)
+            .filter(|(_, spec)| match spec {
+                DependencySpec::Simple(version) => matches!(version.trim(), "" | "*"),
+                DependencySpec::Detailed { version, git, path, .. } => {
+                    git.is_none() && path.is_none() && matches!(version.trim(), "" | "*")
+                }
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        wildcards.sort();
+        wildcards.dedup();
+        wildcards
+    }
+
+    /// Fails listing the wildcard deps when `[deps] strict` or `force` is set.
+    pub fn check_strict_deps(&self, force: bool) -> Result<()> {
+        if !(force || self.strict_deps) {
+            return Ok(());
+        }
+        let wildcards = self.wildcard_deps();
+        if !wildcards.is_empty() {
+            bail!("Strict dependencies: explicit versions required for:\n  {}\nPin them in project.toml, e.g. {} = \"1.2.3\"",
+                wildcards.join("\n  "), wildcards[0]);
+        }
+        Ok(())
+    }
+
+    /// Dev-dependencies installed through vcpkg.
+    pub fn dev_vcpkg_packages(&self) -> Vec<String> {
+        let mut packages: Vec<String> = self.dev_deps.iter()
+            .filter(|(_, spec)| spec.path().is_none())
+            .map(|(name, _)| name.clone())
+            .collect();
+        packages.sort();
+        packages
+    }
+
+    /// `(name, path)` of every path dependency, sorted by name.
+    pub fn path_deps(&self) -> Vec<(String, String)> {
+        let mut deps: Vec<(String, String)> = self.deps.iter()
+            .filter_map(|(name, spec)| spec.path().map(|p| (name.clone(), p.to_string())))
+            .collect();
+        deps.sort();
+        deps
+    }
+
+    /// `[[source]]` paths gated on a feature that isn't in `features`.
+    pub fn inactive_sources(&self, features: &[String]) -> Result<Vec<PathBuf>> {
+        let mut inactive = vec![];
+
+        for entry in &self.source_entries {
+            let Some(feature) = &entry.feature else {
+                continue;
+            };
+
+            if !self.features.contains_key(feature) {
+                bail!("[[source]] {} is gated on unknown feature '{}'", entry.path, feature);
+            }
+
+            if !features.contains(feature) {
+                inactive.push(Path::new(entry.path.trim_start_matches("./")).to_path_buf());
+            }
+        }
+
+        Ok(inactive)
+    }
+
+    /// The `[sources] exclude` globs, e.g. `src/platform/windows/*.c`.
+    pub fn exclude_patterns(&self) -> Result<Vec<glob::Pattern>> {
+        self.sources.exclude.iter()
+            .map(|p| glob::Pattern::new(p).with_context(|| format!("invalid [sources] exclude pattern: {}", p)))
+            .collect()
+    }
+
+    /// Files under the source dirs that aren't built: feature-gated sources
+    /// outside `features` and anything matching a `[sources] exclude` glob.
+    pub fn excluded_sources(&self, features: &[String]) -> Result<Vec<PathBuf>> {
+        let mut excluded = self.inactive_sources(features)?;
+
+        let patterns = self.exclude_patterns()?;
+        if !patterns.is_empty() {
+            for source_dir in &self.sources.dirs {
+                for entry in WalkDir::new(source_dir).into_iter().filter_map(|e| e.ok()) {
+                    let path = entry.path();
+                    let relative = path.strip_prefix(".").unwrap_or(path);
+                    if entry.file_type().is_file()
+                        && patterns.iter().any(|p| p.matches_path(relative))
+                        && !excluded.iter().any(|e| e == relative)
+                    {
+                        excluded.push(relative.to_path_buf());
+                    }
+                }
+            }
+        }
+
+        Ok(excluded)
+    }
+
+    /// C/C++ sources under the source dirs, excluding `main` and `[[bin]]`
+    /// entry points so they can be linked into test, fuzz and other harness
+    /// executables. Sources gated on a feature outside `features` or matching
+    /// `[sources] exclude` are left out.
+    pub fn library_sources(&self, features: &[String]) -> Result<Vec<PathBuf>> {
+        let inactive = self.excluded_sources(features)?;
+        let mut sources = vec![];
+
+        for source_dir in &self.sources.dirs {
+            for entry in WalkDir::new(source_dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+
+                let is_source = path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .map(|ext| self.source_extensions().contains(&ext))
+                    .unwrap_or(false);
+                let is_main = path.file_stem()
+                    .map(|stem| stem == "main")
+                    .unwrap_or(false);
+
+                let relative = path.strip_prefix(".").unwrap_or(path);
+                let is_bin = self.bins.iter().any(|bin| Path::new(bin.path.trim_start_matches("./")) == relative);
+                if is_source && !is_main && !is_bin && !inactive.iter().any(|p| p == relative) {
+                    sources.push(path.to_path_buf());
+                }
+            }
+        }
+
+        sources.sort();
+        Ok(sources)
+    }
+
+    /// C++ or Objective-C++.
     pub fn is_cpp(&self) -> bool {
-        self.language == "cpp" || self.language == "c++"
+        matches!(self.language.as_str(), "cpp" | "c++" | "objcpp" | "objective-c++")
+    }
+
+    /// Objective-C or Objective-C++.
+    pub fn is_objc(&self) -> bool {
+        matches!(self.language.as_str(), "objc" | "objective-c" | "objcpp" | "objective-c++")
+    }
+
+    /// Languages enabled in the generated CMake project; the first is the
+    /// project's primary language.
+    pub fn cmake_languages(&self) -> Vec<&'static str> {
+        match (self.is_cpp(), self.is_objc()) {
+            (false, false) => vec!["C"],
+            (true, false) => vec!["CXX"],
+            (false, true) => vec!["C", "OBJC"],
+            (true, true) => vec!["CXX", "OBJCXX"],
+        }
+    }
+
+    /// Extensions of the files compiled as project sources.
+    pub fn source_extensions(&self) -> &'static [&'static str] {
+        match (self.is_cpp(), self.is_objc()) {
+            (_, false) => &["c", "cpp"],
+            (false, true) => &["c", "m"],
+            (true, true) => &["c", "cpp", "m", "mm"],
+        }
+    }
+
+    /// Extension for newly scaffolded source files.
+    pub fn source_extension(&self) -> &'static str {
+        match (self.is_cpp(), self.is_objc()) {
+            (false, false) => "c",
+            (true, false) => "cpp",
+            (false, true) => "m",
+            (true, true) => "mm",
+        }
+    }
+
+    /// `-x` flags for direct compiles, so every file is treated as
+    /// Objective-C(++) in those projects. Empty otherwise.
+    pub fn language_flags(&self) -> Vec<String> {
+        match (self.is_cpp(), self.is_objc()) {
+            (false, true) => vec!["-x".to_string(), "objective-c".to_string()],
+            (true, true) => vec!["-x".to_string(), "objective-c++".to_string()],
+            _ => vec![],
+        }
+    }
+
+    /// `-std=` for the language standard, in the GNU or ISO dialect per
+    /// `[build] gnu_extensions`, plus `-fno-exceptions`/`-fno-rtti` when
+    /// those are turned off. For compiles that don't go through CMake.
+    pub fn dialect_flags(&self) -> Vec<String> {
+        let mut flags = vec![];
+
+        if !self.std.is_empty() {
+            // Unset leaves CMake's default, which is the GNU dialect
+            let gnu = self.build.gnu_extensions.unwrap_or(true);
+            flags.push(std_flag(&self.std, self.is_cpp(), gnu));
+        }
+
+        flags.extend(self.feature_flags());
+        flags
+    }
+
+    /// `-fno-exceptions`/`-fno-rtti` for C++ with `[build] exceptions` or
+    /// `rtti` set to false.
+    pub fn feature_flags(&self) -> Vec<String> {
+        let mut flags = vec![];
+        if !self.is_cpp() {
+            return flags;
+        }
+
+        if self.build.exceptions == Some(false) {
+            flags.push("-fno-exceptions".to_string());
+        }
+        if self.build.rtti == Some(false) {
+            flags.push("-fno-rtti".to_string());
+        }
+        flags
+    }
+
+    /// `-framework` link flags for `[build] frameworks` (macOS only).
+    pub fn framework_flags(&self) -> Vec<String> {
+        if !cfg!(target_os = "macos") {
+            return vec![];
+        }
+
+        self.build.frameworks.iter()
+            .flat_map(|framework| ["-framework".to_string(), framework.clone()])
+            .collect()
+    }
+
+    /// Compiler used for direct (non-CMake) compiles such as `check` and `test`.
+    /// Honors CC/CXX, and prefers clang when a `stdlib` is requested or the
+    /// project is Objective-C, which gcc barely supports.
+    pub fn compiler(&self) -> String {
+        if self.is_cpp() {
+            std::env::var("CXX").unwrap_or_else(|_| {
+                if self.build.stdlib.is_some() || self.is_objc() { "clang++" } else { "g++" }.to_string()
+            })
+        } else {
+            std::env::var("CC").unwrap_or_else(|_| {
+                if self.is_objc() { "clang" } else { "gcc" }.to_string()
+            })
+        }
+    }
+
+    /// The toolchain for `zora build --target <target>`: `[toolchain.<target>]`,
+    /// or `[toolchain]` when no target is given. A target without a named
+    /// toolchain is only passed to CMake as the system name.
+    pub fn toolchain(&self, target: Option<&str>) -> Option<&Toolchain> {
+        match target {
+            Some(target) => self.toolchain.named.get(target),
+            None => Some(&self.toolchain.default).filter(|toolchain| !toolchain.is_empty()),
+        }
+    }
+
+    /// Flags selecting the configured C++ standard library, validated against `compiler`.
+    pub fn stdlib_flags(&self, compiler: &str) -> Result<Vec<String>> {
+        let stdlib = match &self.build.stdlib {
+            Some(stdlib) if self.is_cpp() => stdlib,
+            _ => return Ok(vec![]),
+        };
+
+        if stdlib != "libc++" && stdlib != "libstdc++" {
+            bail!("Unknown stdlib '{}'. Use \"libc++\" or \"libstdc++\"", stdlib);
+        }
+
+        if !compiler.contains("clang") {
+            bail!("stdlib = \"{}\" requires clang, but the selected compiler is '{}'", stdlib, compiler);
+        }
+
+        Ok(vec![format!("-stdlib={}", stdlib)])
+    }
+
+    /// Flags for compiling a single translation unit outside of CMake, mirroring
+    /// the standard, optimization, includes, flags and defines of the given profile.
+    pub fn compile_flags(&self, mode: &str) -> Vec<String> {
+        let profile = self.get_profile(mode);
+        let mut flags = self.language_flags();
+        flags.push(format!("-O{}", self.opt_level(mode)));
+
+        if profile.debug {
+            flags.push("-g".to_string());
+        }
+
+        flags.extend(self.dialect_flags());
+        flags.extend(self.include_flags());
+
+        flags.extend(self.merged_flags(mode, false));
+
+        for (key, value) in self.merged_defines(mode) {
+            flags.push(format!("-D{}={}", key, value));
+        }
+
+        flags
+    }
+
+    /// Whether headers live beside the sources (`[includes] mode = "colocated"`).
+    pub fn headers_colocated(&self) -> bool {
+        self.includes.mode.as_deref() == Some("colocated")
+    }
+
+    /// The project's own include dirs: `[includes] dirs`, followed by the
+    /// source dirs in the colocated layout.
+    pub fn include_dirs(&self) -> Vec<String> {
+        let mut dirs = self.includes.dirs.clone();
+        if self.headers_colocated() {
+            for dir in &self.sources.dirs {
+                if !dirs.contains(dir) {
+                    dirs.push(dir.clone());
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Colocated layout: the headers under the source dirs matching
+    /// `[includes] public`, each paired with its path relative to its
+    /// source dir, which is where it's installed under include/.
+    pub fn public_headers(&self) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let patterns = self.includes.public.iter()
+            .map(|p| glob::Pattern::new(p).with_context(|| format!("invalid [includes] public pattern: {}", p)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut headers = vec![];
+        for dir in &self.sources.dirs {
+            for entry in WalkDir::new(dir).sort_by_file_name().into_iter().filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let is_header = path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| HEADER_EXTENSIONS.contains(&ext));
+                if !entry.file_type().is_file() || !is_header {
+                    continue;
+                }
+
+                let name = entry.file_name().to_string_lossy();
+                if patterns.is_empty() || patterns.iter().any(|p| p.matches(&name)) {
+                    let relative = path.strip_prefix(dir).unwrap_or(path).to_path_buf();
+                    headers.push((path.to_path_buf(), relative));
+                }
+            }
+        }
+        Ok(headers)
+    }
+
+    /// `-I` flags for the include dirs, in order, followed by `-isystem`
+    /// flags for the system include dirs.
+    pub fn include_flags(&self) -> Vec<String> {
+        let mut flags: Vec<String> = self.include_dirs().iter()
+            .map(|dir| format!("-I{}", dir))
+            .collect();
+
+        for dir in &self.includes.system {
+            flags.push("-isystem".to_string());
+            flags.push(dir.clone());
+        }
+
+        flags
     }
 
     pub fn get_profile(&self, mode: &str) -> ProfileConfig {
@@ -236,9 +1114,173 @@ impl ProjectConfig {
         }
     }
 
-    pub fn enabled_features(&self, features: &[String]) -> Vec<String> {
-        let mut enabled = self.default_features.clone();
-        enabled.extend_from_slice(features);
-        enabled
+    /// The `-O` level for a profile: its own `optimization` if set, then
+    /// `[build] optimization` unless the profile has debug info, in which case
+    /// its opt_level (`0` for dev) wins.
+    pub fn opt_level(&self, mode: &str) -> String {
+        let profile = self.get_profile(mode);
+        if let Some(level) = profile.optimization {
+            return level;
+        }
+        match &self.build.optimization {
+            Some(level) if !profile.debug => level.clone(),
+            _ => profile.opt_level,
+        }
+    }
+
+    /// `[build]` flags followed by the profile's, so the profile's win where
+    /// they conflict; only the profile's with `replace = true`.
+    pub fn merged_flags(&self, mode: &str, no_default_flags: bool) -> Vec<String> {
+        let mut flags = vec![];
+        if !self.get_profile(mode).replace {
+            flags.extend(self.build.flags.iter().cloned());
+        }
+        flags.extend(self.profile_flags(mode, no_default_flags));
+        flags
+    }
+
+    /// `[build]` defines overlaid with the profile's.
+    pub fn merged_defines(&self, mode: &str) -> BTreeMap<String, String> {
+        let profile = self.get_profile(mode);
+        let mut defines = BTreeMap::new();
+        if !profile.replace {
+            defines.extend(self.build.defines.clone());
+        }
+        defines.extend(profile.defines);
+        defines
+    }
+
+    /// `[build] libs` followed by the profile's.
+    pub fn merged_libs(&self, mode: &str) -> Vec<String> {
+        let profile = self.get_profile(mode);
+        let mut libs = vec![];
+        if !profile.replace {
+            libs.extend(self.build.libs.iter().cloned());
+        }
+        libs.extend(profile.libs);
+        libs
+    }
+
+    /// The profile's flags, minus `DEFAULT_FLAGS` when `no_default_flags` is
+    /// given or `[build] inherit_default_flags` is false.
+    pub fn profile_flags(&self, mode: &str, no_default_flags: bool) -> Vec<String> {
+        let inherit = !no_default_flags && self.build.inherit_default_flags != Some(false);
+        self.get_profile(mode).flags.into_iter()
+            .filter(|flag| inherit || !DEFAULT_FLAGS.contains(&flag.as_str()))
+            .collect()
+    }
+
+    /// Entries of `[build] required_env` that are unset or empty.
+    pub fn missing_env(&self) -> Vec<&str> {
+        self.build.required_env.iter()
+            .filter(|var| std::env::var_os(var).is_none_or(|value| value.is_empty()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The default features plus `features`, with the features they enable.
+    pub fn enabled_features(&self, features: &[String]) -> Result<Vec<String>> {
+        self.resolve_features(features, false, false)
+    }
+
+    /// The features a build enables: the defaults unless `no_default`, the
+    /// requested ones (or every one with `all`), and the features those list
+    /// in turn. Sorted; unknown names are an error.
+    pub fn resolve_features(&self, requested: &[String], all: bool, no_default: bool) -> Result<Vec<String>> {
+        let mut pending: Vec<String> = vec![];
+        if !no_default {
+            pending.extend(self.default_features.iter().cloned());
+        }
+        if all {
+            pending.extend(self.features.keys().cloned());
+        } else {
+            pending.extend(requested.iter().cloned());
+        }
+
+        let mut enabled = BTreeSet::new();
+        while let Some(feature) = pending.pop() {
+            let Some(entries) = self.features.get(&feature) else {
+                let mut available: Vec<&str> = self.features.keys().map(String::as_str).collect();
+                available.sort();
+                if available.is_empty() {
+                    bail!("Unknown feature '{}'. No [features] are defined", feature);
+                }
+                bail!("Unknown feature '{}'. Available: {}", feature, available.join(", "));
+            };
+
+            if !enabled.insert(feature.clone()) {
+                continue;
+            }
+            for entry in entries {
+                match entry.split_once(':') {
+                    None => pending.push(entry.clone()),
+                    Some(("dep", _)) | Some(("define", _)) => {}
+                    Some(_) => bail!("Unknown entry '{}' in feature '{}'. Use a feature name, \"dep:<package>\" or \"define:<NAME>[=<value>]\"", entry, feature),
+                }
+            }
+        }
+
+        Ok(enabled.into_iter().collect())
+    }
+
+    /// `FEATURE_<NAME>=1` for each enabled feature, plus the `define:` entries
+    /// they list (`1` when no value is given).
+    pub fn feature_defines(&self, features: &[String]) -> BTreeMap<String, String> {
+        let mut defines = BTreeMap::new();
+        for feature in features {
+            defines.insert(
+                format!("FEATURE_{}", feature.to_uppercase().replace("-", "_")),
+                "1".to_string()
+            );
+            for define in self.feature_entries(feature, "define") {
+                let (key, value) = define.split_once('=').unwrap_or((define, "1"));
+                defines.insert(key.to_string(), value.to_string());
+            }
+        }
+        defines
+    }
+
+    /// vcpkg packages a build with these features links: every non-optional
+    /// vcpkg dependency, and those named by the features' `dep:` entries,
+    /// declared or not.
+    pub fn build_vcpkg_packages(&self, features: &[String]) -> Vec<String> {
+        let mut packages: BTreeSet<String> = self.deps.iter()
+            .filter(|(_, spec)| spec.path().is_none() && !spec.is_optional())
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for feature in features {
+            for package in self.feature_entries(feature, "dep") {
+                if self.deps.get(package).is_none_or(|spec| spec.path().is_none()) {
+                    packages.insert(package.to_string());
+                }
+            }
+        }
+
+        packages.into_iter().collect()
+    }
+
+    /// The values of a feature's `<kind>:` entries.
+    fn feature_entries<'a>(&'a self, feature: &str, kind: &'a str) -> impl Iterator<Item = &'a str> {
+        self.features.get(feature).into_iter().flatten()
+            .filter_map(move |entry| entry.strip_prefix(kind)?.strip_prefix(':'))
+    }
+}
+
+/// Deep-merges `overlay` into `base`. Tables are merged key by key; any
+/// other value (including arrays) in the overlay replaces the base value.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
     }
 }
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Registry used when `--registry` isn't given.
+pub const DEFAULT_REGISTRY: &str = "default";
+
+/// Per-registry tokens from `~/.config/zora/credentials.toml`, kept out of
+/// project.toml and the command line.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Credentials {
+    #[serde(default)]
+    pub registries: BTreeMap<String, RegistryCredentials>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryCredentials {
+    pub token: String,
+}
+
+/// `$XDG_CONFIG_HOME/zora/credentials.toml`, falling back to `~/.config`.
+pub fn path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            let home = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })?;
+            Some(PathBuf::from(home).join(".config"))
+        })?;
+    Some(config_home.join("zora").join("credentials.toml"))
+}
+
+impl Credentials {
+    /// The stored credentials; empty when the file doesn't exist yet.
+    pub fn load() -> Result<Self> {
+        let Some(path) = path() else {
+            return Ok(Credentials::default());
+        };
+        if !path.exists() {
+            return Ok(Credentials::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", path.display()))
+    }
+
+    /// Writes the credentials back, readable only by the current user.
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = path().context("could not determine the home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+
+        let content = toml::to_string_pretty(self)?;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(&path)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+
+        // `mode` only applies on creation, so tighten a file that already existed
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        }
+
+        file.write_all(content.as_bytes())
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    pub fn token(&self, registry: &str) -> Option<&str> {
+        self.registries.get(registry).map(|creds| creds.token.as_str())
+    }
+}
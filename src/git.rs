@@ -0,0 +1,156 @@
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Runs `git submodule add <url> <path>`.
+pub fn submodule_add(url: &str, path: &str) -> Result<()> {
+    let status = Command::new("git")
+        .args(["submodule", "add", url, path])
+        .status()
+        .context("failed to run git. Is git installed?")?;
+
+    if !status.success() {
+        bail!("git submodule add failed for {}", url);
+    }
+
+    Ok(())
+}
+
+/// Runs `git submodule update` for the given paths, initializing them first
+/// or pulling the latest remote commit as requested.
+pub fn submodule_update(paths: &[String], init: bool, remote: bool) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.args(["submodule", "update"]);
+    if init {
+        cmd.arg("--init");
+    }
+    if remote {
+        cmd.arg("--remote");
+    }
+    cmd.arg("--").args(paths);
+
+    let status = cmd.status().context("failed to run git. Is git installed?")?;
+    if !status.success() {
+        bail!("git submodule update failed");
+    }
+
+    Ok(())
+}
+
+/// Paths of the submodules registered in .gitmodules.
+pub fn submodule_paths() -> Vec<String> {
+    let output = Command::new("git")
+        .args(["config", "-f", ".gitmodules", "--get-regexp", r"^submodule\..*\.path$"])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once(' ').map(|(_, path)| path.to_string()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Submodules that are registered but not checked out yet.
+pub fn uninitialized_submodules() -> Vec<String> {
+    let output = Command::new("git")
+        .args(["submodule", "status"])
+        .output();
+
+    match output {
+        // Uninitialized entries are prefixed with '-': "-<sha> <path>"
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.strip_prefix('-'))
+            .filter_map(|rest| rest.split_whitespace().nth(1).map(|p| p.to_string()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Clones `url` into `path`, checking out `reference` (a branch or tag) when given.
+pub fn clone(url: &str, reference: Option<&str>, path: &str) -> Result<()> {
+    let mut cmd = Command::new("git");
+    cmd.args(["clone", "--depth", "1"]);
+    if let Some(reference) = reference {
+        cmd.args(["--branch", reference]);
+    }
+    cmd.args([url, path]);
+
+    let status = cmd.status().context("failed to run git. Is git installed?")?;
+    if !status.success() {
+        bail!("git clone failed for {}", url);
+    }
+
+    Ok(())
+}
+
+/// Checks out `reference` into a new detached worktree at `path`, leaving
+/// the main checkout and its uncommitted changes alone.
+pub fn worktree_add(path: &Path, reference: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["worktree", "add", "--detach"])
+        .arg(path)
+        .arg(reference)
+        .output()
+        .context("failed to run git. Is git installed?")?;
+
+    if !output.status.success() {
+        bail!("git worktree add failed for '{}': {}",
+            reference,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
+/// Removes a worktree created by `worktree_add`.
+pub fn worktree_remove(path: &Path) -> Result<()> {
+    let status = Command::new("git")
+        .args(["worktree", "remove", "--force"])
+        .arg(path)
+        .status()
+        .context("failed to run git. Is git installed?")?;
+
+    if !status.success() {
+        bail!("git worktree remove failed for {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Which of `paths` are ignored by .gitignore. Empty outside a git repository.
+pub fn ignored(paths: &[PathBuf]) -> HashSet<PathBuf> {
+    if paths.is_empty() {
+        return HashSet::new();
+    }
+
+    let child = Command::new("git")
+        .args(["check-ignore", "--stdin"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return HashSet::new();
+    };
+
+    // Fed from another thread so a long path list can't fill both pipes
+    if let Some(mut stdin) = child.stdin.take() {
+        let list: String = paths.iter().map(|p| format!("{}\n", p.display())).collect();
+        std::thread::spawn(move || stdin.write_all(list.as_bytes()));
+    }
+
+    // Exits 1 when nothing is ignored, 128 outside a repository
+    match child.wait_with_output() {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(PathBuf::from)
+            .collect(),
+        Err(_) => HashSet::new(),
+    }
+}
@@ -0,0 +1,89 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::config::ProjectConfig;
+
+pub const LOCK_FILE: &str = "project.lock";
+
+/// Format version written to new lock files.
+const LOCK_VERSION: u32 = 1;
+
+const HEADER: &str = "# This file is automatically generated by Zora.\n# Do not edit manually.\n\n";
+
+/// The resolved dependency versions in project.lock.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub packages: BTreeMap<String, LockedPackage>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    pub version: String,
+}
+
+impl Lockfile {
+    pub fn load() -> Result<Self> {
+        let content = fs::read_to_string(LOCK_FILE)
+            .with_context(|| format!("failed to read {}", LOCK_FILE))?;
+        toml::from_str(&content)
+            .with_context(|| format!("failed to parse {}", LOCK_FILE))
+    }
+
+    /// Every installed vcpkg dependency and dev-dependency at the version
+    /// vcpkg installed. Packages that aren't installed keep the version
+    /// they're locked at, since builds leave dev dependencies out; ones
+    /// that were never installed are left out.
+    pub fn resolve(config: &ProjectConfig) -> Self {
+        let installed = crate::vcpkg::installed_versions();
+        let locked = Lockfile::load().unwrap_or_default();
+        let packages = config.vcpkg_packages().into_iter()
+            .chain(config.dev_vcpkg_packages())
+            .filter_map(|name| {
+                let version = installed.get(&name)
+                    .or_else(|| locked.packages.get(&name).map(|package| &package.version))?
+                    .clone();
+                Some((name, LockedPackage { version }))
+            })
+            .collect();
+
+        Lockfile { version: LOCK_VERSION, packages }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = toml::to_string_pretty(self)
+            .with_context(|| format!("failed to serialize {}", LOCK_FILE))?;
+        fs::write(LOCK_FILE, format!("{}{}", HEADER, content))
+            .with_context(|| format!("failed to write {}", LOCK_FILE))
+    }
+}
+
+/// Rewrites project.lock from what's installed, if that changed. Projects
+/// without vcpkg dependencies don't get one created. Returns whether the
+/// file was written.
+pub fn update(config: &ProjectConfig) -> Result<bool> {
+    let lock = Lockfile::resolve(config);
+    let unchanged = if Path::new(LOCK_FILE).exists() {
+        Lockfile::load().is_ok_and(|current| current == lock)
+    } else {
+        config.vcpkg_packages().is_empty() && config.dev_vcpkg_packages().is_empty()
+    };
+    if unchanged {
+        return Ok(false);
+    }
+
+    lock.save()?;
+    Ok(true)
+}
+
+/// Whether what's installed differs from project.lock. Builds only warn
+/// about this; the lock is rewritten by add, remove and update, so that
+/// `zora verify --locked` has drift left to report.
+pub fn has_drifted(config: &ProjectConfig) -> bool {
+    Lockfile::load().map_or(true, |current| current != Lockfile::resolve(config))
+}
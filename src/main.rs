@@ -1,11 +1,32 @@
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Parser, Subcommand};
+
+use commands::build::{BuildMode, BuildOptions, PgoPhase};
+use commands::clean::CleanScope;
+use commands::run::RunOptions;
+use commands::test::TestOptions;
+use output::Verbosity;
 
 mod commands;
 mod config;
+mod credentials;
+mod git;
+mod lockfile;
+mod output;
+mod paths;
+mod vcpkg;
 
 #[derive(Parser)]
 #[command(name = "zora", about = "Zora — a powerful C/C++ build system", version)]
 struct Cli {
+    /// Write build output (.build, target, compile_commands.json) here
+    /// instead of the project directory
+    #[arg(long, global = true, value_name = "DIR")]
+    out_dir: Option<String>,
+
+    /// Copy artifacts here instead of target/ (overrides [build] target_dir)
+    #[arg(long, global = true, value_name = "DIR")]
+    target_dir: Option<String>,
+
     #[command(subcommand)]
     cmd: Commands,
 }
@@ -18,19 +39,44 @@ enum Commands {
         name: Option<String>,
         #[arg(long)]
         cpp: bool,
+        /// Objective-C (Objective-C++ with --cpp)
+        #[arg(long)]
+        objc: bool,
         #[arg(long)]
         lib: bool,
+        /// Library plus an executable frontend ([[bin]]) linked against it
+        #[arg(long, conflicts_with = "lib")]
+        bin_and_lib: bool,
+        /// Bootstrap from a template git repository
+        #[arg(long, value_name = "GIT_URL")]
+        from: Option<String>,
     },
 
-    /// Create a new zora project
+    /// Create a new zora project at PATH, or with TYPE NAME a new source
+    /// file (source, header, test, bench), a test fixture dir, or a
+    /// bin/example target registered in project.toml
     New {
+        /// Directory for the new project, or the type of file to create
+        #[arg(value_name = "PATH|TYPE")]
         path: String,
-        #[arg(long)]
+        /// Name of the file to create; leave out to create a project
+        #[arg(value_name = "NAME")]
+        file_name: Option<String>,
+        #[arg(long, conflicts_with = "file_name")]
         cpp: bool,
-        #[arg(long)]
+        /// Objective-C (Objective-C++ with --cpp)
+        #[arg(long, conflicts_with = "file_name")]
+        objc: bool,
+        #[arg(long, conflicts_with = "file_name")]
         lib: bool,
-        #[arg(long)]
+        /// Library plus an executable frontend ([[bin]]) linked against it
+        #[arg(long, conflicts_with_all = ["lib", "file_name"])]
+        bin_and_lib: bool,
+        #[arg(long, conflicts_with = "file_name")]
         name: Option<String>,
+        /// With `source`, also create a test for it under tests/
+        #[arg(long, requires = "file_name")]
+        with_test: bool,
     },
 
     /// Build the project
@@ -41,8 +87,9 @@ enum Commands {
         release: bool,
         #[arg(long)]
         profile: Option<String>,
-        #[arg(short, long)]
-        verbose: bool,
+        /// Increase output detail (-v commands, -vv tool output, -vvv tracing)
+        #[arg(short, long, action = ArgAction::Count)]
+        verbose: u8,
         #[arg(short, long)]
         jobs: Option<usize>,
         #[arg(long)]
@@ -51,10 +98,55 @@ enum Commands {
         all_features: bool,
         #[arg(long)]
         no_default_features: bool,
-        #[arg(long)]
+        /// Build with [toolchain.<TARGET>], into the <mode>-<TARGET> slot;
+        /// without such a toolchain TARGET is the CMake system name
+        #[arg(long, value_name = "TARGET")]
         target: Option<String>,
-#[arg(long)]
-    static_linking: bool,
+        #[arg(long)]
+        static_linking: bool,
+        /// Build into a named slot (.build/<NAME>, target/<NAME>) instead of the mode
+        #[arg(long, value_name = "NAME")]
+        build_dir: Option<String>,
+        /// Don't start new jobs while the system load average is above N
+        #[arg(short = 'l', long, value_name = "N")]
+        load_average: Option<f64>,
+        /// Keep compiling past errors and list every failing file at the end
+        #[arg(short = 'k', long)]
+        keep_going: bool,
+        /// Build instrumented for profile-guided optimization (implies --release)
+        #[arg(long, conflicts_with = "pgo_use")]
+        pgo_generate: bool,
+        /// Rebuild using the profile data collected in target/pgo (implies --release)
+        #[arg(long)]
+        pgo_use: bool,
+        /// Link with mold, lld or gold ("system" for the compiler default)
+        #[arg(long, value_name = "LINKER")]
+        linker: Option<String>,
+        /// Run the compiler's static analyzer over the sources instead of building
+        #[arg(long)]
+        analyze: bool,
+        /// Drop zora's default warning flags (-Wall, -Wextra, -Wpedantic)
+        #[arg(long)]
+        no_default_flags: bool,
+        /// Fail if any dependency is pinned as "*" (as with [deps] strict = true)
+        #[arg(long)]
+        strict_deps: bool,
+        /// Regenerate and reconfigure even if no inputs changed since the last build
+        #[arg(long)]
+        force: bool,
+        /// CMake generator for this build: ninja, make or default
+        #[arg(long, value_name = "GENERATOR")]
+        generator: Option<String>,
+        /// Build once per vcpkg triplet (e.g. arm64-osx,x64-osx) into
+        /// <slot>-<triplet>; macOS triplets are merged with lipo
+        #[arg(long, value_name = "TRIPLETS", value_delimiter = ',')]
+        triplets: Vec<String>,
+        /// Build a universal macOS binary (arm64 + x86_64)
+        #[arg(long, conflicts_with = "triplets")]
+        universal: bool,
+        /// Unity build for this run, as with [build] unity_build
+        #[arg(long)]
+        unity: bool,
     },
 
     /// Build and run the project
@@ -63,10 +155,36 @@ enum Commands {
         name: Option<String>,
         #[arg(short, long)]
         release: bool,
-        #[arg(short, long)]
-        verbose: bool,
+        /// Increase output detail (-v commands, -vv tool output, -vvv tracing)
+        #[arg(short, long, action = ArgAction::Count)]
+        verbose: u8,
         #[arg(short, long)]
         jobs: Option<usize>,
+        /// Run every executable the project builds, one after another
+        #[arg(long)]
+        all: bool,
+        /// With --all, continue after a program fails
+        #[arg(long, requires = "all")]
+        keep_going: bool,
+        /// Build and run from a named slot (see `zora build --build-dir`)
+        #[arg(long, value_name = "NAME")]
+        build_dir: Option<String>,
+        /// Kill the program after this long (e.g. 30s, 500ms) and exit with code 124
+        #[arg(long, value_name = "DURATION", value_parser = commands::run::parse_duration)]
+        timeout: Option<std::time::Duration>,
+        /// Save the args, environment and output of the run to FILE (JSON)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["all", "replay"])]
+        record: Option<std::path::PathBuf>,
+        /// Re-run the invocation recorded in FILE and diff the output against it
+        #[arg(long, value_name = "FILE", conflicts_with = "all")]
+        replay: Option<std::path::PathBuf>,
+        /// Forward only the program's stdout, without zora's banners, and exit
+        /// with its exit code (for programs that print JSON or other data)
+        #[arg(long, conflicts_with_all = ["all", "record", "replay"])]
+        json_output: bool,
+        /// Run the existing executable without building first
+        #[arg(long)]
+        no_build: bool,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
@@ -74,6 +192,12 @@ enum Commands {
     /// Add vcpkg packages to the project
     Add {
         packages: Vec<String>,
+        /// Add a git submodule dependency: zora add --submodule <url> <path>
+        #[arg(long, value_name = "URL")]
+        submodule: Option<String>,
+        /// Add to [dev-dependencies], used only by `zora test` and `zora bench`
+        #[arg(long)]
+        dev: bool,
     },
 
     /// Remove vcpkg packages from the project
@@ -83,8 +207,15 @@ enum Commands {
 
     /// Clean build artifacts
     Clean {
+        /// Also remove the vcpkg packages in .build/vcpkg_installed
         #[arg(long)]
         all: bool,
+        /// Only remove the intermediate CMake/object dir (.build), keeping target/
+        #[arg(long, conflicts_with = "target_only")]
+        build_only: bool,
+        /// Only remove the final artifacts in target/, keeping .build
+        #[arg(long)]
+        target_only: bool,
     },
 
     /// Run tests
@@ -93,28 +224,80 @@ enum Commands {
         release: bool,
         #[arg(short, long)]
         test: Option<String>,
+        /// Only run tests tagged with TAG (`// zora-tags: ...`); repeatable
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Skip tests tagged with TAG; repeatable
+        #[arg(long = "skip-tag", value_name = "TAG")]
+        skip_tags: Vec<String>,
+        /// List matching tests and their tags without running them
+        #[arg(long)]
+        list: bool,
+        /// Compare the last run in [tests] history_dir against earlier ones
+        #[arg(long)]
+        trend: bool,
+        /// Show test output even for passing tests
+        #[arg(long)]
+        nocapture: bool,
+        /// Compile the tests without running them
+        #[arg(long, conflicts_with_all = ["list", "trend", "nocapture"])]
+        compile_only: bool,
+        /// Result format: human, tap (TAP v13 on stdout) or junit
+        /// (target/<mode>/tests/results.xml)
+        #[arg(long, default_value = "human", conflicts_with_all = ["list", "trend", "compile_only"])]
+        format: String,
     },
 
     /// Check project without building
     Check {
-        #[arg(short, long)]
-        verbose: bool,
+        /// Increase output detail (-v commands, -vv tool output, -vvv tracing)
+        #[arg(short, long, action = ArgAction::Count)]
+        verbose: u8,
     },
 
     /// Format source code using clang-format
     Fmt {
         #[arg(long)]
         check: bool,
+        /// Write a .clang-format generated from [fmt] instead of formatting
+        #[arg(long)]
+        write_config: bool,
+        /// Overwrite an existing .clang-format
+        #[arg(long, requires = "write_config")]
+        force: bool,
     },
 
     /// Lint source code using clang-tidy
     Lint {
         #[arg(long)]
         fix: bool,
+        /// Write a .clang-tidy generated from [lint] instead of linting
+        #[arg(long)]
+        write_config: bool,
+        /// Overwrite an existing .clang-tidy
+        #[arg(long, requires = "write_config")]
+        force: bool,
     },
 
     /// Show project information
-    Info,
+    Info {
+        /// Print the fully merged build settings instead of the manifest summary
+        #[arg(long)]
+        effective_cmake: bool,
+        #[arg(short, long, requires = "effective_cmake")]
+        release: bool,
+        #[arg(long, requires = "effective_cmake")]
+        profile: Option<String>,
+        #[arg(long, requires = "effective_cmake")]
+        features: Vec<String>,
+        #[arg(long, requires = "effective_cmake")]
+        all_features: bool,
+        #[arg(long, requires = "effective_cmake")]
+        no_default_features: bool,
+        /// Show the disk space each installed vcpkg dependency takes up
+        #[arg(long, conflicts_with = "effective_cmake")]
+        deps_size: bool,
+    },
 
     /// List all dependencies
     Deps {
@@ -127,18 +310,26 @@ enum Commands {
         query: String,
     },
 
-    /// Create a new source file
-    New_ {
-        #[arg(value_name = "TYPE")]
-        file_type: String,
-        #[arg(value_name = "NAME")]
-        name: String,
-    },
-
     /// Benchmark the project
     Bench {
         #[arg(short, long)]
         bench: Option<String>,
+        /// Time the whole release executable instead of benches/
+        #[arg(long, conflicts_with = "bench")]
+        program: bool,
+        /// Benchmark a git revision (checked out in a temporary worktree) and
+        /// compare it against the working tree
+        #[arg(long, value_name = "REF", conflicts_with = "program")]
+        compare: Option<String>,
+        /// Untimed runs before measuring (with --program)
+        #[arg(long, default_value_t = 3, requires = "program")]
+        warmup: usize,
+        /// Timed runs (with --program)
+        #[arg(long, default_value_t = 10, requires = "program")]
+        runs: usize,
+        /// Arguments passed to the program, after `--`
+        #[arg(last = true)]
+        args: Vec<String>,
     },
 
     /// Generate documentation
@@ -151,29 +342,66 @@ enum Commands {
     Watch {
         #[arg(default_value = "build")]
         command: String,
+        /// Run only the tests matching each changed file (src/foo.c -> tests/test_foo.c)
+        #[arg(long)]
+        test_on_save: bool,
+        /// Shell command to run after each successful rebuild
+        #[arg(long)]
+        on_success: Option<String>,
+        /// Shell command to run after each failed rebuild
+        #[arg(long)]
+        on_failure: Option<String>,
+        /// Scan for changes every INTERVAL (default 1s), e.g. --poll=500ms
+        #[arg(long, value_name = "INTERVAL", num_args = 0..=1, require_equals = true,
+            default_missing_value = "1s", value_parser = commands::run::parse_duration)]
+        poll: Option<std::time::Duration>,
     },
 
     /// Package the project for distribution
     Package {
         #[arg(short, long, default_value = "tar")]
         format: String,
+        /// Include split debug info (see [build] split_debug)
+        #[arg(long)]
+        debug_info: bool,
     },
 
     /// Install the built executable
     Install {
         #[arg(long)]
         prefix: Option<String>,
+        /// Stage files under this directory instead of the live prefix
+        #[arg(long)]
+        destdir: Option<String>,
+        /// Install split debug info next to the executable
+        #[arg(long)]
+        debug_info: bool,
     },
 
     /// Uninstall the executable
     Uninstall {
         #[arg(long)]
         prefix: Option<String>,
+        /// Stage files under this directory instead of the live prefix
+        #[arg(long)]
+        destdir: Option<String>,
+    },
+
+    /// Install all dependencies without building the project
+    Fetch,
+
+    /// Export the project for use without zora (formats: makefile, presets)
+    Export {
+        #[arg(value_name = "FORMAT")]
+        format: String,
     },
 
     /// Update vcpkg packages
     Update {
         packages: Vec<String>,
+        /// List the version changes without applying them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show build cache statistics
@@ -194,15 +422,24 @@ enum Commands {
         action: WorkspaceAction,
     },
 
+    /// Visualize the project structure (build: CMake target graph)
+    Graph {
+        #[command(subcommand)]
+        action: GraphAction,
+    },
+
     /// Manage project features
     Features {
         #[command(subcommand)]
         action: FeatureAction,
     },
 
-    /// Run arbitrary scripts
+    /// Run a script from [scripts], or list them when no name is given
     Script {
-        name: String,
+        name: Option<String>,
+        /// Arguments passed to the script as $1, $2, ..., after `--`
+        #[arg(last = true)]
+        args: Vec<String>,
     },
 
     /// Publish package to registry
@@ -213,14 +450,36 @@ enum Commands {
         registry: Option<String>,
     },
 
+    /// Save a registry token to ~/.config/zora/credentials.toml
+    Login {
+        registry: Option<String>,
+    },
+
+    /// Remove a saved registry token
+    Logout {
+        registry: Option<String>,
+    },
+
     /// Verify project integrity
     Verify {
+        /// Fail if any dependency in project.lock isn't installed at its
+        /// locked version
         #[arg(long)]
         locked: bool,
+        /// With --locked, also check that project.lock matches project.toml
+        #[arg(long, requires = "locked")]
+        deps: bool,
+        /// Initialize submodule dependencies that aren't checked out
+        #[arg(long)]
+        fix: bool,
+        /// Fail if any dependency is pinned as "*" (as with [deps] strict = true)
+        #[arg(long)]
+        strict_deps: bool,
     },
 
     /// Generate shell completions
     Completions {
+        /// bash, zsh, fish, powershell or elvish
         shell: String,
     },
 
@@ -229,10 +488,89 @@ enum Commands {
         file: Option<String>,
     },
 
-    /// Show build tree
+    /// Show the dependency tree, including path dependencies' own deps
     Tree {
-        #[arg(long)]
+        /// Only show dependencies up to N levels deep
+        #[arg(long, value_name = "N")]
         depth: Option<usize>,
+        /// List packages that appear more than once in the dependency graph,
+        /// with how each is pulled in, and flag version conflicts
+        #[arg(short, long, conflicts_with = "depth")]
+        duplicates: bool,
+    },
+
+    /// Compile a single file to assembly or LLVM IR
+    Asm {
+        file: String,
+        /// Emit LLVM IR instead of assembly (clang only)
+        #[arg(long)]
+        ir: bool,
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Report the licenses of vcpkg dependencies
+    Licenses {
+        /// Concatenate all license texts into this file (e.g. NOTICE)
+        #[arg(long)]
+        output: Option<String>,
+    },
+
+    /// Run a libFuzzer target from fuzz/
+    Fuzz {
+        target: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Check that the toolchain and vcpkg are set up correctly
+    Doctor {
+        /// Attempt safe fixes: bootstrap vcpkg, create the build directory,
+        /// regenerate compile_commands.json
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Refresh generated boilerplate (.gitignore, .editorconfig) from the latest templates
+    Upgrade {
+        /// Replace files that have no recorded original to merge against
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print the compile_commands.json entry for a single file
+    Compiledb {
+        #[arg(long)]
+        file: String,
+        #[arg(short, long)]
+        release: bool,
+        #[arg(long)]
+        profile: Option<String>,
+        #[arg(long)]
+        features: Vec<String>,
+        #[arg(long)]
+        all_features: bool,
+        #[arg(long)]
+        no_default_features: bool,
+    },
+
+    /// Count lines of code, comments and blanks across the source and include dirs
+    Metrics {
+        /// Print the metrics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print the path of a built executable or library
+    ArtifactPath {
+        /// A [[bin]] to look up instead of the project's own artifact
+        #[arg(short, long)]
+        name: Option<String>,
+        #[arg(short, long)]
+        release: bool,
+        /// Look in a named slot (see `zora build --build-dir`)
+        #[arg(long, value_name = "NAME", conflicts_with = "release")]
+        build_dir: Option<String>,
     },
 }
 
@@ -245,12 +583,25 @@ enum CacheAction {
 
 #[derive(Subcommand)]
 enum WorkspaceAction {
+    /// Add a [workspace] section listing the subdirectories that are projects
     Init,
+    /// Add a directory with a project.toml to the workspace members
     Add { path: String },
+    /// Remove a directory from the workspace members
     Remove { path: String },
+    /// List the workspace members with their names and versions
     List,
 }
 
+#[derive(Subcommand)]
+enum GraphAction {
+    /// Write the CMake target graph to target/graph/
+    Build {
+        #[arg(short, long)]
+        release: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum FeatureAction {
     List,
@@ -261,58 +612,160 @@ enum FeatureAction {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
+    // Absolutized up front so commands that change directory still agree
+    // on where the output lives
+    if let Some(dir) = cli.out_dir.map(std::path::PathBuf::from).or_else(paths::out_dir) {
+        let dir = if dir.is_absolute() { dir } else { std::env::current_dir()?.join(dir) };
+        std::env::set_var(paths::OUT_DIR_ENV, dir);
+    }
+    let target_dir = cli.target_dir.map(std::path::PathBuf::from)
+        .or_else(paths::target_dir)
+        .or_else(|| {
+            let config = config::ProjectConfig::load().ok()?;
+            config.build.target_dir.map(std::path::PathBuf::from)
+        });
+    if let Some(dir) = target_dir {
+        let dir = if dir.is_absolute() { dir } else { std::env::current_dir()?.join(dir) };
+        std::env::set_var(paths::TARGET_DIR_ENV, dir);
+    }
+
     match cli.cmd {
-        Commands::Init { name, cpp, lib } => {
-            commands::init::run(name, cpp, lib)?
+        Commands::Init { name, cpp, objc, lib, bin_and_lib, from } => {
+            commands::init::run(name, cpp, objc, lib, bin_and_lib, from)?
         },
 
-        Commands::New { path, cpp, lib, name } => {
-            commands::new_project::run(path, cpp, lib, name)?
+        Commands::New { path, file_name, cpp, objc, lib, bin_and_lib, name, with_test } => match file_name {
+            Some(file_name) => commands::new::run(&path, &file_name, with_test)?,
+            None => commands::new_project::run(path, cpp, objc, lib, bin_and_lib, name)?,
         },
-        
-Commands::Build { name, release, profile, verbose, jobs, features, all_features, no_default_features, target, static_linking } => {
+
+        Commands::Build { name, release, profile, verbose, jobs, features, all_features, no_default_features, target, static_linking, build_dir, load_average, keep_going, pgo_generate, pgo_use, linker, analyze, no_default_flags, strict_deps, force, generator, triplets, universal, unity } => {
+            let release = release || pgo_generate || pgo_use;
             let mode = profile.as_deref()
                 .or(if release { Some("release") } else { Some("dev") })
                 .unwrap();
-            commands::build::run(name, mode, verbose, jobs, features, all_features, no_default_features, target, static_linking)?
+            let pgo = match (pgo_generate, pgo_use) {
+                (true, _) => Some(PgoPhase::Generate),
+                (_, true) => Some(PgoPhase::Use),
+                _ => None,
+            };
+            if analyze {
+                commands::analyze::run(mode, &features, Verbosity::new(verbose))?;
+                return Ok(());
+            }
+            let triplets = if universal {
+                commands::build::UNIVERSAL_TRIPLETS.iter().map(|t| t.to_string()).collect()
+            } else {
+                triplets
+            };
+            // Cross builds don't share a slot with host builds
+            let build_dir = build_dir.or_else(|| target.as_ref().map(|t| format!("{}-{}", mode, t)));
+            let opts = BuildOptions {
+                name,
+                verbosity: Verbosity::new(verbose),
+                jobs,
+                features,
+                all_features,
+                no_default_features,
+                target,
+                static_link: static_linking,
+                build_dir,
+                load_average,
+                keep_going,
+                pgo,
+                linker,
+                no_default_flags,
+                strict_deps,
+                force,
+                generator,
+                unity,
+                ..BuildOptions::new(mode)
+            };
+            if commands::workspace::is_root() {
+                commands::workspace::build(opts, &triplets)?
+            } else if triplets.is_empty() {
+                commands::build::run(opts)?
+            } else {
+                commands::build::run_triplets(opts, &triplets)?
+            }
         },
         
-        Commands::Run { name, release, verbose, jobs, args } => {
+        Commands::Run { name, release, verbose, jobs, all, keep_going, build_dir, timeout, record, replay, json_output, no_build, args } => {
             let mode = if release { "release" } else { "dev" };
-            commands::run::run(name, mode, verbose, jobs, args)?
-        },
-
-        Commands::Add { packages } => {
-            commands::add::run(packages)?
+            commands::run::run(RunOptions {
+                name,
+                mode: mode.to_string(),
+                verbosity: Verbosity::new(verbose),
+                jobs,
+                args,
+                all,
+                keep_going,
+                build_dir,
+                timeout,
+                record,
+                replay,
+                json_output,
+                no_build,
+            })?
+        },
+
+        Commands::Add { packages, submodule, dev } => {
+            commands::add::run(packages, submodule, dev)?
         },
 
         Commands::Remove { packages } => {
             commands::remove::run(packages)?
         },
 
-        Commands::Clean { all } => {
-            commands::clean::run(all)?
+        Commands::Clean { all, build_only, target_only } => {
+            commands::clean::run(CleanScope {
+                build: !target_only,
+                target: !build_only,
+                vcpkg: all,
+            })?
         },
 
-        Commands::Test { release, test } => {
-            let mode = if release { "release" } else { "dev" };
-            commands::test::run(mode, test)?
+        Commands::Test { release, test, tags, skip_tags, list, trend, nocapture, compile_only, format } => {
+            commands::test::run(TestOptions {
+                mode: if release { BuildMode::Release } else { BuildMode::Dev },
+                test,
+                tags,
+                skip_tags,
+                list,
+                trend,
+                nocapture,
+                compile_only,
+                format,
+            })?
         },
 
         Commands::Check { verbose } => {
-            commands::check::run(verbose)?
+            commands::check::run(Verbosity::new(verbose))?
         },
 
-        Commands::Fmt { check } => {
-            commands::fmt::run(check)?
+        Commands::Fmt { check, write_config, force } => {
+            commands::fmt::run(check, write_config, force)?
         },
 
-        Commands::Lint { fix } => {
-            commands::lint::run(fix)?
+        Commands::Lint { fix, write_config, force } => {
+            commands::lint::run(fix, write_config, force)?
         },
 
-        Commands::Info => {
-            commands::info::run()?
+        Commands::Info { effective_cmake, release, profile, features, all_features, no_default_features, deps_size } => {
+            if deps_size {
+                commands::info::deps_size()?
+            } else if effective_cmake {
+                let mode = profile.as_deref()
+                    .unwrap_or(if release { "release" } else { "dev" });
+                commands::info::effective(BuildOptions {
+                    features,
+                    all_features,
+                    no_default_features,
+                    ..BuildOptions::new(mode)
+                })?
+            } else {
+                commands::info::run()?
+            }
         },
 
         Commands::Deps { tree } => {
@@ -323,36 +776,46 @@ Commands::Build { name, release, profile, verbose, jobs, features, all_features,
             commands::search::run(query)?
         },
 
-        Commands::New_ { file_type, name } => {
-            commands::new::run(&file_type, &name)?
-        },
-
-        Commands::Bench { bench } => {
-            commands::bench::run(bench)?
+        Commands::Bench { bench, program, compare, warmup, runs, args } => {
+            if program {
+                commands::bench::run_program(warmup, runs, args)?
+            } else if let Some(reference) = compare {
+                commands::bench::compare(&reference, bench)?
+            } else {
+                commands::bench::run(bench)?
+            }
         },
 
         Commands::Doc { open } => {
             commands::doc::run(open)?
         },
 
-        Commands::Watch { command } => {
-            commands::watch::run(&command)?
+        Commands::Watch { command, test_on_save, on_success, on_failure, poll } => {
+            commands::watch::run(&command, test_on_save, on_success, on_failure, poll)?
+        },
+
+        Commands::Package { format, debug_info } => {
+            commands::package::run(&format, debug_info)?
+        },
+
+        Commands::Install { prefix, destdir, debug_info } => {
+            commands::install::run(prefix, destdir, debug_info)?
         },
 
-        Commands::Package { format } => {
-            commands::package::run(&format)?
+        Commands::Uninstall { prefix, destdir } => {
+            commands::uninstall::run(prefix, destdir)?
         },
 
-        Commands::Install { prefix } => {
-            commands::install::run(prefix)?
+        Commands::Fetch => {
+            commands::fetch::run()?
         },
 
-        Commands::Uninstall { prefix } => {
-            commands::uninstall::run(prefix)?
+        Commands::Export { format } => {
+            commands::export::run(&format)?
         },
 
-        Commands::Update { packages } => {
-            commands::update::run(packages)?
+        Commands::Update { packages, dry_run } => {
+            commands::update::run(packages, dry_run)?
         },
 
         Commands::Cache { action } => {
@@ -376,6 +839,12 @@ Commands::Build { name, release, profile, verbose, jobs, features, all_features,
             }
         },
 
+        Commands::Graph { action } => {
+            match action {
+                GraphAction::Build { release } => commands::graph::build(release)?,
+            }
+        },
+
         Commands::Features { action } => {
             match action {
                 FeatureAction::List => commands::features::list()?,
@@ -384,16 +853,24 @@ Commands::Build { name, release, profile, verbose, jobs, features, all_features,
             }
         },
 
-        Commands::Script { name } => {
-            commands::script::run(name)?
+        Commands::Script { name, args } => {
+            commands::script::run(name, args)?
         },
 
         Commands::Publish { dry_run, registry } => {
             commands::publish::run(dry_run, registry)?
         },
 
-        Commands::Verify { locked } => {
-            commands::verify::run(locked)?
+        Commands::Login { registry } => {
+            commands::login::run(registry)?
+        },
+
+        Commands::Logout { registry } => {
+            commands::logout::run(registry)?
+        },
+
+        Commands::Verify { locked, deps, fix, strict_deps } => {
+            commands::verify::run(locked, deps, fix, strict_deps)?
         },
 
         Commands::Completions { shell } => {
@@ -404,8 +881,47 @@ Commands::Build { name, release, profile, verbose, jobs, features, all_features,
             commands::expand::run(file)?
         },
 
-        Commands::Tree { depth } => {
-            commands::tree::run(depth)?
+        Commands::Tree { depth, duplicates } => {
+            commands::tree::run(depth, duplicates)?
+        },
+
+        Commands::Asm { file, ir, output } => {
+            commands::asm::run(file, ir, output)?
+        },
+
+        Commands::Licenses { output } => {
+            commands::licenses::run(output)?
+        },
+
+        Commands::Fuzz { target, args } => {
+            commands::fuzz::run(target, args)?
+        },
+
+        Commands::Doctor { fix } => {
+            commands::doctor::run(fix)?
+        },
+
+        Commands::Upgrade { force } => {
+            commands::upgrade::run(force)?
+        },
+
+        Commands::Compiledb { file, release, profile, features, all_features, no_default_features } => {
+            let mode = profile.as_deref()
+                .unwrap_or(if release { "release" } else { "dev" });
+            commands::compiledb::run(file, BuildOptions {
+                features,
+                all_features,
+                no_default_features,
+                ..BuildOptions::new(mode)
+            })?
+        },
+
+        Commands::Metrics { json } => {
+            commands::metrics::run(json)?
+        },
+
+        Commands::ArtifactPath { name, release, build_dir } => {
+            commands::artifact_path::run(name, release, build_dir)?
         },
     }
 
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::process::Command;
+
+/// How much detail zora prints, controlled by stacking `-v`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Verbosity(u8);
+
+impl Verbosity {
+    pub fn new(level: u8) -> Self {
+        Verbosity(level)
+    }
+
+    /// `-v`: show the external commands zora runs.
+    pub fn commands(self) -> bool {
+        self.0 >= 1
+    }
+
+    /// `-vv`: stream the full output of external tools.
+    pub fn tool_output(self) -> bool {
+        self.0 >= 2
+    }
+
+    /// `-vvv`: trace zora's own decisions (resolved flags, paths, timings).
+    pub fn trace(self) -> bool {
+        self.0 >= 3
+    }
+}
+
+/// Formats a command the way it would be typed in a shell.
+pub fn command_line(cmd: &Command) -> String {
+    let mut parts = vec![cmd.get_program().to_string_lossy().into_owned()];
+    parts.extend(cmd.get_args().map(|a| a.to_string_lossy().into_owned()));
+    parts.join(" ")
+}
+
+/// Runs an external tool, honoring the verbosity level. Below `-vv` the
+/// tool's output is captured and only printed if it fails.
+pub fn run_tool(cmd: &mut Command, verbosity: Verbosity) -> Result<bool> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+
+    if verbosity.commands() {
+        println!("  {} {}", "Running".dimmed(), command_line(cmd));
+    }
+
+    if verbosity.tool_output() {
+        let status = cmd.status()
+            .with_context(|| format!("failed to run {}", program))?;
+        return Ok(status.success());
+    }
+
+    let output = cmd.output()
+        .with_context(|| format!("failed to run {}", program))?;
+
+    if !output.status.success() {
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(output.status.success())
+}
+
+/// Runs an external tool with its output passed through at every verbosity
+/// level, for the compile step, whose warnings matter on successful builds
+/// too.
+pub fn stream_tool(cmd: &mut Command, verbosity: Verbosity) -> Result<bool> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+
+    if verbosity.commands() {
+        println!("  {} {}", "Running".dimmed(), command_line(cmd));
+    }
+
+    let status = cmd.status()
+        .with_context(|| format!("failed to run {}", program))?;
+    Ok(status.success())
+}
+
+/// Prints an internal tracing line at `-vvv`.
+pub fn trace(verbosity: Verbosity, message: impl AsRef<str>) {
+    if verbosity.trace() {
+        println!("  {} {}", "trace".magenta(), message.as_ref());
+    }
+}
@@ -0,0 +1,64 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// Relocates everything zora writes while building (set by the global
+/// `--out-dir` flag), so the source tree itself can be read-only.
+pub const OUT_DIR_ENV: &str = "ZORA_OUT_DIR";
+
+/// Replaces `target/` alone (set by the global `--target-dir` flag or
+/// `[build] target_dir`), e.g. to put artifacts on a RAM disk or a shared
+/// CI cache. Takes precedence over the out dir for artifacts.
+pub const TARGET_DIR_ENV: &str = "ZORA_TARGET_DIR";
+
+/// The configured out dir, if any.
+pub fn out_dir() -> Option<PathBuf> {
+    env::var_os(OUT_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The configured target dir, if any.
+pub fn target_dir() -> Option<PathBuf> {
+    env::var_os(TARGET_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+fn under_out_dir(name: &str) -> PathBuf {
+    match out_dir() {
+        Some(dir) => dir.join(name),
+        None => PathBuf::from(name),
+    }
+}
+
+/// Root of the per-slot CMake build dirs (`.build/`).
+pub fn build_root() -> PathBuf {
+    under_out_dir(".build")
+}
+
+/// Root of the copied artifacts (`target/`).
+pub fn target_root() -> PathBuf {
+    target_dir().unwrap_or_else(|| under_out_dir("target"))
+}
+
+/// Where the compile_commands.json link for editors is placed.
+pub fn compile_commands() -> PathBuf {
+    under_out_dir("compile_commands.json")
+}
+
+/// The project root as seen from a generated CMakeLists.txt in
+/// `<build_root>/<slot>`: relative in-tree, absolute when relocated. It's
+/// used before `project()`, so it can't go through PROJECT_SOURCE_DIR.
+pub fn cmake_project_root() -> std::io::Result<String> {
+    if out_dir().is_none() {
+        return Ok("${CMAKE_CURRENT_SOURCE_DIR}/../..".to_string());
+    }
+
+    let root = env::current_dir()?;
+    Ok(cmake_path(&root))
+}
+
+/// CMake wants forward slashes, even on Windows.
+pub fn cmake_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
@@ -0,0 +1,333 @@
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::{DependencySpec, ProjectConfig};
+use crate::output::{run_tool, Verbosity};
+use crate::paths;
+
+/// Where vcpkg's CMake toolchain lives relative to its root.
+const TOOLCHAIN_FILE: &str = "scripts/buildsystems/vcpkg.cmake";
+
+const REPOSITORY: &str = "https://github.com/microsoft/vcpkg";
+
+/// The manifest vcpkg installs from in manifest mode, next to project.toml.
+pub const MANIFEST_FILE: &str = "vcpkg.json";
+
+/// Manifest feature holding the dev dependencies, installed only for the
+/// commands that compile against them.
+pub const DEV_FEATURE: &str = "dev";
+
+/// Where `VCPKG_ROOT` came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RootSource {
+    /// Already set in the environment
+    Environment,
+    /// Derived from the `vcpkg` binary on PATH
+    Derived,
+}
+
+/// Locates the `vcpkg` executable on PATH, following symlinks.
+pub fn find_binary() -> Option<PathBuf> {
+    let exe_name = if cfg!(windows) { "vcpkg.exe" } else { "vcpkg" };
+    let path = env::var_os("PATH")?;
+
+    env::split_paths(&path)
+        .map(|dir| dir.join(exe_name))
+        .find(|candidate| candidate.is_file())
+        .map(|found| found.canonicalize().unwrap_or(found))
+}
+
+/// Makes sure `VCPKG_ROOT` is set for every command zora spawns. When it's
+/// missing, the root is derived from the `vcpkg` binary's install directory
+/// and exported into zora's own environment, with a warning so the user can
+/// set it permanently.
+pub fn ensure_root() -> Option<(PathBuf, RootSource)> {
+    if let Some(root) = env::var_os("VCPKG_ROOT").filter(|r| !r.is_empty()) {
+        return Some((PathBuf::from(root), RootSource::Environment));
+    }
+
+    let root = find_binary()?.parent().map(Path::to_path_buf)?;
+    if !has_toolchain(&root) {
+        return None;
+    }
+
+    env::set_var("VCPKG_ROOT", &root);
+
+    eprintln!("{} VCPKG_ROOT is not set; using {}", "Warning:".yellow(), root.display());
+    eprintln!("  Add {} to your shell profile to make this permanent",
+        format!("export VCPKG_ROOT=\"{}\"", root.display()).bright_white()
+    );
+
+    Some((root, RootSource::Derived))
+}
+
+/// Whether a vcpkg root has the CMake toolchain zora builds against.
+pub fn has_toolchain(root: &Path) -> bool {
+    root.join(TOOLCHAIN_FILE).exists()
+}
+
+/// Where manifest mode installs the project's packages: `.build/vcpkg_installed`,
+/// so it follows `--out-dir` along with the rest of the build.
+pub fn installed_dir() -> PathBuf {
+    paths::build_root().join("vcpkg_installed")
+}
+
+/// Roots vcpkg installs packages under: the project's manifest-mode
+/// `installed_dir` first, then classic mode's `$VCPKG_ROOT/installed`.
+pub fn installed_roots() -> Vec<PathBuf> {
+    let mut roots = vec![installed_dir()];
+    if let Some(root) = env::var_os("VCPKG_ROOT").filter(|r| !r.is_empty()) {
+        roots.push(Path::new(&root).join("installed"));
+    }
+    roots
+}
+
+/// Installed package name -> version, from the `<name>_<version>_<triplet>.list`
+/// files vcpkg keeps under `<root>/vcpkg/info`. Manifest-mode installs win.
+pub fn installed_versions() -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    for root in installed_roots() {
+        let Ok(entries) = std::fs::read_dir(root.join("vcpkg").join("info")) else { continue };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().is_none_or(|ext| ext != "list") {
+                continue;
+            }
+            let parsed = path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.rsplit_once('_'))
+                .and_then(|(rest, _triplet)| rest.split_once('_'));
+            if let Some((name, version)) = parsed {
+                versions.entry(name.to_string()).or_insert_with(|| version.to_string());
+            }
+        }
+    }
+    versions
+}
+
+/// The per-triplet trees (`<root>/<triplet>`) holding installed headers and
+/// libraries.
+fn installed_trees() -> Vec<PathBuf> {
+    let mut trees = vec![];
+    for root in installed_roots() {
+        let Ok(entries) = std::fs::read_dir(&root) else { continue };
+        let mut found: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.join("include").is_dir())
+            .collect();
+        found.sort();
+        trees.extend(found);
+    }
+    trees
+}
+
+/// Flags for compiles zora runs itself (tests, benchmarks) to use vcpkg
+/// packages: the installed include and lib dirs, plus what pkg-config
+/// reports for packages that ship a .pc file. Libraries without one have
+/// to be listed in `[build] libs`.
+pub fn package_flags(packages: &[String]) -> Vec<String> {
+    if packages.is_empty() {
+        return vec![];
+    }
+
+    let trees = installed_trees();
+    let mut flags = vec![];
+    for tree in &trees {
+        flags.push("-isystem".to_string());
+        flags.push(tree.join("include").display().to_string());
+        flags.push(format!("-L{}", tree.join("lib").display()));
+    }
+
+    let pkg_config_path = env::join_paths(trees.iter().map(|tree| tree.join("lib").join("pkgconfig")))
+        .unwrap_or_default();
+    for package in packages {
+        let output = Command::new("pkg-config")
+            .args(["--cflags", "--libs", package])
+            .env("PKG_CONFIG_PATH", &pkg_config_path)
+            .output();
+        if let Ok(output) = output {
+            if output.status.success() {
+                flags.extend(String::from_utf8_lossy(&output.stdout).split_whitespace().map(String::from));
+            }
+        }
+    }
+
+    flags
+}
+
+/// Rewrites the name, version and dependencies in vcpkg.json from
+/// project.toml, keeping anything else in it (`builtin-baseline`,
+/// `overrides`, ...). Optional dependencies are listed too, since vcpkg
+/// installs the whole manifest at configure time; what gets linked is still
+/// up to the build. Dev dependencies go in the `dev` feature, which builds
+/// leave off. Projects without vcpkg dependencies don't get one created.
+/// Returns whether the file was written.
+pub fn sync_manifest(config: &ProjectConfig) -> Result<bool> {
+    let existing = fs::read_to_string(MANIFEST_FILE).ok();
+    let Some(content) = render_manifest(config, existing.as_deref())? else {
+        return Ok(false);
+    };
+    if existing.as_deref() == Some(content.as_str()) {
+        return Ok(false);
+    }
+
+    fs::write(MANIFEST_FILE, content)
+        .with_context(|| format!("failed to write {}", MANIFEST_FILE))?;
+    Ok(true)
+}
+
+/// `sync_manifest` for commands that only build. With `--out-dir` the
+/// source tree is left alone, so a stale vcpkg.json is an error there.
+pub fn ensure_manifest(config: &ProjectConfig) -> Result<bool> {
+    if paths::out_dir().is_none() {
+        return sync_manifest(config);
+    }
+
+    let existing = fs::read_to_string(MANIFEST_FILE).ok();
+    if let Some(content) = render_manifest(config, existing.as_deref())? {
+        if existing.as_deref() != Some(content.as_str()) {
+            bail!("{} is out of date with project.toml. Run 'zora fetch' without --out-dir to update it.",
+                MANIFEST_FILE);
+        }
+    }
+    Ok(false)
+}
+
+/// The vcpkg.json `sync_manifest` would write over `existing`, or None when
+/// the project doesn't need one.
+fn render_manifest(config: &ProjectConfig, existing: Option<&str>) -> Result<Option<String>> {
+    let dependencies = manifest_dependencies(config.deps.iter());
+    let dev_dependencies = manifest_dependencies(config.dev_deps.iter()
+        .filter(|(name, _)| !config.deps.contains_key(*name)));
+    if existing.is_none() && dependencies.is_empty() && dev_dependencies.is_empty() {
+        return Ok(None);
+    }
+
+    let mut manifest: Map<String, Value> = match &existing {
+        Some(content) => serde_json::from_str(content)
+            .with_context(|| format!("failed to parse {}", MANIFEST_FILE))?,
+        None => Map::new(),
+    };
+    manifest.insert("name".to_string(), json!(manifest_name(&config.name)));
+    manifest.insert("version-string".to_string(), json!(config.version));
+    manifest.insert("dependencies".to_string(), Value::Array(dependencies));
+
+    let mut features = match manifest.remove("features") {
+        Some(Value::Object(features)) => features,
+        _ => Map::new(),
+    };
+    if dev_dependencies.is_empty() {
+        features.remove(DEV_FEATURE);
+    } else {
+        features.insert(DEV_FEATURE.to_string(), json!({
+            "description": "Dev dependencies for tests and benchmarks",
+            "dependencies": dev_dependencies,
+        }));
+    }
+    if !features.is_empty() {
+        manifest.insert("features".to_string(), Value::Object(features));
+    }
+
+    Ok(Some(serde_json::to_string_pretty(&manifest)? + "\n"))
+}
+
+/// `"name"`, or `{ "name", "features" }` for dependencies asking for
+/// features, sorted by name. Path dependencies are left out.
+fn manifest_dependencies<'a>(deps: impl Iterator<Item = (&'a String, &'a DependencySpec)>) -> Vec<Value> {
+    let specs: BTreeMap<&String, &DependencySpec> = deps
+        .filter(|(_, spec)| spec.path().is_none())
+        .collect();
+
+    specs.into_iter()
+        .map(|(name, spec)| match spec {
+            DependencySpec::Detailed { features, .. } if !features.is_empty() => {
+                json!({ "name": name, "features": features })
+            }
+            _ => json!(name),
+        })
+        .collect()
+}
+
+/// vcpkg only takes lowercase letters, digits and dashes in a manifest name.
+fn manifest_name(name: &str) -> String {
+    let name: String = name.to_lowercase().chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    name.trim_matches('-').to_string()
+}
+
+/// `vcpkg install` of the manifest into `installed_dir`, with the `dev`
+/// feature when asked for.
+pub fn install_command(dev: bool) -> Command {
+    let mut cmd = Command::new("vcpkg");
+    cmd.arg("install")
+        .arg(format!("--x-install-root={}", installed_dir().display()));
+    if dev {
+        cmd.arg(format!("--x-feature={}", DEV_FEATURE));
+    }
+    cmd
+}
+
+/// Installs the manifest with its `dev` feature before tests or benchmarks
+/// compile against dev dependencies. Builds leave the feature off, so this
+/// also brings back what a build's configure removed.
+pub fn install_dev_deps(config: &ProjectConfig) -> Result<()> {
+    if !config.use_vcpkg || config.dev_vcpkg_packages().is_empty() {
+        return Ok(());
+    }
+    if ensure_root().is_none() {
+        bail!("VCPKG_ROOT is not set and vcpkg was not found on PATH. Run 'zora doctor' for details.");
+    }
+    ensure_manifest(config)?;
+
+    if !run_tool(&mut install_command(true), Verbosity::default())? {
+        bail!("vcpkg install of the dev dependencies failed");
+    }
+    Ok(())
+}
+
+/// Where `bootstrap` installs vcpkg: `VCPKG_ROOT` if set, else `~/.vcpkg`.
+pub fn default_root() -> Option<PathBuf> {
+    if let Some(root) = env::var_os("VCPKG_ROOT").filter(|r| !r.is_empty()) {
+        return Some(PathBuf::from(root));
+    }
+
+    let home = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })?;
+    Some(PathBuf::from(home).join(".vcpkg"))
+}
+
+/// Clones vcpkg into `root` (unless it's already there) and runs its
+/// bootstrap script, then exports `VCPKG_ROOT` for the rest of this run.
+pub fn bootstrap(root: &Path) -> Result<()> {
+    if !root.join(".git").exists() {
+        let status = Command::new("git")
+            .args(["clone", REPOSITORY])
+            .arg(root)
+            .status()
+            .context("failed to run git. Is git installed?")?;
+
+        if !status.success() {
+            bail!("failed to clone vcpkg into {}", root.display());
+        }
+    }
+
+    let script = if cfg!(windows) { "bootstrap-vcpkg.bat" } else { "bootstrap-vcpkg.sh" };
+    let status = Command::new(root.join(script))
+        .arg("-disableMetrics")
+        .current_dir(root)
+        .status()
+        .with_context(|| format!("failed to run {}", script))?;
+
+    if !status.success() {
+        bail!("{} failed", script);
+    }
+
+    env::set_var("VCPKG_ROOT", root);
+    Ok(())
+}